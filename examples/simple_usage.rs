@@ -17,6 +17,7 @@ fn main() -> anyhow::Result<()> {
             signature_data: "7321ED9434799FED...TRUNCATED...E1F1".to_string(),
             expected_address: "rExampleAddr123456789xrpL1234567890".to_string(),
             challenge: Some("example.com:1234567890:12345678-abcd-1234-abcd-123456789abc:login:rExampleAddr123456789xrpL1234567890".to_string()),
+            ..Default::default()
         };
 
         let provider = get_wallet_provider(WalletType::Xaman);
@@ -25,6 +25,7 @@ fn main() -> anyhow::Result<()> {
         signature_data: "7321ED9434799FED...TRUNCATED...E1F1".to_string(),
         expected_address: "rExampleAddr123456789xrpL1234567890".to_string(),
         challenge: Some("example.com:1234567890:12345678-abcd-1234-abcd-123456789abc:login:rExampleAddr123456789xrpL1234567890".to_string()),
+        ..Default::default()
     };
 
     let xaman_provider = get_wallet_provider(WalletType::Xaman);
@@ -52,6 +53,7 @@ fn main() -> anyhow::Result<()> {
         signature_data: "3045022100ABC123...TRUNCATED...7890".to_string(),
         expected_address: "rTestAddr789012345xrpLTest567890abc".to_string(),
         challenge: Some("example.com:1234567891:87654321-dcba-4321-dcba-987654321cba:login:rTestAddr789012345xrpLTest567890abc".to_string()),
+        ..Default::default()
     };
 
     let web3auth_provider = get_wallet_provider(WalletType::Web3Auth);
@@ -126,6 +128,7 @@ fn verify_user_login(
         signature_data: signature,
         expected_address: address,
         challenge,
+        ..Default::default()
     };
 
     let result = provider.verify(&input)?;
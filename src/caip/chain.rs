@@ -0,0 +1,198 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// CAIP-2 namespace for EVM chains
+pub const NAMESPACE_EIP155: &str = "eip155";
+
+/// CAIP-2 namespace for the XRP Ledger
+pub const NAMESPACE_XRPL: &str = "xrpl";
+
+/// CAIP-2 namespace for Solana clusters (reference = first 32 chars of the genesis hash)
+pub const NAMESPACE_SOLANA: &str = "solana";
+
+/// CAIP-2 blockchain ID (`namespace:reference`, e.g. `eip155:1`, `xrpl:0`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChainId {
+    pub namespace: String,
+    pub reference: String,
+}
+
+impl ChainId {
+    /// Creates a chain ID, validating both parts against the CAIP-2 grammar
+    pub fn new(namespace: &str, reference: &str) -> anyhow::Result<Self> {
+        let namespace_valid = (3..=8).contains(&namespace.len())
+            && namespace
+                .chars()
+                .all(|c| c == '-' || c.is_ascii_lowercase() || c.is_ascii_digit());
+        if !namespace_valid {
+            return Err(anyhow::anyhow!("Invalid CAIP-2 namespace: '{}'", namespace));
+        }
+
+        let reference_valid = (1..=32).contains(&reference.len())
+            && reference
+                .chars()
+                .all(|c| c == '-' || c == '_' || c.is_ascii_alphanumeric());
+        if !reference_valid {
+            return Err(anyhow::anyhow!("Invalid CAIP-2 reference: '{}'", reference));
+        }
+
+        Ok(ChainId {
+            namespace: namespace.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    /// EVM chain with the given EIP-155 chain ID
+    pub fn eip155(chain_id: u64) -> Self {
+        ChainId {
+            namespace: NAMESPACE_EIP155.to_string(),
+            reference: chain_id.to_string(),
+        }
+    }
+
+    /// XRPL network with the given network ID (0 = mainnet, 1 = testnet)
+    pub fn xrpl(network_id: u32) -> Self {
+        ChainId {
+            namespace: NAMESPACE_XRPL.to_string(),
+            reference: network_id.to_string(),
+        }
+    }
+
+    /// Solana cluster identified by its (truncated) genesis hash
+    pub fn solana(genesis_hash: &str) -> Self {
+        ChainId {
+            namespace: NAMESPACE_SOLANA.to_string(),
+            reference: genesis_hash.chars().take(32).collect(),
+        }
+    }
+
+    /// Human-readable chain name used in "Sign in with X" messages
+    pub fn display_name(&self) -> &str {
+        match self.namespace.as_str() {
+            NAMESPACE_EIP155 => "Ethereum",
+            NAMESPACE_XRPL => "XRPL",
+            NAMESPACE_SOLANA => "Solana",
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.reference)
+    }
+}
+
+impl FromStr for ChainId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, reference) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid CAIP-2 chain ID: '{}'", s))?;
+        ChainId::new(namespace, reference)
+    }
+}
+
+/// CAIP-10 account ID (`namespace:reference:address`, e.g. `eip155:1:0xab...`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountId {
+    pub chain_id: ChainId,
+    pub address: String,
+}
+
+impl AccountId {
+    /// Creates an account ID, validating the address against the CAIP-10 grammar
+    pub fn new(chain_id: ChainId, address: &str) -> anyhow::Result<Self> {
+        let address_valid = (1..=128).contains(&address.len())
+            && address
+                .chars()
+                .all(|c| matches!(c, '-' | '.' | '%') || c.is_ascii_alphanumeric());
+        if !address_valid {
+            return Err(anyhow::anyhow!("Invalid CAIP-10 address: '{}'", address));
+        }
+
+        Ok(AccountId {
+            chain_id,
+            address: address.to_string(),
+        })
+    }
+
+    /// Returns true if this account is `address`
+    ///
+    /// EVM addresses are compared case-insensitively (the EIP-55 checksum is optional),
+    /// addresses of other namespaces exactly.
+    pub fn matches_address(&self, address: &str) -> bool {
+        if self.chain_id.namespace == NAMESPACE_EIP155 {
+            self.address.eq_ignore_ascii_case(address)
+        } else {
+            self.address == address
+        }
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.chain_id, self.address)
+    }
+}
+
+impl FromStr for AccountId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chain, address) = s
+            .rsplit_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid CAIP-10 account ID: '{}'", s))?;
+        AccountId::new(chain.parse()?, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_id_roundtrip() {
+        let chain: ChainId = "eip155:137".parse().unwrap();
+        assert_eq!(chain, ChainId::eip155(137));
+        assert_eq!(chain.to_string(), "eip155:137");
+    }
+
+    #[test]
+    fn test_chain_id_rejects_invalid_namespace() {
+        assert!("ETH:1".parse::<ChainId>().is_err());
+        assert!("ab:1".parse::<ChainId>().is_err());
+        assert!("eip155".parse::<ChainId>().is_err());
+    }
+
+    #[test]
+    fn test_account_id_roundtrip() {
+        let account: AccountId = "xrpl:0:rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU".parse().unwrap();
+        assert_eq!(account.chain_id, ChainId::xrpl(0));
+        assert_eq!(account.address, "rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU");
+        assert_eq!(
+            account.to_string(),
+            "xrpl:0:rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU"
+        );
+    }
+
+    #[test]
+    fn test_account_id_matches_address() {
+        let evm: AccountId = "eip155:1:0x33f9D9f0348c1a4Bace2ad839903bBD47F430651"
+            .parse()
+            .unwrap();
+        assert!(evm.matches_address("0x33f9d9f0348c1a4bace2ad839903bbd47f430651"));
+        assert!(!evm.matches_address("0x0000000000000000000000000000000000000000"));
+
+        let xrpl: AccountId = "xrpl:0:rN7n7otQDd6FczFgLdSqtcsAUxDkw6fzRH".parse().unwrap();
+        assert!(xrpl.matches_address("rN7n7otQDd6FczFgLdSqtcsAUxDkw6fzRH"));
+        assert!(!xrpl.matches_address("rn7n7otqdd6fczfgldsqtcsauxdkw6fzrh"));
+    }
+
+    #[test]
+    fn test_account_id_rejects_invalid_address() {
+        assert!(AccountId::new(ChainId::eip155(1), "").is_err());
+        assert!(AccountId::new(ChainId::eip155(1), "0x12 34").is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! Chain-agnostic identifiers and sign-in messages.
+//!
+//! This module provides:
+//! - CAIP-2 chain IDs and CAIP-10 account IDs
+//! - The CAIP-122 "Sign in with X" message data model, rendered as SIWE (EIP-4361)
+//!   for `eip155` accounts and as plain text for `xrpl` and other namespaces
+//!
+//! Challenges that parse as a sign-in message are exposed on
+//! [`VerificationResult`](crate::types::VerificationResult) by every provider.
+
+pub mod chain;
+pub mod sign_in;
+
+pub use chain::{AccountId, ChainId};
+pub use sign_in::{parse_sign_in_challenge, SignInMessage};
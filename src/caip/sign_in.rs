@@ -0,0 +1,299 @@
+use super::chain::{AccountId, ChainId, NAMESPACE_EIP155};
+
+/// Suffix of the first line of every "Sign in with X" message
+const HEADER_SUFFIX: &str = " account:";
+
+/// CAIP-122 "Sign in with X" abstract data model
+///
+/// The same message renders as SIWE (EIP-4361) for `eip155` accounts and as the
+/// equivalent plain-text template for other namespaces such as `xrpl`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignInMessage {
+    /// RFC 3986 authority requesting the sign-in
+    pub domain: String,
+    /// CAIP-10 account performing the sign-in
+    pub account: AccountId,
+    /// Human-readable assertion the user signs
+    pub statement: Option<String>,
+    /// RFC 3986 URI referring to the resource that is the subject of the signing
+    pub uri: String,
+    /// Message version (currently "1")
+    pub version: String,
+    /// Randomized token to prevent replay attacks
+    pub nonce: String,
+    /// RFC 3339 timestamp of when the message was generated
+    pub issued_at: String,
+    /// RFC 3339 timestamp after which the message is no longer valid
+    pub expiration_time: Option<String>,
+    /// RFC 3339 timestamp before which the message is not yet valid
+    pub not_before: Option<String>,
+    /// System-specific identifier for the sign-in request
+    pub request_id: Option<String>,
+    /// URIs the user wishes to have resolved as part of the sign-in
+    pub resources: Vec<String>,
+}
+
+impl SignInMessage {
+    /// Chain the account belongs to
+    pub fn chain_id(&self) -> &ChainId {
+        &self.account.chain_id
+    }
+
+    /// Renders the message text that the wallet signs
+    pub fn render(&self) -> String {
+        let chain = self.chain_id();
+        let mut message = format!(
+            "{} wants you to sign in with your {}{}\n{}\n\n",
+            self.domain,
+            chain.display_name(),
+            HEADER_SUFFIX,
+            self.account.address
+        );
+
+        if let Some(statement) = &self.statement {
+            message.push_str(statement);
+            message.push('\n');
+        }
+        message.push('\n');
+
+        message.push_str(&format!("URI: {}\n", self.uri));
+        message.push_str(&format!("Version: {}\n", self.version));
+        // SIWE uses the bare EIP-155 chain ID, other namespaces the full CAIP-2 ID
+        if chain.namespace == NAMESPACE_EIP155 {
+            message.push_str(&format!("Chain ID: {}\n", chain.reference));
+        } else {
+            message.push_str(&format!("Chain ID: {}\n", chain));
+        }
+        message.push_str(&format!("Nonce: {}\n", self.nonce));
+        message.push_str(&format!("Issued At: {}", self.issued_at));
+
+        if let Some(expiration_time) = &self.expiration_time {
+            message.push_str(&format!("\nExpiration Time: {}", expiration_time));
+        }
+        if let Some(not_before) = &self.not_before {
+            message.push_str(&format!("\nNot Before: {}", not_before));
+        }
+        if let Some(request_id) = &self.request_id {
+            message.push_str(&format!("\nRequest ID: {}", request_id));
+        }
+        if !self.resources.is_empty() {
+            message.push_str("\nResources:");
+            for resource in &self.resources {
+                message.push_str(&format!("\n- {}", resource));
+            }
+        }
+
+        message
+    }
+
+    /// Parses a rendered "Sign in with X" message back into its data model
+    pub fn parse(message: &str) -> anyhow::Result<Self> {
+        let mut lines = message.lines();
+
+        // Header: "{domain} wants you to sign in with your {chain} account:"
+        let header = lines
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Sign-in message is empty"))?;
+        let (domain, chain_name) = header
+            .strip_suffix(HEADER_SUFFIX)
+            .and_then(|h| h.split_once(" wants you to sign in with your "))
+            .ok_or_else(|| anyhow::anyhow!("Missing sign-in message header"))?;
+
+        let address = lines
+            .next()
+            .filter(|a| !a.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing account address"))?;
+
+        // Everything between the address and "URI:" is the optional statement
+        let mut statement_lines = Vec::new();
+        let mut uri = None;
+        for line in lines.by_ref() {
+            if let Some(value) = line.strip_prefix("URI: ") {
+                uri = Some(value.to_string());
+                break;
+            }
+            if !line.is_empty() {
+                statement_lines.push(line);
+            }
+        }
+        let uri = uri.ok_or_else(|| anyhow::anyhow!("Missing 'URI' field"))?;
+
+        let mut version = None;
+        let mut chain_field = None;
+        let mut nonce = None;
+        let mut issued_at = None;
+        let mut expiration_time = None;
+        let mut not_before = None;
+        let mut request_id = None;
+        let mut resources = Vec::new();
+        let mut in_resources = false;
+
+        for line in lines {
+            if in_resources {
+                if let Some(resource) = line.strip_prefix("- ") {
+                    resources.push(resource.to_string());
+                    continue;
+                }
+                in_resources = false;
+            }
+
+            if line == "Resources:" {
+                in_resources = true;
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| anyhow::anyhow!("Malformed sign-in message line: '{}'", line))?;
+            let value = Some(value.to_string());
+            match key {
+                "Version" => version = value,
+                "Chain ID" => chain_field = value,
+                "Nonce" => nonce = value,
+                "Issued At" => issued_at = value,
+                "Expiration Time" => expiration_time = value,
+                "Not Before" => not_before = value,
+                "Request ID" => request_id = value,
+                _ => return Err(anyhow::anyhow!("Unknown sign-in message field: '{}'", key)),
+            }
+        }
+
+        let chain_field = chain_field.ok_or_else(|| anyhow::anyhow!("Missing 'Chain ID' field"))?;
+        let chain_id = if chain_field.contains(':') {
+            chain_field.parse()?
+        } else {
+            ChainId::new(&namespace_for_chain_name(chain_name), &chain_field)?
+        };
+
+        Ok(SignInMessage {
+            domain: domain.to_string(),
+            account: AccountId::new(chain_id, address)?,
+            statement: if statement_lines.is_empty() {
+                None
+            } else {
+                Some(statement_lines.join("\n"))
+            },
+            uri,
+            version: version.ok_or_else(|| anyhow::anyhow!("Missing 'Version' field"))?,
+            nonce: nonce.ok_or_else(|| anyhow::anyhow!("Missing 'Nonce' field"))?,
+            issued_at: issued_at.ok_or_else(|| anyhow::anyhow!("Missing 'Issued At' field"))?,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+/// Maps the chain name in the message header back to a CAIP-2 namespace
+fn namespace_for_chain_name(chain_name: &str) -> String {
+    match chain_name {
+        "Ethereum" => NAMESPACE_EIP155.to_string(),
+        other => other.to_lowercase(),
+    }
+}
+
+/// Tries to interpret a signed challenge as a CAIP-122 sign-in message
+///
+/// Returns `None` for challenges in any other format (e.g. "domain:timestamp:uuid:action:address").
+pub fn parse_sign_in_challenge(challenge: &str) -> Option<SignInMessage> {
+    match SignInMessage::parse(challenge) {
+        Ok(message) => {
+            log::debug!(
+                "Challenge is a CAIP-122 sign-in message for {}",
+                message.account
+            );
+            Some(message)
+        }
+        Err(e) => {
+            log::debug!("Challenge is not a CAIP-122 sign-in message: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evm_message() -> SignInMessage {
+        SignInMessage {
+            domain: "example.com".to_string(),
+            account: AccountId::new(
+                ChainId::eip155(1),
+                "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651",
+            )
+            .unwrap(),
+            statement: Some("Sign in to Example".to_string()),
+            uri: "https://example.com/login".to_string(),
+            version: "1".to_string(),
+            nonce: "32891756".to_string(),
+            issued_at: "2021-09-30T16:25:24Z".to_string(),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: vec![
+                "ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/".to_string(),
+                "https://example.com/my-web2-claim.json".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_siwe() {
+        let expected = "example.com wants you to sign in with your Ethereum account:\n\
+            0x33f9D9f0348c1a4Bace2ad839903bBD47F430651\n\
+            \n\
+            Sign in to Example\n\
+            \n\
+            URI: https://example.com/login\n\
+            Version: 1\n\
+            Chain ID: 1\n\
+            Nonce: 32891756\n\
+            Issued At: 2021-09-30T16:25:24Z\n\
+            Resources:\n\
+            - ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/\n\
+            - https://example.com/my-web2-claim.json";
+
+        assert_eq!(evm_message().render(), expected);
+    }
+
+    #[test]
+    fn test_siwe_roundtrip() {
+        let message = evm_message();
+        let parsed = SignInMessage::parse(&message.render()).unwrap();
+        assert_eq!(parsed, message);
+        assert_eq!(parsed.chain_id(), &ChainId::eip155(1));
+    }
+
+    #[test]
+    fn test_xrpl_roundtrip_without_statement() {
+        let message = SignInMessage {
+            domain: "example.com".to_string(),
+            account: AccountId::new(ChainId::xrpl(0), "rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU")
+                .unwrap(),
+            statement: None,
+            uri: "https://example.com".to_string(),
+            version: "1".to_string(),
+            nonce: "57e06102".to_string(),
+            issued_at: "2025-10-10T06:54:50Z".to_string(),
+            expiration_time: Some("2025-10-10T07:04:50Z".to_string()),
+            not_before: None,
+            request_id: Some("login".to_string()),
+            resources: Vec::new(),
+        };
+
+        let rendered = message.render();
+        assert!(rendered.starts_with("example.com wants you to sign in with your XRPL account:"));
+        assert!(rendered.contains("Chain ID: xrpl:0\n"));
+
+        assert_eq!(SignInMessage::parse(&rendered).unwrap(), message);
+    }
+
+    #[test]
+    fn test_parse_rejects_legacy_challenge() {
+        let challenge = "example.com:1760079290:57e06102-c0c8-4cf8-be97-530c2515a55d:login:rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU";
+        assert!(SignInMessage::parse(challenge).is_err());
+        assert!(parse_sign_in_challenge(challenge).is_none());
+    }
+}
@@ -0,0 +1,260 @@
+//! Minimal CBOR (RFC 8949) codec for the COSE structures CIP-30 wallets and WebAuthn
+//! authenticators return
+//!
+//! Only definite-length items are supported; neither `signData` results nor COSE keys
+//! use indefinite lengths or floats.
+
+/// Maximum nesting of arrays, maps and tags accepted while decoding
+const MAX_DEPTH: usize = 16;
+
+/// Decoded CBOR data item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CborValue {
+    /// Unsigned (major type 0) or negative (major type 1) integer
+    Int(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<CborValue>),
+    /// Key/value pairs in encoded order
+    Map(Vec<(CborValue, CborValue)>),
+    Tag(u64, Box<CborValue>),
+    Bool(bool),
+    /// `null` or `undefined`
+    Null,
+}
+
+impl CborValue {
+    /// Decodes a single data item, rejecting trailing bytes
+    pub fn decode(data: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = Reader { data };
+        let value = reader.item(0)?;
+        if !reader.data.is_empty() {
+            return Err(anyhow::anyhow!(
+                "{} trailing bytes after CBOR item",
+                reader.data.len()
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Encodes the item with the shortest argument encodings (deterministic CBOR)
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write(&mut out);
+        out
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            CborValue::Int(n) if *n >= 0 => write_head(out, 0, *n as u64),
+            CborValue::Int(n) => write_head(out, 1, (-1 - *n) as u64),
+            CborValue::Bytes(bytes) => {
+                write_head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            CborValue::Text(text) => {
+                write_head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            CborValue::Array(items) => {
+                write_head(out, 4, items.len() as u64);
+                items.iter().for_each(|item| item.write(out));
+            }
+            CborValue::Map(entries) => {
+                write_head(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    key.write(out);
+                    value.write(out);
+                }
+            }
+            CborValue::Tag(tag, item) => {
+                write_head(out, 6, *tag);
+                item.write(out);
+            }
+            CborValue::Bool(false) => out.push(0xf4),
+            CborValue::Bool(true) => out.push(0xf5),
+            CborValue::Null => out.push(0xf6),
+        }
+    }
+
+    /// Value of `key` if this is a map containing it
+    pub fn get(&self, key: &CborValue) -> Option<&CborValue> {
+        match self {
+            CborValue::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            CborValue::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            CborValue::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Writes a major type and its argument in the shortest form
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => out.push(major | argument as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, argument as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(anyhow::anyhow!("Unexpected end of CBOR data"));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn argument(&mut self, info: u8) -> anyhow::Result<u64> {
+        Ok(match info {
+            0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into()?) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into()?) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into()?),
+            31 => {
+                return Err(anyhow::anyhow!(
+                    "Indefinite-length CBOR items are not supported"
+                ))
+            }
+            _ => return Err(anyhow::anyhow!("Invalid CBOR additional info {}", info)),
+        })
+    }
+
+    /// Length argument, bounded by the remaining data (every item takes at least a byte)
+    fn length(&mut self, info: u8) -> anyhow::Result<usize> {
+        let len = self.argument(info)?;
+        if len > self.data.len() as u64 {
+            return Err(anyhow::anyhow!(
+                "CBOR length {} exceeds remaining data",
+                len
+            ));
+        }
+        Ok(len as usize)
+    }
+
+    fn item(&mut self, depth: usize) -> anyhow::Result<CborValue> {
+        if depth > MAX_DEPTH {
+            return Err(anyhow::anyhow!("CBOR nesting exceeds {} levels", MAX_DEPTH));
+        }
+
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+
+        Ok(match major {
+            0 => CborValue::Int(self.argument(info)? as i128),
+            1 => CborValue::Int(-1 - self.argument(info)? as i128),
+            2 => {
+                let len = self.length(info)?;
+                CborValue::Bytes(self.take(len)?.to_vec())
+            }
+            3 => {
+                let len = self.length(info)?;
+                let text = std::str::from_utf8(self.take(len)?)
+                    .map_err(|e| anyhow::anyhow!("Invalid UTF-8 in CBOR text: {}", e))?;
+                CborValue::Text(text.to_string())
+            }
+            4 => {
+                let len = self.length(info)?;
+                let items = (0..len)
+                    .map(|_| self.item(depth + 1))
+                    .collect::<anyhow::Result<_>>()?;
+                CborValue::Array(items)
+            }
+            5 => {
+                let len = self.length(info)?;
+                let entries = (0..len)
+                    .map(|_| Ok((self.item(depth + 1)?, self.item(depth + 1)?)))
+                    .collect::<anyhow::Result<_>>()?;
+                CborValue::Map(entries)
+            }
+            6 => {
+                let tag = self.argument(info)?;
+                CborValue::Tag(tag, Box::new(self.item(depth + 1)?))
+            }
+            _ => match info {
+                20 => CborValue::Bool(false),
+                21 => CborValue::Bool(true),
+                22 | 23 => CborValue::Null,
+                _ => return Err(anyhow::anyhow!("Unsupported CBOR simple value {}", info)),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let value = CborValue::Array(vec![
+            CborValue::Text("Signature1".to_string()),
+            CborValue::Bytes(vec![0xa1, 0x01, 0x27]),
+            CborValue::Map(vec![
+                (CborValue::Int(-2), CborValue::Bytes(vec![0; 32])),
+                (
+                    CborValue::Text("hashed".to_string()),
+                    CborValue::Bool(false),
+                ),
+            ]),
+            CborValue::Tag(18, Box::new(CborValue::Null)),
+            CborValue::Int(1000),
+            CborValue::Int(-1000),
+        ]);
+
+        let encoded = value.encode();
+        assert_eq!(&encoded[..12], b"\x86\x6aSignature1");
+        assert_eq!(CborValue::decode(&encoded).unwrap(), value);
+    }
+
+    #[test]
+    fn test_shortest_argument_encoding() {
+        assert_eq!(CborValue::Int(23).encode(), [0x17]);
+        assert_eq!(CborValue::Int(24).encode(), [0x18, 0x18]);
+        assert_eq!(CborValue::Int(-8).encode(), [0x27]);
+        assert_eq!(CborValue::Int(65536).encode(), [0x1a, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_items() {
+        // Trailing byte
+        assert!(CborValue::decode(&[0x01, 0x02]).is_err());
+        // Byte string longer than the data
+        assert!(CborValue::decode(&[0x58, 0x20, 0x00]).is_err());
+        // Indefinite-length array
+        assert!(CborValue::decode(&[0x9f, 0x01, 0xff]).is_err());
+        // Nesting beyond the limit
+        assert!(CborValue::decode(&[[0x81; 40].as_slice(), &[0x01]].concat()).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use super::hash::account_id_from_pubkey;
+use ethers_core::{types::Address, utils::keccak256, utils::to_checksum};
+use secp256k1::PublicKey;
+
+/// Bitcoin mainnet P2PKH version byte
+const BITCOIN_P2PKH_VERSION: u8 = 0x00;
+
+/// Addresses derived from one public key on the chains that share its curve
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DerivedAddresses {
+    /// XRPL classic address (`r...`)
+    pub xrpl: String,
+    /// EIP-55 checksummed EVM address (secp256k1 keys only)
+    pub evm: Option<String>,
+    /// Bitcoin mainnet P2PKH address (secp256k1 keys only)
+    pub bitcoin_p2pkh: Option<String>,
+}
+
+/// XRPL classic address: base58 (Ripple alphabet) of RIPEMD-160(SHA-256(pubkey))
+///
+/// Works for both secp256k1 (33-byte compressed) and ed25519 (`0xED` + 32 bytes) keys.
+pub fn xrpl_address(pubkey: &[u8]) -> String {
+    ripple_address_codec::encode_account_id(&account_id_from_pubkey(pubkey))
+}
+
+/// 20-byte EVM account of a secp256k1 key: last 20 bytes of Keccak-256 over the
+/// uncompressed key (without the `0x04` prefix)
+pub fn evm_account_id(pubkey: &[u8]) -> anyhow::Result<[u8; 20]> {
+    let public_key = PublicKey::from_slice(pubkey)
+        .map_err(|e| anyhow::anyhow!("Invalid secp256k1 public key: {}", e))?;
+    let hash = keccak256(&public_key.serialize_uncompressed()[1..]);
+
+    let mut account_id = [0u8; 20];
+    account_id.copy_from_slice(&hash[12..]);
+    Ok(account_id)
+}
+
+/// EIP-55 checksummed EVM address of a secp256k1 key
+pub fn evm_address(pubkey: &[u8]) -> anyhow::Result<String> {
+    Ok(to_checksum(&Address::from(evm_account_id(pubkey)?), None))
+}
+
+/// Bitcoin mainnet P2PKH address: base58check(0x00 || RIPEMD-160(SHA-256(pubkey)))
+///
+/// The key is hashed as given, so compressed and uncompressed keys give different
+/// addresses (as in Bitcoin).
+pub fn bitcoin_p2pkh_address(pubkey: &[u8]) -> anyhow::Result<String> {
+    PublicKey::from_slice(pubkey)
+        .map_err(|e| anyhow::anyhow!("Invalid secp256k1 public key: {}", e))?;
+
+    let mut payload = vec![BITCOIN_P2PKH_VERSION];
+    payload.extend_from_slice(&account_id_from_pubkey(pubkey));
+    Ok(bs58::encode(payload).with_check().into_string())
+}
+
+/// Derives the XRPL, EVM and Bitcoin P2PKH addresses of a public key
+///
+/// EVM and Bitcoin addresses are only derived for secp256k1 keys; ed25519 keys
+/// (`0xED` prefix) only have an XRPL address.
+pub fn derive_addresses(pubkey: &[u8]) -> DerivedAddresses {
+    DerivedAddresses {
+        xrpl: xrpl_address(pubkey),
+        evm: evm_address(pubkey).ok(),
+        bitcoin_p2pkh: bitcoin_p2pkh_address(pubkey).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Public key of the secp256k1 private key `1`
+    const GENERATOR_COMPRESSED: &str =
+        "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+
+    #[test]
+    fn test_derive_addresses_for_secp256k1() {
+        let pubkey = hex::decode(GENERATOR_COMPRESSED).unwrap();
+        let addresses = derive_addresses(&pubkey);
+
+        assert!(addresses.xrpl.starts_with('r'));
+        assert_eq!(
+            addresses.evm.as_deref(),
+            Some("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf")
+        );
+        assert_eq!(
+            addresses.bitcoin_p2pkh.as_deref(),
+            Some("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH")
+        );
+    }
+
+    #[test]
+    fn test_xrpl_address_matches_known_account() {
+        let pubkey =
+            hex::decode("02ACE0AE76CC7DA925442A417FA3618811B5043A66566C9909503D22A96514B2B8")
+                .unwrap();
+        assert_eq!(xrpl_address(&pubkey), "rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU");
+    }
+
+    #[test]
+    fn test_ed25519_key_has_only_xrpl_address() {
+        let mut pubkey = vec![0xED];
+        pubkey.extend_from_slice(&[0x11; 32]);
+        let addresses = derive_addresses(&pubkey);
+
+        assert!(addresses.xrpl.starts_with('r'));
+        assert_eq!(addresses.evm, None);
+        assert_eq!(addresses.bitcoin_p2pkh, None);
+    }
+}
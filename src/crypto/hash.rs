@@ -11,6 +11,21 @@ pub fn sha512half(data: &[u8]) -> [u8; 32] {
     half
 }
 
+/// Calculates double SHA-256 (Bitcoin's hash for txids and signed messages)
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// BIP-340 tagged hash: `SHA256(SHA256(tag) || SHA256(tag) || data)`
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
 /// Derives the Account ID (20 bytes) from a public key
 /// using SHA-256 followed by RIPEMD-160
 pub fn account_id_from_pubkey(pubkey: &[u8]) -> [u8; 20] {
@@ -92,6 +107,19 @@ mod tests {
         assert_eq!(address, "rDTXLQ7ZKZVKz33zJbHjgVShjsBnqMBhmN");
     }
 
+    #[test]
+    fn test_tagged_hash_matches_definition() {
+        let tag_hash = Sha256::digest(b"BIP0322-signed-message");
+        let mut data = tag_hash.to_vec();
+        data.extend_from_slice(&tag_hash);
+        data.extend_from_slice(b"Hello World");
+
+        assert_eq!(
+            tagged_hash("BIP0322-signed-message", b"Hello World").to_vec(),
+            Sha256::digest(&data).to_vec()
+        );
+    }
+
     #[test]
     fn test_account_id_length() {
         let pubkey =
@@ -1,12 +1,14 @@
 //! Cryptographic operations for signature verification.
 //!
 //! This module provides cryptographic primitives for:
-//! - Hashing (SHA-512Half, RIPEMD-160)
+//! - Hashing (SHA-512Half, RIPEMD-160, double SHA-256, BIP-340 tagged hashes)
 //! - Signature verification (ECDSA secp256k1, Ed25519)
-//! - Address derivation from public keys
+//! - Address derivation from public keys (XRPL, EVM, Bitcoin P2PKH)
 
+pub mod address;
 pub mod hash;
 pub mod verify;
 
-pub use hash::{account_id_from_pubkey, sha512half};
+pub use address::{derive_addresses, DerivedAddresses};
+pub use hash::{account_id_from_pubkey, sha256d, sha512half, tagged_hash};
 pub use verify::verify_signature;
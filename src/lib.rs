@@ -11,7 +11,18 @@
 //!
 //! - `xaman` - Support for Xaman wallet (XRPL SignIn)
 //! - `web3auth` - Support for Web3Auth wallet
-//! - `wallet_connect` - Support for WalletConnect and EVM-compatible wallets (EIP-191)
+//! - `wallet_connect` - Support for WalletConnect and EVM-compatible wallets (EIP-191, EIP-712)
+//! - `solana` - Support for Solana wallets (ed25519 `signMessage`, Sign In With Solana)
+//! - `bitcoin` - Support for Bitcoin wallets (BIP-137 / Electrum and BIP-322 signed messages)
+//! - `cosmos` - Support for Cosmos SDK wallets (Keplr/Leap ADR-036 `signArbitrary`)
+//! - `tron` - Support for Tron wallets (TronLink TIP-191 signed messages)
+//! - `cardano` - Support for Cardano wallets (CIP-30 `signData` COSE signatures)
+//! - `substrate` - Support for Polkadot/Substrate wallets (sr25519, ed25519 and ecdsa `signRaw`)
+//! - `near` - Support for NEAR wallets (NEP-413 `signMessage`)
+//! - `stellar` - Support for Stellar wallets (SEP-10 challenge transactions)
+//! - `move_chains` - Support for Sui and Aptos wallets (personal message signatures)
+//! - `ton` - Support for TON wallets (TON Connect `ton_proof`)
+//! - `webauthn` - Support for passkeys and security keys (WebAuthn ES256 assertions)
 //! - `cli` - CLI binary with env_logger
 //! - `all-wallets` - Convenience feature to enable all wallets
 //!
@@ -45,6 +56,7 @@
 //!         signature_data: "732102DB4811...".to_string(), // Signature hex
 //!         expected_address: "rExampleAddr123456789xrpL1234567890".to_string(),
 //!         challenge: Some("domain:timestamp:uuid:action:address".to_string()),
+//!         ..Default::default()
 //!     };
 //!
 //!     // Get wallet provider
@@ -74,14 +86,24 @@
 //!
 //! The verifier validates that the address actually signed the challenge, proving
 //! ownership of the private key without exposing it.
+//!
+//! Challenges may also be CAIP-122 "Sign in with X" messages (SIWE for EVM, plain
+//! text for XRPL). These are parsed back and exposed through
+//! [`VerificationResult::sign_in`](types::VerificationResult::sign_in), including the
+//! CAIP-10 account and CAIP-2 chain. See the [`caip`] module.
 
+pub mod caip;
+#[cfg(any(feature = "cardano", feature = "webauthn"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cardano", feature = "webauthn"))))]
+pub mod cbor;
 pub mod crypto;
 pub mod output;
 pub mod parser;
 pub mod types;
 pub mod wallets;
 
-use crate::crypto::{account_id_from_pubkey, sha512half, verify_signature};
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::{account_id_from_pubkey, derive_addresses, sha512half, verify_signature};
 use crate::parser::{extract_fields, reconstruct_unsigned_blob};
 use crate::types::{TransactionFields, VerificationResult};
 
@@ -136,13 +158,20 @@ pub fn verify_xrpl_signin(
         if signature_valid { "VALID" } else { "INVALID" }
     );
 
+    // 4. Interpret the MemoData as a CAIP-122 sign-in message (if it is one)
+    let sign_in = found_challenge.as_deref().and_then(parse_sign_in_challenge);
+
     Ok(VerificationResult {
         address_valid,
         challenge_valid,
         signature_valid,
         derived_address,
         found_challenge,
-    })
+        sign_in,
+        derived_addresses: Some(derive_addresses(&fields.signing_pubkey)),
+        ..Default::default()
+    }
+    .check_sign_in_account())
 }
 
 /// Verifies the challenge in MemoData
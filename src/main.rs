@@ -1,6 +1,7 @@
 use std::env;
 use wallet_signature_verify::{
     output::print_verification_result,
+    types::{MessageEncoding, MessageHashScheme},
     wallets::{
         get_wallet_provider, registry::unsupported_wallet_error, VerificationInput, WalletType,
     },
@@ -28,6 +29,13 @@ fn print_usage(program_name: &str) {
     eprintln!(
         "  --challenge <str>      Challenge string (optional for Xaman, required for Web3Auth)"
     );
+    eprintln!(
+        "  --message-encoding <e> How the challenge was signed: utf8 (default), hex, auto (EVM only)"
+    );
+    eprintln!("  --public-key <key>     Signer public key: verified directly instead of recovered (required for Web3Auth ed25519, Cosmos and Cardano)");
+    eprintln!(
+        "  --hash-scheme <s>      Challenge hash: sha512half (default), sha256, keccak256, eip191, auto (Web3Auth only)"
+    );
     eprintln!();
     eprintln!("Supported Wallets:");
     for wallet in WalletType::supported_wallets() {
@@ -71,6 +79,9 @@ fn main() -> anyhow::Result<()> {
     let mut signature_data: Option<String> = None;
     let mut expected_address: Option<String> = None;
     let mut challenge: Option<String> = None;
+    let mut message_encoding = MessageEncoding::default();
+    let mut hash_scheme = MessageHashScheme::default();
+    let mut public_key: Option<String> = None;
 
     let mut i = 1;
     while i < args.len() {
@@ -111,6 +122,45 @@ fn main() -> anyhow::Result<()> {
                     std::process::exit(2);
                 }
             }
+            "--message-encoding" => {
+                if i + 1 < args.len() {
+                    message_encoding = match args[i + 1].parse() {
+                        Ok(encoding) => encoding,
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            std::process::exit(2);
+                        }
+                    };
+                    i += 2;
+                } else {
+                    eprintln!("Error: --message-encoding requires a value");
+                    std::process::exit(2);
+                }
+            }
+            "--public-key" | "-p" => {
+                if i + 1 < args.len() {
+                    public_key = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --public-key requires a value");
+                    std::process::exit(2);
+                }
+            }
+            "--hash-scheme" => {
+                if i + 1 < args.len() {
+                    hash_scheme = match args[i + 1].parse() {
+                        Ok(scheme) => scheme,
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            std::process::exit(2);
+                        }
+                    };
+                    i += 2;
+                } else {
+                    eprintln!("Error: --hash-scheme requires a value");
+                    std::process::exit(2);
+                }
+            }
             "--help" | "-h" => {
                 print_usage(&args[0]);
                 std::process::exit(0);
@@ -169,6 +219,9 @@ fn main() -> anyhow::Result<()> {
         signature_data: signature_data.clone(),
         expected_address: expected_address.clone(),
         challenge: challenge.clone(),
+        message_encoding,
+        hash_scheme,
+        public_key,
     };
 
     log::debug!("Signature data length: {} bytes", signature_data.len());
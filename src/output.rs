@@ -48,6 +48,34 @@ pub fn print_verification_result(
         println!();
     }
 
+    // Sign-in message (CAIP-122)
+    if let Some(sign_in) = &result.sign_in {
+        println!("🔗 Sign-In Message (CAIP-122):");
+        println!("   Domain:   {}", sign_in.domain);
+        println!("   Account:  {}", sign_in.account);
+        println!("   Chain:    {}", sign_in.chain_id());
+        println!();
+    }
+
+    // Recipient named in the signed message
+    if let Some(recipient) = &result.recipient {
+        println!("📨 Signed For: {}", recipient);
+        println!();
+    }
+
+    // Addresses of the signing key on other chains
+    if let Some(addresses) = &result.derived_addresses {
+        println!("🔑 Signing Key Addresses:");
+        println!("   XRPL:     {}", addresses.xrpl);
+        if let Some(evm) = &addresses.evm {
+            println!("   EVM:      {}", evm);
+        }
+        if let Some(bitcoin) = &addresses.bitcoin_p2pkh {
+            println!("   Bitcoin:  {}", bitcoin);
+        }
+        println!();
+    }
+
     // Signature verification
     println!("🔏 Cryptographic Signature:");
     println!(
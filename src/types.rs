@@ -1,3 +1,6 @@
+use crate::caip::{AccountId, ChainId, SignInMessage};
+use crate::crypto::DerivedAddresses;
+
 /// Fields extracted from a signed XRPL transaction
 #[derive(Debug, Clone)]
 pub struct TransactionFields {
@@ -6,18 +9,190 @@ pub struct TransactionFields {
     pub memo_data: Vec<u8>,
 }
 
+/// Signature encoding detected while parsing the signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureEncoding {
+    /// 65-byte `r || s || v` with `v` = 0/1
+    RecoverableRaw,
+    /// 65-byte `r || s || v` with `v` = 27/28
+    RecoverableLegacy,
+    /// `r || s || v` with an EIP-155 `v` = chain_id * 2 + 35/36
+    Eip155 { chain_id: u64 },
+    /// 64-byte EIP-2098 compact `r || yParityAndS`
+    Eip2098,
+    /// ASN.1 DER-encoded `(r, s)` without recovery ID
+    Der,
+    /// 64-byte `r || s` without recovery ID
+    Compact,
+}
+
+impl SignatureEncoding {
+    /// Chain ID recovered from an EIP-155 `v` value
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            SignatureEncoding::Eip155 { chain_id } => Some(*chain_id),
+            _ => None,
+        }
+    }
+}
+
+/// How a text challenge maps to the bytes the wallet signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageEncoding {
+    /// The challenge text is signed as UTF-8 bytes
+    #[default]
+    Utf8,
+    /// The challenge is a `0x`-prefixed hex string and the decoded bytes are signed
+    HexBytes,
+    /// `0x`-prefixed hex challenges are decoded, anything else is signed as UTF-8
+    /// (what `personal_sign` wallets such as MetaMask do)
+    Auto,
+}
+
+impl MessageEncoding {
+    /// Returns the signed bytes and the encoding they were resolved with
+    /// (never [`MessageEncoding::Auto`])
+    pub fn decode(self, message: &str) -> anyhow::Result<(Vec<u8>, MessageEncoding)> {
+        match self {
+            MessageEncoding::Utf8 => Ok((message.as_bytes().to_vec(), MessageEncoding::Utf8)),
+            MessageEncoding::HexBytes => {
+                let hex_part = message.strip_prefix("0x").ok_or_else(|| {
+                    anyhow::anyhow!("Hex-bytes message must start with '0x', got '{}'", message)
+                })?;
+                let bytes = hex::decode(hex_part)
+                    .map_err(|e| anyhow::anyhow!("Failed to decode hex message: {}", e))?;
+                Ok((bytes, MessageEncoding::HexBytes))
+            }
+            MessageEncoding::Auto => match message.strip_prefix("0x").map(hex::decode) {
+                Some(Ok(bytes)) => Ok((bytes, MessageEncoding::HexBytes)),
+                _ => Ok((message.as_bytes().to_vec(), MessageEncoding::Utf8)),
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for MessageEncoding {
+    type Err = anyhow::Error;
+
+    /// Parses an encoding name ("utf8", "hex", "auto")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utf8" | "utf-8" | "text" => Ok(MessageEncoding::Utf8),
+            "hex" | "hex-bytes" | "bytes" => Ok(MessageEncoding::HexBytes),
+            "auto" => Ok(MessageEncoding::Auto),
+            _ => Err(anyhow::anyhow!(
+                "Unknown message encoding '{}' (expected utf8, hex or auto)",
+                s
+            )),
+        }
+    }
+}
+
+/// Hash applied to the challenge before it was signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageHashScheme {
+    /// SHA-256 of the challenge
+    Sha256,
+    /// Keccak-256 of the challenge
+    Keccak256,
+    /// First half of SHA-512 (XRPL standard)
+    #[default]
+    Sha512Half,
+    /// EIP-191 `personal_sign` hash: Keccak-256 of `"\x19Ethereum Signed Message:\n" + len + message`
+    Eip191,
+    /// Tries each of the known schemes and reports the one that matched
+    Auto,
+}
+
+impl MessageHashScheme {
+    /// Concrete schemes tried by [`MessageHashScheme::Auto`], in order
+    pub const KNOWN: [MessageHashScheme; 4] = [
+        MessageHashScheme::Sha512Half,
+        MessageHashScheme::Sha256,
+        MessageHashScheme::Keccak256,
+        MessageHashScheme::Eip191,
+    ];
+
+    /// Schemes to try for this selection
+    pub fn candidates(self) -> Vec<MessageHashScheme> {
+        match self {
+            MessageHashScheme::Auto => Self::KNOWN.to_vec(),
+            scheme => vec![scheme],
+        }
+    }
+}
+
+impl std::str::FromStr for MessageHashScheme {
+    type Err = anyhow::Error;
+
+    /// Parses a scheme name ("sha256", "keccak256", "sha512half", "eip191", "auto")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sha256" | "sha-256" => Ok(MessageHashScheme::Sha256),
+            "keccak" | "keccak256" => Ok(MessageHashScheme::Keccak256),
+            "sha512half" | "sha-512half" => Ok(MessageHashScheme::Sha512Half),
+            "eip191" | "eip-191" | "personal_sign" => Ok(MessageHashScheme::Eip191),
+            "auto" => Ok(MessageHashScheme::Auto),
+            _ => Err(anyhow::anyhow!(
+                "Unknown hash scheme '{}' (expected sha256, keccak256, sha512half, eip191 or auto)",
+                s
+            )),
+        }
+    }
+}
+
 /// Authentication verification result
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct VerificationResult {
     pub address_valid: bool,
     pub challenge_valid: bool,
     pub signature_valid: bool,
     pub derived_address: String,
     pub found_challenge: Option<String>,
+    /// Signed challenge parsed as a CAIP-122 sign-in message, if it is one
+    pub sign_in: Option<SignInMessage>,
+    /// Signature encoding detected by providers that accept several encodings
+    pub signature_encoding: Option<SignatureEncoding>,
+    /// How the challenge was interpreted by providers that accept several message encodings
+    pub message_encoding: Option<MessageEncoding>,
+    /// Hash scheme that matched, for providers that accept several
+    pub hash_scheme: Option<MessageHashScheme>,
+    /// Addresses of the signing key on other chains, for providers that know the key
+    pub derived_addresses: Option<DerivedAddresses>,
+    /// Recipient the wallet signed for, for providers whose messages name one
+    pub recipient: Option<String>,
 }
 
 impl VerificationResult {
     pub fn is_valid(&self) -> bool {
         self.address_valid && self.challenge_valid && self.signature_valid
     }
+
+    /// CAIP-10 account from the sign-in message, if the challenge was one
+    pub fn caip10_account(&self) -> Option<&AccountId> {
+        self.sign_in.as_ref().map(|message| &message.account)
+    }
+
+    /// CAIP-2 chain from the sign-in message, if the challenge was one
+    pub fn chain_id(&self) -> Option<&ChainId> {
+        self.sign_in.as_ref().map(|message| message.chain_id())
+    }
+
+    /// Marks the challenge invalid if it is a sign-in message for another account
+    /// than the derived one
+    pub(crate) fn check_sign_in_account(mut self) -> Self {
+        if let Some(sign_in) = &self.sign_in {
+            if !self.derived_address.is_empty()
+                && !sign_in.account.matches_address(&self.derived_address)
+            {
+                log::warn!(
+                    "Sign-in message is for {}, not the signer {}",
+                    sign_in.account,
+                    self.derived_address
+                );
+                self.challenge_valid = false;
+            }
+        }
+        self
+    }
 }
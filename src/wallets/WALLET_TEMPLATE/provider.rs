@@ -63,6 +63,7 @@ impl WalletProvider for YourWalletProvider {
             signature_valid: true,     // Is the signature valid?
             derived_address: input.expected_address.clone(),
             found_challenge: Some(challenge.clone()),
+            ..Default::default()
         })
     }
 }
@@ -0,0 +1,305 @@
+use crate::crypto::{account_id_from_pubkey, tagged_hash};
+use bech32::{hrp, segwit, Fe32, Hrp};
+use secp256k1::{PublicKey, Scalar, SECP256K1};
+use std::fmt;
+use std::str::FromStr;
+
+/// Bitcoin network an address belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitcoinNetwork {
+    Mainnet,
+    /// Testnet and signet (they share address prefixes)
+    Testnet,
+}
+
+impl BitcoinNetwork {
+    fn p2pkh_version(self) -> u8 {
+        match self {
+            BitcoinNetwork::Mainnet => 0x00,
+            BitcoinNetwork::Testnet => 0x6f,
+        }
+    }
+
+    fn p2sh_version(self) -> u8 {
+        match self {
+            BitcoinNetwork::Mainnet => 0x05,
+            BitcoinNetwork::Testnet => 0xc4,
+        }
+    }
+
+    fn hrp(self) -> Hrp {
+        match self {
+            BitcoinNetwork::Mainnet => hrp::BC,
+            BitcoinNetwork::Testnet => hrp::TB,
+        }
+    }
+}
+
+/// Single-key address types a signed message can be verified against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitcoinAddressType {
+    /// Legacy pay-to-pubkey-hash (`1...`)
+    P2pkh,
+    /// Nested SegWit, P2WPKH wrapped in P2SH (`3...`)
+    P2shP2wpkh,
+    /// Native SegWit v0 pay-to-witness-pubkey-hash (`bc1q...`)
+    P2wpkh,
+    /// Taproot pay-to-taproot (`bc1p...`), BIP-322 only
+    P2tr,
+}
+
+/// Bitcoin address parsed from text: its type, network and witness program or hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinAddress {
+    pub address_type: BitcoinAddressType,
+    pub network: BitcoinNetwork,
+    /// 20-byte public key hash (P2PKH, P2WPKH) or script hash (P2SH), or the 32-byte
+    /// x-only output key (P2TR)
+    pub program: Vec<u8>,
+}
+
+impl BitcoinAddress {
+    /// Address of `address_type` on `network` paying to `pubkey`
+    ///
+    /// The key is hashed as given: compressed and uncompressed keys give different
+    /// addresses, and SegWit addresses are only standard for compressed keys. P2TR
+    /// addresses commit to the key with a BIP-86 tweak (no script tree).
+    pub fn from_public_key(
+        address_type: BitcoinAddressType,
+        network: BitcoinNetwork,
+        pubkey: &[u8],
+    ) -> anyhow::Result<Self> {
+        let program = match address_type {
+            BitcoinAddressType::P2pkh | BitcoinAddressType::P2wpkh => {
+                account_id_from_pubkey(pubkey).to_vec()
+            }
+            BitcoinAddressType::P2shP2wpkh => {
+                account_id_from_pubkey(&p2wpkh_script(&account_id_from_pubkey(pubkey))).to_vec()
+            }
+            BitcoinAddressType::P2tr => taproot_output_key(pubkey)?.to_vec(),
+        };
+
+        Ok(BitcoinAddress {
+            address_type,
+            network,
+            program,
+        })
+    }
+
+    /// Output script (scriptPubKey) paying to this address
+    pub fn script_pubkey(&self) -> Vec<u8> {
+        match self.address_type {
+            // OP_DUP OP_HASH160 PUSH20 <hash> OP_EQUALVERIFY OP_CHECKSIG
+            BitcoinAddressType::P2pkh => {
+                [&[0x76, 0xa9, 0x14], &self.program[..], &[0x88, 0xac]].concat()
+            }
+            // OP_HASH160 PUSH20 <hash> OP_EQUAL
+            BitcoinAddressType::P2shP2wpkh => [&[0xa9, 0x14], &self.program[..], &[0x87]].concat(),
+            BitcoinAddressType::P2wpkh => p2wpkh_script(&self.program),
+            // OP_1 PUSH32 <output key>
+            BitcoinAddressType::P2tr => [&[0x51, 0x20], &self.program[..]].concat(),
+        }
+    }
+
+    fn parse_base58(s: &str) -> anyhow::Result<Self> {
+        let payload = bs58::decode(s)
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| anyhow::anyhow!("Invalid Bitcoin address '{}': {}", s, e))?;
+        if payload.len() != 21 {
+            return Err(anyhow::anyhow!(
+                "Invalid Bitcoin address '{}': expected 21-byte payload, got {}",
+                s,
+                payload.len()
+            ));
+        }
+
+        let (address_type, network) = match payload[0] {
+            0x00 => (BitcoinAddressType::P2pkh, BitcoinNetwork::Mainnet),
+            0x6f => (BitcoinAddressType::P2pkh, BitcoinNetwork::Testnet),
+            // A P2SH address can hide any script; only P2SH-P2WPKH can be checked
+            0x05 => (BitcoinAddressType::P2shP2wpkh, BitcoinNetwork::Mainnet),
+            0xc4 => (BitcoinAddressType::P2shP2wpkh, BitcoinNetwork::Testnet),
+            version => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported Bitcoin address version 0x{:02x} in '{}'",
+                    version,
+                    s
+                ))
+            }
+        };
+
+        Ok(BitcoinAddress {
+            address_type,
+            network,
+            program: payload[1..].to_vec(),
+        })
+    }
+
+    fn parse_segwit(s: &str) -> anyhow::Result<Self> {
+        let (hrp, version, program) = segwit::decode(s)
+            .map_err(|e| anyhow::anyhow!("Invalid Bitcoin address '{}': {}", s, e))?;
+
+        let network = if hrp == hrp::BC {
+            BitcoinNetwork::Mainnet
+        } else if hrp == hrp::TB {
+            BitcoinNetwork::Testnet
+        } else {
+            return Err(anyhow::anyhow!(
+                "Unsupported Bitcoin address prefix '{}' in '{}'",
+                hrp,
+                s
+            ));
+        };
+
+        let address_type = match (version, program.len()) {
+            (Fe32::Q, 20) => BitcoinAddressType::P2wpkh,
+            (Fe32::P, 32) => BitcoinAddressType::P2tr,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported SegWit address '{}': only P2WPKH and P2TR can sign messages",
+                    s
+                ))
+            }
+        };
+
+        Ok(BitcoinAddress {
+            address_type,
+            network,
+            program,
+        })
+    }
+}
+
+impl FromStr for BitcoinAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        if lower.starts_with("bc1") || lower.starts_with("tb1") {
+            Self::parse_segwit(s)
+        } else {
+            Self::parse_base58(s)
+        }
+    }
+}
+
+impl fmt::Display for BitcoinAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let version = match self.address_type {
+            BitcoinAddressType::P2pkh => self.network.p2pkh_version(),
+            BitcoinAddressType::P2shP2wpkh => self.network.p2sh_version(),
+            BitcoinAddressType::P2wpkh => {
+                let address =
+                    segwit::encode_v0(self.network.hrp(), &self.program).map_err(|_| fmt::Error)?;
+                return write!(f, "{}", address);
+            }
+            BitcoinAddressType::P2tr => {
+                let address =
+                    segwit::encode_v1(self.network.hrp(), &self.program).map_err(|_| fmt::Error)?;
+                return write!(f, "{}", address);
+            }
+        };
+
+        let mut payload = vec![version];
+        payload.extend_from_slice(&self.program);
+        write!(f, "{}", bs58::encode(payload).with_check().into_string())
+    }
+}
+
+/// P2WPKH witness program script: OP_0 PUSH20 <pubkey hash> (also the P2SH-P2WPKH
+/// redeem script)
+pub(crate) fn p2wpkh_script(pubkey_hash: &[u8]) -> Vec<u8> {
+    [&[0x00, 0x14], pubkey_hash].concat()
+}
+
+/// BIP-86 Taproot output key: the x-only internal key tweaked with
+/// `TaggedHash("TapTweak", internal_key)`
+pub fn taproot_output_key(pubkey: &[u8]) -> anyhow::Result<[u8; 32]> {
+    let (internal_key, _) = PublicKey::from_slice(pubkey)
+        .map_err(|e| anyhow::anyhow!("Invalid secp256k1 public key: {}", e))?
+        .x_only_public_key();
+    let tweak = Scalar::from_be_bytes(tagged_hash("TapTweak", &internal_key.serialize()))
+        .map_err(|_| anyhow::anyhow!("Invalid Taproot tweak"))?;
+    let (output_key, _) = internal_key
+        .add_tweak(SECP256K1, &tweak)
+        .map_err(|e| anyhow::anyhow!("Failed to tweak Taproot key: {}", e))?;
+
+    Ok(output_key.serialize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Public key of the secp256k1 private key `1`
+    const GENERATOR_COMPRESSED: &str =
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn generator_address(address_type: BitcoinAddressType, network: BitcoinNetwork) -> String {
+        let pubkey = hex::decode(GENERATOR_COMPRESSED).unwrap();
+        BitcoinAddress::from_public_key(address_type, network, &pubkey)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_addresses_of_generator_key() {
+        use BitcoinAddressType::*;
+        use BitcoinNetwork::*;
+
+        assert_eq!(
+            generator_address(P2pkh, Mainnet),
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+        );
+        assert_eq!(
+            generator_address(P2shP2wpkh, Mainnet),
+            "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"
+        );
+        assert_eq!(
+            generator_address(P2wpkh, Mainnet),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        // BIP-86 test vector for the same key
+        assert_eq!(
+            generator_address(P2tr, Mainnet),
+            "bc1pmfr3p9j00pfxjh0zmgp99y8zftmd3s5pmedqhyptwy6lm87hf5sspknck9"
+        );
+        assert_eq!(
+            generator_address(P2pkh, Testnet),
+            "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"
+        );
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        for address in [
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH",
+            "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN",
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+            "bc1pmfr3p9j00pfxjh0zmgp99y8zftmd3s5pmedqhyptwy6lm87hf5sspknck9",
+        ] {
+            let parsed: BitcoinAddress = address.parse().unwrap();
+            assert_eq!(parsed.to_string(), address);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported_addresses() {
+        // Bad checksum
+        assert!("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ"
+            .parse::<BitcoinAddress>()
+            .is_err());
+        // P2WSH (32-byte v0 program) is a script, not a single key
+        assert!(
+            "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3"
+                .parse::<BitcoinAddress>()
+                .is_err()
+        );
+        // XRPL address
+        assert!("rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU"
+            .parse::<BitcoinAddress>()
+            .is_err());
+    }
+}
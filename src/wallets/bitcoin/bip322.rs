@@ -0,0 +1,379 @@
+use super::address::{p2wpkh_script, BitcoinAddress, BitcoinAddressType};
+use super::core::verify_bitcoin_signature;
+use super::transaction::{
+    decode_witness, OutPoint, Transaction, TxIn, TxOut, SIGHASH_ALL, SIGHASH_DEFAULT,
+};
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::{account_id_from_pubkey, tagged_hash};
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use secp256k1::{ecdsa, schnorr, Message, PublicKey, XOnlyPublicKey, SECP256K1};
+
+/// BIP-340 tag of the message hash committed to by `to_spend`
+pub const BIP322_TAG: &str = "BIP0322-signed-message";
+
+/// `OP_RETURN`, the only output script of `to_sign`
+const OP_RETURN: u8 = 0x6a;
+
+/// How a BIP-322 signature was encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bip322Format {
+    /// Base64 of the consensus-encoded witness stack of `to_sign`
+    Simple,
+    /// Base64 of the whole signed `to_sign` transaction
+    Full,
+    /// BIP-137 compact signature (what P2PKH addresses sign with)
+    Legacy,
+}
+
+/// Message hash committed to by `to_spend`: `TaggedHash("BIP0322-signed-message", message)`
+pub fn bip322_message_hash(message: &[u8]) -> [u8; 32] {
+    tagged_hash(BIP322_TAG, message)
+}
+
+/// Virtual transaction `to_spend`: spends a null outpoint with the message hash in its
+/// scriptSig and creates one zero-value output to the signing address
+pub fn to_spend(script_pubkey: &[u8], message: &[u8]) -> Transaction {
+    // OP_0 PUSH32 <message hash>
+    let mut script_sig = vec![0x00, 0x20];
+    script_sig.extend_from_slice(&bip322_message_hash(message));
+
+    Transaction {
+        version: 0,
+        inputs: vec![TxIn {
+            previous_output: OutPoint {
+                txid: [0u8; 32],
+                vout: 0xffff_ffff,
+            },
+            script_sig,
+            sequence: 0,
+            witness: Vec::new(),
+        }],
+        outputs: vec![TxOut {
+            value: 0,
+            script_pubkey: script_pubkey.to_vec(),
+        }],
+        lock_time: 0,
+    }
+}
+
+/// Virtual transaction `to_sign`, unsigned: spends output 0 of `to_spend` into a single
+/// `OP_RETURN` output
+pub fn to_sign(to_spend: &Transaction) -> Transaction {
+    Transaction {
+        version: 0,
+        inputs: vec![TxIn {
+            previous_output: OutPoint {
+                txid: to_spend.txid(),
+                vout: 0,
+            },
+            script_sig: Vec::new(),
+            sequence: 0,
+            witness: Vec::new(),
+        }],
+        outputs: vec![TxOut {
+            value: 0,
+            script_pubkey: vec![OP_RETURN],
+        }],
+        lock_time: 0,
+    }
+}
+
+/// Verifies a BIP-322 generic signed message
+///
+/// The simple (witness-only) and full (`to_sign` transaction) formats are accepted for
+/// P2WPKH, P2SH-P2WPKH and P2TR (key path, BIP-340 Schnorr) addresses. 65-byte legacy
+/// signatures are verified as BIP-137, which is the only format P2PKH addresses have.
+pub fn verify_bip322_signature(
+    signature_base64: &str,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("BIP-322 verification starting");
+    log::debug!("Signature: {}", signature_base64);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let expected: BitcoinAddress = expected_address.parse()?;
+    let signature = BASE64
+        .decode(signature_base64.trim())
+        .map_err(|e| anyhow::anyhow!("Failed to decode signature base64: {}", e))?;
+
+    if signature.len() == 65 && expected.address_type != BitcoinAddressType::P2tr {
+        log::debug!("BIP-322 format: {:?}", Bip322Format::Legacy);
+        return verify_bitcoin_signature(signature_base64, challenge, expected_address);
+    }
+    if expected.address_type == BitcoinAddressType::P2pkh {
+        return Err(anyhow::anyhow!(
+            "P2PKH addresses only support legacy (BIP-137) signatures"
+        ));
+    }
+
+    let to_spend = to_spend(&expected.script_pubkey(), challenge.as_bytes());
+    let (format, to_sign) = match decode_witness(&signature) {
+        Ok(witness) => (
+            Bip322Format::Simple,
+            simple_to_sign(&to_spend, &expected, witness),
+        ),
+        Err(_) => {
+            let transaction = Transaction::deserialize(&signature).map_err(|e| {
+                anyhow::anyhow!(
+                    "Signature is neither a witness stack nor a transaction: {}",
+                    e
+                )
+            })?;
+            check_full_to_sign(&to_spend, &transaction)?;
+            (Bip322Format::Full, transaction)
+        }
+    };
+    log::debug!("BIP-322 format: {:?}", format);
+
+    let (derived, signature_valid) = verify_input(&expected, &to_spend, &to_sign)?;
+    let address_valid = derived == expected;
+
+    if address_valid && signature_valid {
+        log::info!("BIP-322 signature verification successful!");
+    } else if !address_valid {
+        log::warn!("Signing key {} does not match expected address", derived);
+    } else {
+        log::warn!("BIP-322 signature verification failed");
+    }
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address: derived.to_string(),
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+/// Builds `to_sign` for a simple signature
+///
+/// P2SH-P2WPKH also needs the redeem script in the scriptSig; it is implied by the
+/// public key in the witness.
+fn simple_to_sign(
+    to_spend: &Transaction,
+    expected: &BitcoinAddress,
+    witness: Vec<Vec<u8>>,
+) -> Transaction {
+    let mut transaction = to_sign(to_spend);
+
+    if expected.address_type == BitcoinAddressType::P2shP2wpkh {
+        if let Some(pubkey) = witness.get(1) {
+            let redeem_script = p2wpkh_script(&account_id_from_pubkey(pubkey));
+            transaction.inputs[0].script_sig =
+                [&[redeem_script.len() as u8], &redeem_script[..]].concat();
+        }
+    }
+
+    transaction.inputs[0].witness = witness;
+    transaction
+}
+
+/// Checks that a full-format `to_sign` spends `to_spend` into a single `OP_RETURN`
+fn check_full_to_sign(to_spend: &Transaction, transaction: &Transaction) -> anyhow::Result<()> {
+    let [input] = transaction.inputs.as_slice() else {
+        return Err(anyhow::anyhow!(
+            "to_sign must have exactly one input, got {}",
+            transaction.inputs.len()
+        ));
+    };
+    if input.previous_output.txid != to_spend.txid() || input.previous_output.vout != 0 {
+        return Err(anyhow::anyhow!(
+            "to_sign does not spend the to_spend output for this message and address"
+        ));
+    }
+
+    let [output] = transaction.outputs.as_slice() else {
+        return Err(anyhow::anyhow!(
+            "to_sign must have exactly one output, got {}",
+            transaction.outputs.len()
+        ));
+    };
+    if output.value != 0 || output.script_pubkey != [OP_RETURN] {
+        return Err(anyhow::anyhow!(
+            "to_sign output must be a zero-value OP_RETURN"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Runs the script check of the `to_sign` input against the expected address
+///
+/// Returns the address of the signing key and whether its signature is valid.
+fn verify_input(
+    expected: &BitcoinAddress,
+    to_spend: &Transaction,
+    to_sign: &Transaction,
+) -> anyhow::Result<(BitcoinAddress, bool)> {
+    let input = &to_sign.inputs[0];
+
+    match expected.address_type {
+        BitcoinAddressType::P2wpkh | BitcoinAddressType::P2shP2wpkh => {
+            let [signature, pubkey] = input.witness.as_slice() else {
+                return Err(anyhow::anyhow!(
+                    "P2WPKH witness must have 2 items, got {}",
+                    input.witness.len()
+                ));
+            };
+            let public_key = PublicKey::from_slice(pubkey)
+                .map_err(|e| anyhow::anyhow!("Invalid witness public key: {}", e))?;
+            if pubkey.len() != 33 {
+                return Err(anyhow::anyhow!("SegWit public keys must be compressed"));
+            }
+
+            let derived =
+                BitcoinAddress::from_public_key(expected.address_type, expected.network, pubkey)?;
+            if expected.address_type == BitcoinAddressType::P2shP2wpkh {
+                let redeem_script = p2wpkh_script(&account_id_from_pubkey(pubkey));
+                if input.script_sig != [&[redeem_script.len() as u8], &redeem_script[..]].concat() {
+                    return Err(anyhow::anyhow!(
+                        "P2SH-P2WPKH scriptSig must push the redeem script"
+                    ));
+                }
+            } else if !input.script_sig.is_empty() {
+                return Err(anyhow::anyhow!("P2WPKH scriptSig must be empty"));
+            }
+
+            // BIP-143 scriptCode of P2WPKH is the P2PKH script of the key hash
+            let script_code = BitcoinAddress {
+                address_type: BitcoinAddressType::P2pkh,
+                network: expected.network,
+                program: account_id_from_pubkey(pubkey).to_vec(),
+            }
+            .script_pubkey();
+            let sighash = to_sign.segwit_v0_sighash(0, &script_code, to_spend.outputs[0].value);
+
+            Ok((derived, verify_ecdsa(signature, &public_key, sighash)))
+        }
+        BitcoinAddressType::P2tr => {
+            let [signature] = input.witness.as_slice() else {
+                return Err(anyhow::anyhow!(
+                    "Only Taproot key path spends are supported (1 witness item, got {})",
+                    input.witness.len()
+                ));
+            };
+            if !input.script_sig.is_empty() {
+                return Err(anyhow::anyhow!("P2TR scriptSig must be empty"));
+            }
+
+            let (signature, sighash_type) = match signature.len() {
+                64 => (&signature[..], SIGHASH_DEFAULT),
+                // An explicit SIGHASH_DEFAULT byte is invalid
+                65 if signature[64] != SIGHASH_DEFAULT => (&signature[..64], signature[64]),
+                len => {
+                    return Err(anyhow::anyhow!(
+                        "Invalid Schnorr signature length: {} bytes",
+                        len
+                    ))
+                }
+            };
+            let output_key = XOnlyPublicKey::from_slice(&expected.program)
+                .map_err(|e| anyhow::anyhow!("Invalid Taproot output key: {}", e))?;
+            let sighash = to_sign.taproot_key_spend_sighash(0, &to_spend.outputs, sighash_type)?;
+
+            let signature_valid = schnorr::Signature::from_slice(signature)
+                .map(|signature| {
+                    SECP256K1
+                        .verify_schnorr(&signature, &Message::from_digest(sighash), &output_key)
+                        .is_ok()
+                })
+                .unwrap_or(false);
+
+            Ok((expected.clone(), signature_valid))
+        }
+        BitcoinAddressType::P2pkh => Err(anyhow::anyhow!(
+            "P2PKH addresses only support legacy (BIP-137) signatures"
+        )),
+    }
+}
+
+/// Checks a DER + `SIGHASH_ALL` witness signature
+fn verify_ecdsa(signature: &[u8], public_key: &PublicKey, sighash: [u8; 32]) -> bool {
+    let Some((&sighash_type, der)) = signature.split_last() else {
+        return false;
+    };
+    if sighash_type != SIGHASH_ALL {
+        log::warn!("Unsupported sighash type 0x{:02x}", sighash_type);
+        return false;
+    }
+
+    ecdsa::Signature::from_der(der)
+        .map(|signature| {
+            SECP256K1
+                .verify_ecdsa(&Message::from_digest(sighash), &signature, public_key)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test vectors from BIP-322, signed by `L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k`
+    const P2WPKH_ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+    const P2TR_ADDRESS: &str = "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3";
+
+    #[test]
+    fn test_message_hashes() {
+        assert_eq!(
+            hex::encode(bip322_message_hash(b"")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(bip322_message_hash(b"Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn test_virtual_transaction_ids() {
+        let script_pubkey = P2WPKH_ADDRESS
+            .parse::<BitcoinAddress>()
+            .unwrap()
+            .script_pubkey();
+        let to_spend = to_spend(&script_pubkey, b"Hello World");
+        let to_sign = to_sign(&to_spend);
+
+        // Displayed txids are the reversed internal byte order
+        let display = |txid: [u8; 32]| hex::encode(txid.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(
+            display(to_spend.txid()),
+            "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b"
+        );
+        assert_eq!(
+            display(to_sign.txid()),
+            "88737ae86f2077145f93cc4b153ae9a1cb8d56afa511988c149c5c8c9d93bddf"
+        );
+    }
+
+    #[test]
+    fn test_bip322_p2wpkh_vectors() {
+        for (message, signature) in [
+            ("", "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="),
+            ("Hello World", "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="),
+        ] {
+            let result = verify_bip322_signature(signature, message, P2WPKH_ADDRESS).unwrap();
+            assert!(result.is_valid(), "BIP-322 vector for '{}' should verify", message);
+
+            let swapped = verify_bip322_signature(signature, "other", P2WPKH_ADDRESS).unwrap();
+            assert!(!swapped.is_valid());
+        }
+    }
+
+    #[test]
+    fn test_bip322_p2tr_vector() {
+        let signature = "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==";
+
+        let result = verify_bip322_signature(signature, "Hello World", P2TR_ADDRESS).unwrap();
+
+        assert!(result.is_valid(), "BIP-322 Taproot vector should verify");
+        assert_eq!(result.derived_address, P2TR_ADDRESS);
+    }
+}
@@ -0,0 +1,183 @@
+use super::address::{BitcoinAddress, BitcoinAddressType};
+use super::transaction::write_varint;
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::sha256d;
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, SECP256K1,
+};
+
+/// Prefix of every signed message, after its varint length
+pub const BITCOIN_MESSAGE_PREFIX: &str = "Bitcoin Signed Message:\n";
+
+/// Hash a wallet signs for "Sign Message":
+/// `SHA256(SHA256(varint(24) || "Bitcoin Signed Message:\n" || varint(len) || message))`
+pub fn bitcoin_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(BITCOIN_MESSAGE_PREFIX.len() + message.len() + 10);
+    write_varint(&mut data, BITCOIN_MESSAGE_PREFIX.len());
+    data.extend_from_slice(BITCOIN_MESSAGE_PREFIX.as_bytes());
+    write_varint(&mut data, message.len());
+    data.extend_from_slice(message);
+
+    sha256d(&data)
+}
+
+/// 65-byte compact signature `header || r || s`, with the header decoded
+///
+/// BIP-137 header ranges:
+/// - 27-30: P2PKH, uncompressed key
+/// - 31-34: P2PKH, compressed key (Electrum also uses it for SegWit addresses)
+/// - 35-38: P2SH-P2WPKH
+/// - 39-42: P2WPKH
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitcoinSignature {
+    pub signature: RecoverableSignature,
+    /// Whether the signing key is serialized compressed
+    pub compressed: bool,
+    /// Address type named by the header, or `None` when it only says "compressed key"
+    /// (any single-key address type, as Electrum signs)
+    pub address_type: Option<BitcoinAddressType>,
+}
+
+impl BitcoinSignature {
+    /// Decodes a base64 65-byte compact signature
+    pub fn parse(signature_base64: &str) -> anyhow::Result<Self> {
+        let bytes = BASE64
+            .decode(signature_base64.trim())
+            .map_err(|e| anyhow::anyhow!("Failed to decode signature base64: {}", e))?;
+        if bytes.len() != 65 {
+            return Err(anyhow::anyhow!(
+                "Invalid signature length: expected 65 bytes, got {}",
+                bytes.len()
+            ));
+        }
+
+        let header = bytes[0];
+        let (compressed, address_type) = match header {
+            27..=30 => (false, Some(BitcoinAddressType::P2pkh)),
+            31..=34 => (true, None),
+            35..=38 => (true, Some(BitcoinAddressType::P2shP2wpkh)),
+            39..=42 => (true, Some(BitcoinAddressType::P2wpkh)),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid signature header byte {}: expected 27-42",
+                    header
+                ))
+            }
+        };
+
+        let recovery_id = RecoveryId::from_i32(((header - 27) % 4) as i32)
+            .map_err(|e| anyhow::anyhow!("Invalid recovery ID: {}", e))?;
+        let signature = RecoverableSignature::from_compact(&bytes[1..], recovery_id)
+            .map_err(|e| anyhow::anyhow!("Invalid compact signature: {}", e))?;
+
+        Ok(BitcoinSignature {
+            signature,
+            compressed,
+            address_type,
+        })
+    }
+
+    /// Whether the header allows verifying against an address of `address_type`
+    pub fn allows(&self, address_type: BitcoinAddressType) -> bool {
+        self.address_type.is_none_or(|t| t == address_type)
+    }
+}
+
+/// Verifies a Bitcoin "Sign Message" signature (BIP-137, Electrum)
+///
+/// The public key is recovered from the signature, serialized as the header says
+/// (compressed or not), and hashed into an address of the expected address's type
+/// and network. The header must also allow that address type.
+pub fn verify_bitcoin_signature(
+    signature_base64: &str,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Bitcoin verification starting");
+    log::debug!("Signature: {}", signature_base64);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let expected: BitcoinAddress = expected_address.parse()?;
+    if expected.address_type == BitcoinAddressType::P2tr {
+        return Err(anyhow::anyhow!(
+            "Taproot addresses cannot sign BIP-137 messages, use BIP-322"
+        ));
+    }
+    let signature = BitcoinSignature::parse(signature_base64)?;
+
+    let hash = bitcoin_message_hash(challenge.as_bytes());
+    log::debug!("Message hash: {}", hex::encode(hash));
+
+    let public_key = SECP256K1
+        .recover_ecdsa(&Message::from_digest(hash), &signature.signature)
+        .map_err(|e| anyhow::anyhow!("Failed to recover public key from signature: {}", e))?;
+    let public_key = if signature.compressed {
+        public_key.serialize().to_vec()
+    } else {
+        public_key.serialize_uncompressed().to_vec()
+    };
+    log::debug!("Recovered public key: {}", hex::encode(&public_key));
+
+    let address_type = signature.address_type.unwrap_or(expected.address_type);
+    let derived = BitcoinAddress::from_public_key(address_type, expected.network, &public_key)?;
+    log::debug!("Recovered address: {}", derived);
+
+    let address_valid = signature.allows(expected.address_type) && derived == expected;
+    if address_valid {
+        log::info!("Signature verification successful!");
+        log::info!("Address matches: {}", derived);
+    } else {
+        log::warn!("Signature verification failed!");
+        log::warn!("Recovered address does not match expected address");
+    }
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true,          // Challenge is what we signed
+        signature_valid: address_valid, // If address matches, signature is valid
+        derived_address: derived.to_string(),
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn test_message_hash_layout() {
+        let mut data = vec![0x18];
+        data.extend_from_slice(b"Bitcoin Signed Message:\n");
+        data.push(5);
+        data.extend_from_slice(b"hello");
+        let expected: [u8; 32] = Sha256::digest(Sha256::digest(&data)).into();
+
+        assert_eq!(bitcoin_message_hash(b"hello"), expected);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_headers_and_lengths() {
+        let mut bytes = [0x11u8; 65];
+
+        bytes[0] = 26;
+        assert!(BitcoinSignature::parse(&BASE64.encode(bytes)).is_err());
+        bytes[0] = 43;
+        assert!(BitcoinSignature::parse(&BASE64.encode(bytes)).is_err());
+        assert!(BitcoinSignature::parse(&BASE64.encode([0x1f; 64])).is_err());
+        assert!(BitcoinSignature::parse("not base64!").is_err());
+
+        bytes[0] = 40;
+        let signature = BitcoinSignature::parse(&BASE64.encode(bytes)).unwrap();
+        assert!(signature.compressed);
+        assert!(signature.allows(BitcoinAddressType::P2wpkh));
+        assert!(!signature.allows(BitcoinAddressType::P2pkh));
+    }
+}
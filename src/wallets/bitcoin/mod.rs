@@ -0,0 +1,57 @@
+//! Bitcoin Provider
+//!
+//! This module provides signature verification for Bitcoin wallets using the
+//! "Sign Message" feature (BIP-137 and the Electrum variant).
+//!
+//! # Supported Wallets
+//!
+//! - Bitcoin Core (`signmessage`)
+//! - Electrum
+//! - Trezor, Ledger and other BIP-137 signers
+//! - Xverse, Leather, Unisat and other BIP-322 signers
+//!
+//! # Signature Format
+//!
+//! - Message hash: `SHA256(SHA256(varint(24) || "Bitcoin Signed Message:\n" || varint(len) || message))`
+//! - Signature: base64 of 65 bytes `header || r || s`; the header carries the recovery ID
+//!   and the address type (27-30 P2PKH uncompressed, 31-34 P2PKH compressed,
+//!   35-38 P2SH-P2WPKH, 39-42 P2WPKH)
+//! - Address: P2PKH (`1...`), P2SH-P2WPKH (`3...`) or P2WPKH (`bc1q...`), mainnet or testnet
+//!
+//! Electrum signs SegWit addresses with the compressed P2PKH header (31-34), so that
+//! header is accepted for any of the three address types.
+//!
+//! # BIP-322
+//!
+//! Taproot (`bc1p...`) addresses cannot sign BIP-137 messages. Wallets such as Xverse,
+//! Leather and Unisat sign them with BIP-322 instead: the message is committed to by a
+//! virtual `to_spend` transaction paying to the address, and the signature is the
+//! witness (simple format) or the whole signed `to_sign` transaction (full format)
+//! spending it. Select [`BitcoinSigningScheme::Bip322`] to verify these; P2WPKH,
+//! P2SH-P2WPKH and Taproot key path (BIP-340 Schnorr) spends are supported.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{get_wallet_provider, WalletType, VerificationInput};
+//!
+//! let input = VerificationInput {
+//!     signature_data: "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=".to_string(),
+//!     expected_address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = get_wallet_provider(WalletType::Bitcoin);
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod address;
+pub mod bip322;
+pub mod core;
+mod provider;
+pub mod transaction;
+
+pub use address::{BitcoinAddress, BitcoinAddressType, BitcoinNetwork};
+pub use provider::{BitcoinProvider, BitcoinSigningScheme};
@@ -0,0 +1,115 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::address::{BitcoinAddress, BitcoinAddressType};
+use super::bip322::verify_bip322_signature;
+use super::core::verify_bitcoin_signature;
+use crate::types::VerificationResult;
+
+/// How the Bitcoin wallet produced the signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitcoinSigningScheme {
+    /// BIP-137 / Electrum "Sign Message" (65-byte compact, key recovery)
+    #[default]
+    Bip137,
+    /// BIP-322 generic signed message (simple or full format), required for Taproot;
+    /// legacy 65-byte signatures are still accepted for non-Taproot addresses
+    Bip322,
+}
+
+/// Provider for Bitcoin signed messages
+///
+/// With [`BitcoinSigningScheme::Bip137`] (the default) the public key is recovered from
+/// the signature, so the challenge and address are all that is needed.
+/// [`BitcoinSigningScheme::Bip322`] verifies the witness of the BIP-322 virtual
+/// transaction instead, which is how Xverse, Leather and Unisat sign for Taproot
+/// (`bc1p...`) addresses.
+///
+/// ```rust,no_run
+/// use wallet_signature_verify::wallets::{
+///     BitcoinProvider, BitcoinSigningScheme, VerificationInput, WalletProvider,
+/// };
+///
+/// let provider = BitcoinProvider::new().with_signing_scheme(BitcoinSigningScheme::Bip322);
+/// let input = VerificationInput {
+///     signature_data: "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==".to_string(),
+///     expected_address: "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3".to_string(),
+///     challenge: Some("Hello World".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let result = provider.verify(&input)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BitcoinProvider {
+    signing_scheme: BitcoinSigningScheme,
+}
+
+impl BitcoinProvider {
+    /// Creates a provider for BIP-137 signatures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the signing scheme the wallet used
+    pub fn with_signing_scheme(mut self, signing_scheme: BitcoinSigningScheme) -> Self {
+        self.signing_scheme = signing_scheme;
+        self
+    }
+}
+
+impl WalletProvider for BitcoinProvider {
+    fn name(&self) -> &str {
+        "Bitcoin"
+    }
+
+    fn description(&self) -> &str {
+        "Bitcoin - BIP-137 / Electrum and BIP-322 signed message verification"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Bitcoin: challenge is required for verification"
+            ));
+        }
+
+        if input.signature_data.is_empty() {
+            return Err(anyhow::anyhow!("Bitcoin: signature_data is required"));
+        }
+
+        // Validate that address is a supported single-key Bitcoin address
+        let address = input
+            .expected_address
+            .parse::<BitcoinAddress>()
+            .map_err(|e| anyhow::anyhow!("Bitcoin: {}", e))?;
+
+        if address.address_type == BitcoinAddressType::P2tr
+            && self.signing_scheme == BitcoinSigningScheme::Bip137
+        {
+            return Err(anyhow::anyhow!(
+                "Bitcoin: Taproot addresses require the BIP-322 signing scheme"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Bitcoin requires challenge"))?;
+
+        match self.signing_scheme {
+            BitcoinSigningScheme::Bip137 => {
+                verify_bitcoin_signature(&input.signature_data, challenge, &input.expected_address)
+            }
+            BitcoinSigningScheme::Bip322 => {
+                verify_bip322_signature(&input.signature_data, challenge, &input.expected_address)
+            }
+        }
+    }
+}
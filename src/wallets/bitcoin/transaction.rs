@@ -0,0 +1,392 @@
+use crate::crypto::{sha256d, tagged_hash};
+use sha2::{Digest, Sha256};
+
+/// `SIGHASH_DEFAULT`: Taproot signatures without an explicit hash type byte
+pub const SIGHASH_DEFAULT: u8 = 0x00;
+/// `SIGHASH_ALL`: the signature commits to every input and output
+pub const SIGHASH_ALL: u8 = 0x01;
+
+/// Appends a Bitcoin CompactSize (varint) length
+pub(crate) fn write_varint(out: &mut Vec<u8>, len: usize) {
+    match len {
+        0..=0xfc => out.push(len as u8),
+        0xfd..=0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        _ => {
+            out.push(0xff);
+            out.extend_from_slice(&(len as u64).to_le_bytes());
+        }
+    }
+}
+
+/// Appends varint-length-prefixed bytes
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+/// Cursor over consensus-encoded bytes
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(anyhow::anyhow!("Unexpected end of data"));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn varint(&mut self) -> anyhow::Result<usize> {
+        let len = match self.u8()? {
+            0xfd => u16::from_le_bytes(self.take(2)?.try_into()?) as u64,
+            0xfe => self.u32()? as u64,
+            0xff => self.u64()?,
+            n => n as u64,
+        };
+        // Anything longer than the remaining data is malformed
+        if len > self.data.len() as u64 {
+            return Err(anyhow::anyhow!("Length {} exceeds remaining data", len));
+        }
+        Ok(len as usize)
+    }
+
+    fn bytes(&mut self) -> anyhow::Result<Vec<u8>> {
+        let len = self.varint()?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn finish(&self) -> anyhow::Result<()> {
+        if !self.data.is_empty() {
+            return Err(anyhow::anyhow!("{} trailing bytes", self.data.len()));
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a witness stack: item count, then each item length-prefixed
+pub fn encode_witness(witness: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_varint(&mut out, witness.len());
+    for item in witness {
+        write_bytes(&mut out, item);
+    }
+    out
+}
+
+/// Decodes a consensus-encoded witness stack, rejecting trailing data
+pub fn decode_witness(data: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+    let mut reader = Reader { data };
+    let witness = read_witness(&mut reader)?;
+    reader.finish()?;
+    Ok(witness)
+}
+
+fn read_witness(reader: &mut Reader) -> anyhow::Result<Vec<Vec<u8>>> {
+    let count = reader.varint()?;
+    (0..count).map(|_| reader.bytes()).collect()
+}
+
+/// Reference to a transaction output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutPoint {
+    /// Txid in internal byte order (as hashed, not as displayed)
+    pub txid: [u8; 32],
+    pub vout: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    pub previous_output: OutPoint,
+    pub script_sig: Vec<u8>,
+    pub sequence: u32,
+    pub witness: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    /// Amount in satoshis
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+/// Minimal Bitcoin transaction, enough to build and sign BIP-322 virtual transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub version: i32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+}
+
+impl Transaction {
+    /// Consensus encoding, in the BIP-144 SegWit format when any input has a witness
+    pub fn serialize(&self) -> Vec<u8> {
+        self.encode(self.inputs.iter().any(|input| !input.witness.is_empty()))
+    }
+
+    fn encode(&self, with_witness: bool) -> Vec<u8> {
+        let mut out = self.version.to_le_bytes().to_vec();
+        if with_witness {
+            out.extend_from_slice(&[0x00, 0x01]);
+        }
+
+        write_varint(&mut out, self.inputs.len());
+        for input in &self.inputs {
+            out.extend_from_slice(&input.previous_output.txid);
+            out.extend_from_slice(&input.previous_output.vout.to_le_bytes());
+            write_bytes(&mut out, &input.script_sig);
+            out.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+
+        write_varint(&mut out, self.outputs.len());
+        for output in &self.outputs {
+            out.extend_from_slice(&output.value.to_le_bytes());
+            write_bytes(&mut out, &output.script_pubkey);
+        }
+
+        if with_witness {
+            for input in &self.inputs {
+                out.extend_from_slice(&encode_witness(&input.witness));
+            }
+        }
+
+        out.extend_from_slice(&self.lock_time.to_le_bytes());
+        out
+    }
+
+    /// Decodes a consensus-encoded transaction (legacy or SegWit format)
+    pub fn deserialize(data: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = Reader { data };
+        let version = reader.u32()? as i32;
+
+        let mut input_count = reader.varint()?;
+        let with_witness = input_count == 0;
+        if with_witness {
+            if reader.u8()? != 0x01 {
+                return Err(anyhow::anyhow!("Invalid SegWit flag"));
+            }
+            input_count = reader.varint()?;
+        }
+
+        let mut inputs = Vec::with_capacity(input_count);
+        for _ in 0..input_count {
+            let txid = reader.take(32)?.try_into()?;
+            let vout = reader.u32()?;
+            inputs.push(TxIn {
+                previous_output: OutPoint { txid, vout },
+                script_sig: reader.bytes()?,
+                sequence: reader.u32()?,
+                witness: Vec::new(),
+            });
+        }
+
+        let output_count = reader.varint()?;
+        let mut outputs = Vec::with_capacity(output_count);
+        for _ in 0..output_count {
+            outputs.push(TxOut {
+                value: reader.u64()?,
+                script_pubkey: reader.bytes()?,
+            });
+        }
+
+        if with_witness {
+            for input in &mut inputs {
+                input.witness = read_witness(&mut reader)?;
+            }
+        }
+
+        let lock_time = reader.u32()?;
+        reader.finish()?;
+
+        Ok(Transaction {
+            version,
+            inputs,
+            outputs,
+            lock_time,
+        })
+    }
+
+    /// Transaction ID (double SHA-256 of the encoding without witnesses), internal byte order
+    pub fn txid(&self) -> [u8; 32] {
+        sha256d(&self.encode(false))
+    }
+
+    fn serialized_prevouts(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for input in &self.inputs {
+            out.extend_from_slice(&input.previous_output.txid);
+            out.extend_from_slice(&input.previous_output.vout.to_le_bytes());
+        }
+        out
+    }
+
+    fn serialized_sequences(&self) -> Vec<u8> {
+        self.inputs
+            .iter()
+            .flat_map(|input| input.sequence.to_le_bytes())
+            .collect()
+    }
+
+    fn serialized_outputs(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for output in &self.outputs {
+            out.extend_from_slice(&output.value.to_le_bytes());
+            write_bytes(&mut out, &output.script_pubkey);
+        }
+        out
+    }
+
+    /// BIP-143 (SegWit v0) signature hash of input `index` with `SIGHASH_ALL`
+    pub fn segwit_v0_sighash(&self, index: usize, script_code: &[u8], amount: u64) -> [u8; 32] {
+        let input = &self.inputs[index];
+
+        let mut preimage = self.version.to_le_bytes().to_vec();
+        preimage.extend_from_slice(&sha256d(&self.serialized_prevouts()));
+        preimage.extend_from_slice(&sha256d(&self.serialized_sequences()));
+        preimage.extend_from_slice(&input.previous_output.txid);
+        preimage.extend_from_slice(&input.previous_output.vout.to_le_bytes());
+        write_bytes(&mut preimage, script_code);
+        preimage.extend_from_slice(&amount.to_le_bytes());
+        preimage.extend_from_slice(&input.sequence.to_le_bytes());
+        preimage.extend_from_slice(&sha256d(&self.serialized_outputs()));
+        preimage.extend_from_slice(&self.lock_time.to_le_bytes());
+        preimage.extend_from_slice(&(SIGHASH_ALL as u32).to_le_bytes());
+
+        sha256d(&preimage)
+    }
+
+    /// BIP-341 (Taproot key path) signature hash of input `index`
+    ///
+    /// `spent_outputs` are the outputs spent by every input, in order. Only
+    /// [`SIGHASH_DEFAULT`] and [`SIGHASH_ALL`] are supported.
+    pub fn taproot_key_spend_sighash(
+        &self,
+        index: usize,
+        spent_outputs: &[TxOut],
+        sighash_type: u8,
+    ) -> anyhow::Result<[u8; 32]> {
+        if sighash_type != SIGHASH_DEFAULT && sighash_type != SIGHASH_ALL {
+            return Err(anyhow::anyhow!(
+                "Unsupported Taproot sighash type 0x{:02x}",
+                sighash_type
+            ));
+        }
+        if spent_outputs.len() != self.inputs.len() {
+            return Err(anyhow::anyhow!(
+                "Expected {} spent outputs, got {}",
+                self.inputs.len(),
+                spent_outputs.len()
+            ));
+        }
+
+        let mut amounts = Vec::new();
+        let mut script_pubkeys = Vec::new();
+        for output in spent_outputs {
+            amounts.extend_from_slice(&output.value.to_le_bytes());
+            write_bytes(&mut script_pubkeys, &output.script_pubkey);
+        }
+
+        // Epoch 0, then the SigMsg of BIP-341 for a key path spend without annex
+        let mut message = vec![0x00, sighash_type];
+        message.extend_from_slice(&self.version.to_le_bytes());
+        message.extend_from_slice(&self.lock_time.to_le_bytes());
+        message.extend_from_slice(&Sha256::digest(self.serialized_prevouts()));
+        message.extend_from_slice(&Sha256::digest(&amounts));
+        message.extend_from_slice(&Sha256::digest(&script_pubkeys));
+        message.extend_from_slice(&Sha256::digest(self.serialized_sequences()));
+        message.extend_from_slice(&Sha256::digest(self.serialized_outputs()));
+        message.push(0x00); // spend_type: key path, no annex
+        message.extend_from_slice(&(index as u32).to_le_bytes());
+
+        Ok(tagged_hash("TapSighash", &message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_transaction() -> Transaction {
+        Transaction {
+            version: 2,
+            inputs: vec![TxIn {
+                previous_output: OutPoint {
+                    txid: [0xab; 32],
+                    vout: 1,
+                },
+                script_sig: vec![0x51],
+                sequence: 0xffff_fffd,
+                witness: vec![vec![0x01; 72], vec![0x02; 33]],
+            }],
+            outputs: vec![TxOut {
+                value: 50_000,
+                script_pubkey: vec![0x6a],
+            }],
+            lock_time: 800_000,
+        }
+    }
+
+    #[test]
+    fn test_varint_encoding() {
+        let encode = |len| {
+            let mut out = Vec::new();
+            write_varint(&mut out, len);
+            out
+        };
+
+        assert_eq!(encode(0x18), [0x18]);
+        assert_eq!(encode(0xfc), [0xfc]);
+        assert_eq!(encode(0xfd), [0xfd, 0xfd, 0x00]);
+        assert_eq!(encode(0x1_0000), [0xfe, 0x00, 0x00, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_transaction_roundtrip() {
+        let transaction = sample_transaction();
+
+        let encoded = transaction.serialize();
+        assert_eq!(encoded[4..6], [0x00, 0x01]);
+        assert_eq!(Transaction::deserialize(&encoded).unwrap(), transaction);
+
+        // The txid ignores the witness
+        let mut stripped = transaction.clone();
+        stripped.inputs[0].witness.clear();
+        assert_eq!(stripped.txid(), transaction.txid());
+        assert_eq!(
+            Transaction::deserialize(&stripped.serialize()).unwrap(),
+            stripped
+        );
+    }
+
+    #[test]
+    fn test_witness_roundtrip_and_trailing_data() {
+        let witness = sample_transaction().inputs[0].witness.clone();
+        let encoded = encode_witness(&witness);
+
+        assert_eq!(decode_witness(&encoded).unwrap(), witness);
+        assert!(decode_witness(&[encoded, vec![0x00]].concat()).is_err());
+        assert!(decode_witness(&[0x02, 0x05, 0x01]).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use bech32::{Bech32, Hrp};
+use blake2::{digest::consts::U28, Blake2b, Digest};
+
+/// BLAKE2b-224, the hash behind Cardano key and script credentials
+pub fn blake2b_224(data: &[u8]) -> [u8; 28] {
+    Blake2b::<U28>::digest(data).into()
+}
+
+/// Shelley address (CIP-19) in its raw byte form
+///
+/// The first byte holds the address type (high nibble) and network ID (low nibble,
+/// `1` for mainnet); it is followed by the payment and/or stake credentials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardanoAddress {
+    pub bytes: Vec<u8>,
+}
+
+impl CardanoAddress {
+    /// Decodes a bech32 `addr1...`, `addr_test1...`, `stake1...` or `stake_test1...` address
+    pub fn from_bech32(address: &str) -> anyhow::Result<Self> {
+        let (hrp, bytes) = bech32::decode(address)
+            .map_err(|e| anyhow::anyhow!("Invalid Cardano address '{}': {}", address, e))?;
+        let parsed = Self::from_bytes(bytes)?;
+
+        if hrp.as_str() != parsed.hrp() {
+            return Err(anyhow::anyhow!(
+                "Invalid Cardano address '{}': prefix '{}' does not match its header (expected '{}')",
+                address,
+                hrp,
+                parsed.hrp()
+            ));
+        }
+        Ok(parsed)
+    }
+
+    /// Wraps raw address bytes (such as the COSE `address` header), checking the header
+    pub fn from_bytes(bytes: Vec<u8>) -> anyhow::Result<Self> {
+        let address_type = *bytes
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Empty Cardano address"))?
+            >> 4;
+
+        let expected_len = match address_type {
+            // Base addresses: payment and stake credentials
+            0..=3 => Some(57),
+            // Pointer addresses: payment credential and a variable-length pointer
+            4 | 5 => None,
+            // Enterprise and reward addresses: a single credential
+            6 | 7 | 14 | 15 => Some(29),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported Cardano address type {} (Byron and other legacy addresses cannot sign messages)",
+                    address_type
+                ))
+            }
+        };
+        if expected_len.is_some_and(|len| len != bytes.len()) || bytes.len() < 29 {
+            return Err(anyhow::anyhow!(
+                "Invalid Cardano address: {} bytes for address type {}",
+                bytes.len(),
+                address_type
+            ));
+        }
+
+        Ok(CardanoAddress { bytes })
+    }
+
+    /// Address type from the header (0-7 payment addresses, 14-15 reward addresses)
+    pub fn address_type(&self) -> u8 {
+        self.bytes[0] >> 4
+    }
+
+    /// Network ID from the header (`1` mainnet, `0` testnets)
+    pub fn network_id(&self) -> u8 {
+        self.bytes[0] & 0x0f
+    }
+
+    /// Whether this is a reward (`stake...`) address
+    pub fn is_reward(&self) -> bool {
+        self.address_type() >= 14
+    }
+
+    /// Bech32 prefix for the address type and network
+    pub fn hrp(&self) -> &'static str {
+        match (self.is_reward(), self.network_id() == 1) {
+            (false, true) => "addr",
+            (false, false) => "addr_test",
+            (true, true) => "stake",
+            (true, false) => "stake_test",
+        }
+    }
+
+    /// Hash of the key that signs for this address: the payment key hash, or the stake
+    /// key hash of a reward address. `None` when that credential is a script.
+    pub fn signing_key_hash(&self) -> Option<&[u8]> {
+        // Odd address types (and 15) carry a script hash as their first credential
+        let is_script = match self.address_type() {
+            14 => false,
+            15 => true,
+            address_type => address_type % 2 == 1,
+        };
+        (!is_script).then(|| &self.bytes[1..29])
+    }
+
+    /// Bech32 form of the address
+    pub fn to_bech32(&self) -> anyhow::Result<String> {
+        let hrp = Hrp::parse(self.hrp()).map_err(|e| anyhow::anyhow!("Invalid prefix: {}", e))?;
+        bech32::encode::<Bech32>(hrp, &self.bytes)
+            .map_err(|e| anyhow::anyhow!("Failed to encode Cardano address: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CIP-19 test vector keys (`addr_vk1w0l2...` and `stake_vk1px4j...`)
+    const PAYMENT_KEY: &str = "73fea80d424276ad0978d4fe5310e8bc2d485f5f6bb3bf87612989f112ad5a7d";
+    const STAKE_KEY: &str = "09ab278d49b7b86a055185c474c4942281ddfa05a54684c7e8a6f230625aee57";
+
+    fn key_hash(key: &str) -> Vec<u8> {
+        blake2b_224(&hex::decode(key).unwrap()).to_vec()
+    }
+
+    #[test]
+    fn test_cip19_address_vectors() {
+        let payment = key_hash(PAYMENT_KEY);
+        let stake = key_hash(STAKE_KEY);
+
+        for (address, header, credentials) in [
+            (
+                "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x",
+                0x01,
+                [payment.clone(), stake.clone()].concat(),
+            ),
+            (
+                "addr_test1qz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs68faae",
+                0x00,
+                [payment.clone(), stake.clone()].concat(),
+            ),
+            (
+                "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8",
+                0x61,
+                payment.clone(),
+            ),
+            (
+                "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw",
+                0xe1,
+                stake.clone(),
+            ),
+        ] {
+            let parsed = CardanoAddress::from_bech32(address).unwrap();
+            assert_eq!(parsed.bytes, [vec![header], credentials].concat());
+            assert_eq!(parsed.to_bech32().unwrap(), address);
+        }
+
+        let reward = CardanoAddress::from_bech32(
+            "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw",
+        )
+        .unwrap();
+        assert_eq!(reward.signing_key_hash().unwrap(), stake.as_slice());
+    }
+
+    #[test]
+    fn test_rejects_mismatched_prefix_and_script_credentials() {
+        let enterprise = CardanoAddress::from_bech32(
+            "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8",
+        )
+        .unwrap();
+
+        // Same bytes under the testnet prefix
+        let hrp = Hrp::parse("addr_test").unwrap();
+        let relabeled = bech32::encode::<Bech32>(hrp, &enterprise.bytes).unwrap();
+        assert!(CardanoAddress::from_bech32(&relabeled).is_err());
+
+        // Enterprise script address (type 7) has no signing key
+        let mut script = enterprise.bytes.clone();
+        script[0] = 0x71;
+        assert!(CardanoAddress::from_bytes(script)
+            .unwrap()
+            .signing_key_hash()
+            .is_none());
+
+        assert!(CardanoAddress::from_bytes(vec![0x61; 10]).is_err());
+    }
+}
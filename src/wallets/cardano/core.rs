@@ -0,0 +1,388 @@
+use super::address::{blake2b_224, CardanoAddress};
+use crate::caip::parse_sign_in_challenge;
+use crate::cbor::CborValue;
+use crate::types::VerificationResult;
+use ed25519_dalek::{Signature, VerifyingKey};
+
+/// COSE tag of a `COSE_Sign1` structure
+pub const COSE_SIGN1_TAG: u64 = 18;
+
+/// COSE algorithm identifier for EdDSA
+pub const COSE_ALG_EDDSA: i128 = -8;
+
+/// How the wallet hashed the payload before signing (CIP-8 `hashed` header)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CardanoPayloadHashing {
+    /// Follow the `hashed` unprotected header of the signature
+    #[default]
+    Auto,
+    /// Require the raw challenge as payload (`hashed: false`, what CIP-30 wallets do)
+    Unhashed,
+    /// Require the BLAKE2b-224 of the challenge as payload (`hashed: true`)
+    Hashed,
+}
+
+/// `COSE_Sign1` structure returned as the `signature` of a CIP-30 `DataSignature`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoseSign1 {
+    /// Serialized protected header map, as signed
+    pub protected: Vec<u8>,
+    pub protected_headers: CborValue,
+    pub unprotected_headers: CborValue,
+    /// Signed payload, `None` when detached
+    pub payload: Option<Vec<u8>>,
+    pub signature: [u8; 64],
+}
+
+impl CoseSign1 {
+    /// Decodes a (possibly tagged) `COSE_Sign1` from CBOR bytes
+    pub fn from_cbor(data: &[u8]) -> anyhow::Result<Self> {
+        let value = match CborValue::decode(data)? {
+            CborValue::Tag(COSE_SIGN1_TAG, item) => *item,
+            CborValue::Tag(tag, _) => {
+                return Err(anyhow::anyhow!(
+                    "Unexpected CBOR tag {} for COSE_Sign1",
+                    tag
+                ))
+            }
+            value => value,
+        };
+
+        let CborValue::Array(items) = value else {
+            return Err(anyhow::anyhow!("COSE_Sign1 must be a CBOR array"));
+        };
+        let [protected, unprotected_headers, payload, signature]: [CborValue; 4] =
+            items.try_into().map_err(|items: Vec<_>| {
+                anyhow::anyhow!("COSE_Sign1 must have 4 elements, got {}", items.len())
+            })?;
+
+        let protected = protected
+            .as_bytes()
+            .ok_or_else(|| anyhow::anyhow!("COSE_Sign1 protected header must be a byte string"))?
+            .to_vec();
+        let protected_headers = if protected.is_empty() {
+            CborValue::Map(Vec::new())
+        } else {
+            CborValue::decode(&protected)?
+        };
+        if !matches!(protected_headers, CborValue::Map(_))
+            || !matches!(unprotected_headers, CborValue::Map(_))
+        {
+            return Err(anyhow::anyhow!("COSE_Sign1 headers must be maps"));
+        }
+
+        let payload = match payload {
+            CborValue::Bytes(bytes) => Some(bytes),
+            CborValue::Null => None,
+            _ => return Err(anyhow::anyhow!("COSE_Sign1 payload must be bytes or null")),
+        };
+        let signature = signature
+            .as_bytes()
+            .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+            .ok_or_else(|| anyhow::anyhow!("COSE_Sign1 signature must be 64 bytes"))?;
+
+        Ok(CoseSign1 {
+            protected,
+            protected_headers,
+            unprotected_headers,
+            payload,
+            signature,
+        })
+    }
+
+    /// Algorithm (`alg`, label 1) of the protected header
+    pub fn algorithm(&self) -> Option<i128> {
+        self.protected_headers
+            .get(&CborValue::Int(1))
+            .and_then(CborValue::as_int)
+    }
+
+    /// Raw address bytes of the protected `address` header (CIP-8)
+    pub fn address(&self) -> Option<&[u8]> {
+        self.protected_headers
+            .get(&CborValue::Text("address".to_string()))
+            .and_then(CborValue::as_bytes)
+    }
+
+    /// Whether the unprotected `hashed` header marks the payload as BLAKE2b-224 hashed
+    pub fn is_hashed(&self) -> bool {
+        self.unprotected_headers
+            .get(&CborValue::Text("hashed".to_string()))
+            .is_some_and(|hashed| *hashed == CborValue::Bool(true))
+    }
+
+    /// Serialized `Sig_structure` for `payload`: `["Signature1", protected, h'', payload]`
+    pub fn sig_structure(&self, payload: &[u8]) -> Vec<u8> {
+        CborValue::Array(vec![
+            CborValue::Text("Signature1".to_string()),
+            CborValue::Bytes(self.protected.clone()),
+            CborValue::Bytes(Vec::new()),
+            CborValue::Bytes(payload.to_vec()),
+        ])
+        .encode()
+    }
+}
+
+/// CIP-30 `DataSignature`: hex `COSE_Sign1` and, when known, hex `COSE_Key`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardanoDataSignature {
+    pub signature: String,
+    pub key: Option<String>,
+}
+
+impl CardanoDataSignature {
+    /// Parses a hex `COSE_Sign1`, or the `{"signature":"...","key":"..."}` JSON object
+    /// returned by `signData`
+    pub fn parse(signature: &str) -> anyhow::Result<Self> {
+        let signature = signature.trim();
+
+        if signature.starts_with('{') {
+            let json: serde_json::Value = serde_json::from_str(signature)
+                .map_err(|e| anyhow::anyhow!("Failed to parse DataSignature JSON: {}", e))?;
+            let cose_sign1 = json["signature"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("DataSignature is missing 'signature'"))?;
+
+            return Ok(CardanoDataSignature {
+                signature: cose_sign1.to_string(),
+                key: json["key"].as_str().map(str::to_string),
+            });
+        }
+
+        Ok(CardanoDataSignature {
+            signature: signature.to_string(),
+            key: None,
+        })
+    }
+}
+
+/// Decodes a hex `COSE_Sign1`
+pub fn parse_cose_sign1(cose_sign1_hex: &str) -> anyhow::Result<CoseSign1> {
+    let bytes = hex::decode(cose_sign1_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("COSE_Sign1 is not valid hex: {}", e))?;
+    CoseSign1::from_cbor(&bytes)
+}
+
+/// Extracts the ed25519 public key from a hex `COSE_Key` (OKP, crv Ed25519), or a raw
+/// 32-byte hex key
+pub fn parse_cose_key(key_hex: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = hex::decode(key_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("COSE_Key is not valid hex: {}", e))?;
+    if let Ok(key) = <[u8; 32]>::try_from(bytes.as_slice()) {
+        return Ok(key);
+    }
+
+    let key = CborValue::decode(&bytes)?;
+    // kty (1) = OKP (1), crv (-1) = Ed25519 (6)
+    if key.get(&CborValue::Int(1)) != Some(&CborValue::Int(1))
+        || key.get(&CborValue::Int(-1)) != Some(&CborValue::Int(6))
+    {
+        return Err(anyhow::anyhow!("COSE_Key is not an Ed25519 OKP key"));
+    }
+    if key
+        .get(&CborValue::Int(3))
+        .is_some_and(|alg| *alg != CborValue::Int(COSE_ALG_EDDSA))
+    {
+        return Err(anyhow::anyhow!("COSE_Key algorithm is not EdDSA"));
+    }
+
+    // x (-2): the public key bytes
+    key.get(&CborValue::Int(-2))
+        .and_then(CborValue::as_bytes)
+        .and_then(|x| <[u8; 32]>::try_from(x).ok())
+        .ok_or_else(|| anyhow::anyhow!("COSE_Key is missing a 32-byte 'x' coordinate"))
+}
+
+/// Payloads a wallet may have signed for the challenge: its UTF-8 bytes, and the decoded
+/// bytes when the challenge is itself hex (CIP-30 takes the payload as hex)
+fn challenge_payloads(challenge: &str, hashed: bool) -> Vec<Vec<u8>> {
+    let mut payloads = vec![challenge.as_bytes().to_vec()];
+    if let Ok(bytes) = hex::decode(challenge.trim_start_matches("0x")) {
+        payloads.push(bytes);
+    }
+
+    if hashed {
+        payloads.iter().map(|p| blake2b_224(p).to_vec()).collect()
+    } else {
+        payloads
+    }
+}
+
+/// Verifies a CIP-30 `signData` signature (CIP-8 message signing)
+///
+/// - `address_valid`: the protected `address` header is the expected address, and the
+///   COSE key hashes to its payment (or, for `stake...` addresses, stake) credential
+/// - `challenge_valid`: the embedded payload is the challenge (or its BLAKE2b-224 hash)
+/// - `signature_valid`: the ed25519 signature over the `Sig_structure` is valid under
+///   `verify_strict`, which rejects small-order keys and non-canonical signatures
+pub fn verify_cardano_signature(
+    data_signature: &str,
+    public_key: Option<&str>,
+    challenge: &str,
+    expected_address: &str,
+    hashing: CardanoPayloadHashing,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Cardano verification starting");
+    log::debug!("Signature: {}", data_signature);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let data_signature = CardanoDataSignature::parse(data_signature)?;
+    let cose_sign1 = parse_cose_sign1(&data_signature.signature)?;
+    let key_hex = public_key
+        .or(data_signature.key.as_deref())
+        .ok_or_else(|| anyhow::anyhow!("Cardano verification requires the COSE_Key"))?;
+    let public_key = parse_cose_key(key_hex)?;
+    let expected = CardanoAddress::from_bech32(expected_address)?;
+
+    if cose_sign1
+        .algorithm()
+        .is_some_and(|alg| alg != COSE_ALG_EDDSA)
+    {
+        return Err(anyhow::anyhow!("COSE_Sign1 algorithm is not EdDSA"));
+    }
+
+    let hashed = cose_sign1.is_hashed();
+    match (hashing, hashed) {
+        (CardanoPayloadHashing::Unhashed, true) => {
+            return Err(anyhow::anyhow!("Cardano payload must not be hashed"))
+        }
+        (CardanoPayloadHashing::Hashed, false) => {
+            return Err(anyhow::anyhow!("Cardano payload must be hashed"))
+        }
+        _ => {}
+    }
+
+    // Address: protected header and key credential
+    let header_address = cose_sign1
+        .address()
+        .ok_or_else(|| anyhow::anyhow!("COSE_Sign1 is missing the 'address' header"))?;
+    let derived_address = CardanoAddress::from_bytes(header_address.to_vec())
+        .and_then(|address| address.to_bech32())
+        .unwrap_or_default();
+    let key_matches = expected
+        .signing_key_hash()
+        .is_some_and(|hash| hash == blake2b_224(&public_key));
+    let address_valid = header_address == expected.bytes.as_slice() && key_matches;
+
+    log::debug!("Signed address: {}", derived_address);
+    if !address_valid {
+        log::warn!("Signed address or COSE key does not match the expected address");
+    }
+
+    // Challenge: embedded payload, or the challenge itself when detached
+    let candidates = challenge_payloads(challenge, hashed);
+    let (challenge_valid, signed_payloads) = match &cose_sign1.payload {
+        Some(payload) => (candidates.contains(payload), vec![payload.clone()]),
+        None => (true, candidates),
+    };
+    if !challenge_valid {
+        log::warn!("Signed payload does not match the challenge");
+    }
+
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|e| anyhow::anyhow!("Invalid ed25519 public key: {}", e))?;
+    let signature = Signature::from_bytes(&cose_sign1.signature);
+    let signature_valid = signed_payloads.iter().any(|payload| {
+        verifying_key
+            .verify_strict(&cose_sign1.sig_structure(payload), &signature)
+            .is_ok()
+    });
+
+    if signature_valid {
+        log::info!("Cardano signature verification successful!");
+    } else {
+        log::warn!("Cardano signature verification failed");
+    }
+
+    let found_challenge = match &cose_sign1.payload {
+        Some(payload) if !hashed => String::from_utf8(payload.clone()).ok(),
+        _ => Some(challenge.to_string()),
+    };
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid,
+        signature_valid,
+        derived_address,
+        found_challenge,
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Protected header `{1: -8, "address": h'61...'}` for the CIP-19 enterprise address
+    fn protected_header() -> Vec<u8> {
+        let address = CardanoAddress::from_bech32(
+            "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8",
+        )
+        .unwrap();
+        CborValue::Map(vec![
+            (CborValue::Int(1), CborValue::Int(COSE_ALG_EDDSA)),
+            (
+                CborValue::Text("address".to_string()),
+                CborValue::Bytes(address.bytes),
+            ),
+        ])
+        .encode()
+    }
+
+    #[test]
+    fn test_parse_tagged_cose_sign1() {
+        let cose_sign1 = CborValue::Tag(
+            COSE_SIGN1_TAG,
+            Box::new(CborValue::Array(vec![
+                CborValue::Bytes(protected_header()),
+                CborValue::Map(vec![(
+                    CborValue::Text("hashed".to_string()),
+                    CborValue::Bool(true),
+                )]),
+                CborValue::Null,
+                CborValue::Bytes(vec![0x11; 64]),
+            ])),
+        );
+
+        let parsed = CoseSign1::from_cbor(&cose_sign1.encode()).unwrap();
+        assert_eq!(parsed.algorithm(), Some(COSE_ALG_EDDSA));
+        assert_eq!(parsed.address().unwrap()[0], 0x61);
+        assert!(parsed.is_hashed());
+        assert_eq!(parsed.payload, None);
+        assert_eq!(parsed.signature, [0x11; 64]);
+
+        // Sig_structure: array(4), "Signature1", protected, h'', payload
+        let sig_structure = parsed.sig_structure(b"hi");
+        assert_eq!(&sig_structure[..12], b"\x84\x6aSignature1");
+        assert!(sig_structure.ends_with(&[0x40, 0x42, b'h', b'i']));
+    }
+
+    #[test]
+    fn test_parse_cose_key() {
+        let key = CborValue::Map(vec![
+            (CborValue::Int(1), CborValue::Int(1)),
+            (CborValue::Int(3), CborValue::Int(COSE_ALG_EDDSA)),
+            (CborValue::Int(-1), CborValue::Int(6)),
+            (CborValue::Int(-2), CborValue::Bytes(vec![0x22; 32])),
+        ]);
+        assert_eq!(
+            parse_cose_key(&hex::encode(key.encode())).unwrap(),
+            [0x22; 32]
+        );
+        assert_eq!(
+            parse_cose_key(&hex::encode([0x33; 32])).unwrap(),
+            [0x33; 32]
+        );
+
+        // X25519 (crv 4) is not a signing key
+        let x25519 = CborValue::Map(vec![
+            (CborValue::Int(1), CborValue::Int(1)),
+            (CborValue::Int(-1), CborValue::Int(4)),
+            (CborValue::Int(-2), CborValue::Bytes(vec![0x22; 32])),
+        ]);
+        assert!(parse_cose_key(&hex::encode(x25519.encode())).is_err());
+    }
+}
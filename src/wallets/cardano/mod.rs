@@ -0,0 +1,52 @@
+//! Cardano Provider
+//!
+//! This module provides signature verification for Cardano wallets using CIP-30
+//! `signData`, which signs with CIP-8 (COSE) message signing.
+//!
+//! # Supported Wallets
+//!
+//! - Eternl
+//! - Nami
+//! - Lace, Typhon, Yoroi and other CIP-30 wallets
+//!
+//! # Signature Format
+//!
+//! - Signature: CBOR `COSE_Sign1` (hex) `[protected, unprotected, payload, signature]`;
+//!   the protected header carries `alg: EdDSA` and the signing `address`
+//! - Key: CBOR `COSE_Key` (hex) holding the ed25519 public key
+//! - Signed bytes: `Sig_structure = ["Signature1", protected, h'', payload]`
+//! - Payload: the challenge bytes, or their BLAKE2b-224 when the unprotected
+//!   `hashed` header is `true`
+//! - Address: bech32 Shelley address (`addr1...` or `stake1...`, CIP-19); the key must
+//!   hash to its payment credential (stake credential for `stake1...`)
+//!
+//! `signData` returns `{"signature": "<COSE_Sign1>", "key": "<COSE_Key>"}`: pass that
+//! JSON as the signature, or the `COSE_Sign1` as the signature and the `COSE_Key` as
+//! the public key.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{get_wallet_provider, WalletType, VerificationInput};
+//!
+//! let input = VerificationInput {
+//!     signature_data: "845846a201276761646472657373...".to_string(),
+//!     expected_address: "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     public_key: Some("a4010103272006215820...".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = get_wallet_provider(WalletType::Cardano);
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod address;
+pub mod core;
+mod provider;
+
+pub use crate::cbor;
+pub use address::CardanoAddress;
+pub use core::CardanoPayloadHashing;
+pub use provider::CardanoProvider;
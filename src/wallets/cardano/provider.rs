@@ -0,0 +1,77 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::address::CardanoAddress;
+use super::core::{verify_cardano_signature, CardanoPayloadHashing};
+use crate::types::VerificationResult;
+
+/// Provider for Cardano wallets signing with CIP-30 `signData`
+///
+/// The signature is the `COSE_Sign1` hex (or the whole `DataSignature` JSON) and the
+/// `COSE_Key` is passed as `public_key` unless it is part of that JSON. The signed
+/// address is read from the protected header and compared with the bech32 `addr1...`
+/// or `stake1...` expected address.
+#[derive(Debug, Clone, Default)]
+pub struct CardanoProvider {
+    payload_hashing: CardanoPayloadHashing,
+}
+
+impl CardanoProvider {
+    /// Creates a provider following the signature's `hashed` header
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the payload to be hashed (or not) instead of following the header
+    pub fn with_payload_hashing(mut self, payload_hashing: CardanoPayloadHashing) -> Self {
+        self.payload_hashing = payload_hashing;
+        self
+    }
+}
+
+impl WalletProvider for CardanoProvider {
+    fn name(&self) -> &str {
+        "Cardano"
+    }
+
+    fn description(&self) -> &str {
+        "Cardano - CIP-30 signData (COSE_Sign1) verification (Eternl, Nami, Lace)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Cardano: challenge is required for verification"
+            ));
+        }
+
+        // Validate that the COSE_Key is available, separately or in the DataSignature
+        if input.public_key.is_none() && !input.signature_data.trim_start().starts_with('{') {
+            return Err(anyhow::anyhow!(
+                "Cardano: public key (COSE_Key) is required for verification"
+            ));
+        }
+
+        // Validate that address is a bech32 Shelley address
+        CardanoAddress::from_bech32(&input.expected_address)
+            .map_err(|e| anyhow::anyhow!("Cardano: {}", e))?;
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Cardano requires challenge"))?;
+
+        verify_cardano_signature(
+            &input.signature_data,
+            input.public_key.as_deref(),
+            challenge,
+            &input.expected_address,
+            self.payload_hashing,
+        )
+    }
+}
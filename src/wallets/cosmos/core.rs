@@ -0,0 +1,229 @@
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::account_id_from_pubkey;
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use bech32::{Bech32, Hrp};
+use hex::FromHex;
+use secp256k1::{ecdsa, Message, PublicKey, SECP256K1};
+use sha2::{Digest, Sha256};
+
+/// Amino type of the ADR-036 message
+pub const MSG_SIGN_DATA_TYPE: &str = "sign/MsgSignData";
+
+/// Signature as returned by Keplr/Leap `signArbitrary` (`StdSignature`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CosmosSignature {
+    /// 64-byte `r || s`
+    pub signature: [u8; 64],
+    /// Base64 `pub_key.value`, when the signature came as a `StdSignature` JSON object
+    pub public_key: Option<String>,
+}
+
+impl CosmosSignature {
+    /// Parses a base64 (or hex) 64-byte signature, or the `StdSignature` JSON object
+    /// `{"pub_key":{"type":"tendermint/PubKeySecp256k1","value":"..."},"signature":"..."}`
+    pub fn parse(signature: &str) -> anyhow::Result<Self> {
+        let signature = signature.trim();
+
+        if signature.starts_with('{') {
+            let json: serde_json::Value = serde_json::from_str(signature)
+                .map_err(|e| anyhow::anyhow!("Failed to parse StdSignature JSON: {}", e))?;
+            let encoded = json["signature"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("StdSignature is missing 'signature'"))?;
+
+            return Ok(CosmosSignature {
+                signature: decode_signature_bytes(encoded)?,
+                public_key: json["pub_key"]["value"].as_str().map(str::to_string),
+            });
+        }
+
+        Ok(CosmosSignature {
+            signature: decode_signature_bytes(signature)?,
+            public_key: None,
+        })
+    }
+}
+
+/// Decodes a 64-byte signature given as hex (128 chars, optional `0x`) or base64
+fn decode_signature_bytes(signature: &str) -> anyhow::Result<[u8; 64]> {
+    let bytes = match <[u8; 64]>::from_hex(signature.trim_start_matches("0x")) {
+        Ok(bytes) => return Ok(bytes),
+        Err(_) => BASE64
+            .decode(signature)
+            .map_err(|e| anyhow::anyhow!("Signature is not valid base64 or hex: {}", e))?,
+    };
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid signature length: expected 64 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Parses a secp256k1 public key given as base64 (as Keplr returns it) or hex
+pub fn parse_cosmos_public_key(public_key: &str) -> anyhow::Result<PublicKey> {
+    let bytes = match Vec::from_hex(public_key.trim_start_matches("0x")) {
+        Ok(bytes) => bytes,
+        Err(_) => BASE64
+            .decode(public_key)
+            .map_err(|e| anyhow::anyhow!("Public key is not valid base64 or hex: {}", e))?,
+    };
+
+    PublicKey::from_slice(&bytes)
+        .map_err(|e| anyhow::anyhow!("Invalid secp256k1 public key: {}", e))
+}
+
+/// Bech32 account address of a secp256k1 key: `bech32(hrp, RIPEMD-160(SHA-256(compressed key)))`
+pub fn cosmos_address(hrp: &str, public_key: &PublicKey) -> anyhow::Result<String> {
+    let hrp = Hrp::parse(hrp).map_err(|e| anyhow::anyhow!("Invalid bech32 prefix: {}", e))?;
+    let account_id = account_id_from_pubkey(&public_key.serialize());
+
+    bech32::encode::<Bech32>(hrp, &account_id)
+        .map_err(|e| anyhow::anyhow!("Failed to encode bech32 address: {}", e))
+}
+
+/// Human-readable prefix of a bech32 address (e.g. `cosmos`, `osmo`)
+pub fn address_prefix(address: &str) -> anyhow::Result<String> {
+    let (hrp, data) = bech32::decode(address)
+        .map_err(|e| anyhow::anyhow!("Invalid bech32 address '{}': {}", address, e))?;
+    if data.len() != 20 {
+        return Err(anyhow::anyhow!(
+            "Invalid account address '{}': expected 20 bytes, got {}",
+            address,
+            data.len()
+        ));
+    }
+
+    Ok(hrp.to_lowercase())
+}
+
+/// ADR-036 amino JSON sign doc for `data` signed by `signer`
+///
+/// Keys are sorted and there is no whitespace, exactly as Keplr serializes it: chain ID,
+/// memo and fee are empty, account number and sequence are zero.
+pub fn adr036_sign_doc(signer: &str, data: &[u8]) -> String {
+    // serde_json escapes the strings; base64 and bech32 never need HTML escaping
+    let signer = serde_json::Value::from(signer);
+    let data = serde_json::Value::from(BASE64.encode(data));
+
+    format!(
+        concat!(
+            r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","#,
+            r#""msgs":[{{"type":"{}","value":{{"data":{},"signer":{}}}}}],"sequence":"0"}}"#
+        ),
+        MSG_SIGN_DATA_TYPE, data, signer
+    )
+}
+
+/// Verifies a Keplr/Leap `signArbitrary` (ADR-036) signature over the challenge
+///
+/// The sign doc is rebuilt for the expected address and the challenge, hashed with
+/// SHA-256 and checked against the public key; the key must derive to the expected
+/// address under `hrp` (or the expected address's own prefix when `None`).
+pub fn verify_cosmos_signature(
+    signature: &CosmosSignature,
+    public_key: &PublicKey,
+    challenge: &str,
+    expected_address: &str,
+    hrp: Option<&str>,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Cosmos ADR-036 verification starting");
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let expected_prefix = address_prefix(expected_address)?;
+    if let Some(hrp) = hrp {
+        if !expected_prefix.eq_ignore_ascii_case(hrp) {
+            return Err(anyhow::anyhow!(
+                "Address '{}' does not use the '{}' prefix",
+                expected_address,
+                hrp
+            ));
+        }
+    }
+
+    let derived_address = cosmos_address(&expected_prefix, public_key)?;
+    let address_valid = derived_address.eq_ignore_ascii_case(expected_address);
+    log::debug!("Public key address: {}", derived_address);
+    if !address_valid {
+        log::warn!("Public key does not match the expected address");
+    }
+
+    let sign_doc = adr036_sign_doc(&expected_address.to_lowercase(), challenge.as_bytes());
+    log::debug!("Sign doc: {}", sign_doc);
+    let hash: [u8; 32] = Sha256::digest(sign_doc.as_bytes()).into();
+
+    // Cosmos SDK only accepts low-S signatures, like libsecp256k1's verify
+    let signature_valid = ecdsa::Signature::from_compact(&signature.signature)
+        .map(|sig| {
+            SECP256K1
+                .verify_ecdsa(&Message::from_digest(hash), &sig, public_key)
+                .is_ok()
+        })
+        .unwrap_or(false);
+
+    if signature_valid {
+        log::info!("Cosmos signature verification successful!");
+    } else {
+        log::warn!("Cosmos signature verification failed");
+    }
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address,
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Public key of the secp256k1 private key `1`
+    const GENERATOR_COMPRESSED: &str =
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    #[test]
+    fn test_sign_doc_layout() {
+        let sign_doc = adr036_sign_doc("cosmos1abc", b"hello");
+
+        assert_eq!(
+            sign_doc,
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"aGVsbG8=","signer":"cosmos1abc"}}],"sequence":"0"}"#
+        );
+    }
+
+    #[test]
+    fn test_address_derivation_and_prefix() {
+        let public_key = parse_cosmos_public_key(GENERATOR_COMPRESSED).unwrap();
+        let address = cosmos_address("cosmos", &public_key).unwrap();
+
+        // Same key hash as the Bitcoin P2WPKH address bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+        assert_eq!(address, "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c");
+        assert_eq!(address_prefix(&address).unwrap(), "cosmos");
+        assert_eq!(
+            parse_cosmos_public_key(&BASE64.encode(public_key.serialize())).unwrap(),
+            public_key
+        );
+    }
+
+    #[test]
+    fn test_parse_std_signature_json() {
+        let json = format!(
+            r#"{{"pub_key":{{"type":"tendermint/PubKeySecp256k1","value":"A3k="}},"signature":"{}"}}"#,
+            BASE64.encode([7u8; 64])
+        );
+        let signature = CosmosSignature::parse(&json).unwrap();
+
+        assert_eq!(signature.signature, [7u8; 64]);
+        assert_eq!(signature.public_key.as_deref(), Some("A3k="));
+        assert!(CosmosSignature::parse(&BASE64.encode([7u8; 65])).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+//! Cosmos Provider
+//!
+//! This module provides signature verification for Cosmos SDK wallets using ADR-036
+//! offline signatures (`signArbitrary`).
+//!
+//! # Supported Wallets
+//!
+//! - Keplr
+//! - Leap
+//! - Any wallet implementing `signArbitrary` for Cosmos SDK chains
+//!
+//! # Signature Format
+//!
+//! The wallet signs an amino JSON `StdSignDoc` with a single `sign/MsgSignData` message:
+//!
+//! ```text
+//! {"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"",
+//!  "msgs":[{"type":"sign/MsgSignData","value":{"data":"<base64 challenge>","signer":"<address>"}}],
+//!  "sequence":"0"}
+//! ```
+//!
+//! - Hash: SHA-256 of the sign doc (sorted keys, no whitespace)
+//! - Signature: base64 64-byte secp256k1 `r || s` (low-S), or the `StdSignature` JSON
+//! - Public key: compressed secp256k1, base64 or hex (not recoverable from the signature)
+//! - Address: `bech32(hrp, RIPEMD-160(SHA-256(public key)))`
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{CosmosProvider, VerificationInput, WalletProvider};
+//!
+//! let input = VerificationInput {
+//!     signature_data: "k5Ia2Z3eMh4kBQq2O2fDoaCYkEyzV9dJ6BqkvwlVG4IZb6ABrEqZyqPIq0WeNTsZZtgsG9f7oIDdR2a1nl/eDg==".to_string(),
+//!     expected_address: "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     public_key: Some("Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = CosmosProvider::new().with_hrp("cosmos");
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod core;
+mod provider;
+
+pub use provider::CosmosProvider;
@@ -0,0 +1,101 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::core::{
+    address_prefix, parse_cosmos_public_key, verify_cosmos_signature, CosmosSignature,
+};
+use crate::types::VerificationResult;
+
+/// Provider for Cosmos wallets signing with ADR-036 (Keplr/Leap `signArbitrary`)
+///
+/// The signature is a base64 64-byte `r || s`, or the whole `StdSignature` JSON that
+/// `signArbitrary` returns. secp256k1 keys cannot be recovered from these signatures,
+/// so the public key comes from [`VerificationInput::public_key`] (base64 or hex) or
+/// from the `StdSignature`'s `pub_key`.
+///
+/// By default any bech32 prefix is accepted; [`CosmosProvider::with_hrp`] restricts
+/// addresses to one chain (e.g. `cosmos`, `osmo`, `juno`).
+#[derive(Debug, Clone, Default)]
+pub struct CosmosProvider {
+    hrp: Option<String>,
+}
+
+impl CosmosProvider {
+    /// Creates a provider accepting addresses with any bech32 prefix
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires addresses to use the bech32 prefix `hrp`
+    pub fn with_hrp(mut self, hrp: &str) -> Self {
+        self.hrp = Some(hrp.to_lowercase());
+        self
+    }
+}
+
+impl WalletProvider for CosmosProvider {
+    fn name(&self) -> &str {
+        "Cosmos"
+    }
+
+    fn description(&self) -> &str {
+        "Cosmos - ADR-036 offline signature verification (Keplr, Leap signArbitrary)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Cosmos: challenge is required for verification"
+            ));
+        }
+
+        if input.signature_data.is_empty() {
+            return Err(anyhow::anyhow!("Cosmos: signature_data is required"));
+        }
+
+        // Validate that address is a bech32 account address with the configured prefix
+        let prefix = address_prefix(&input.expected_address)
+            .map_err(|e| anyhow::anyhow!("Cosmos: {}", e))?;
+        if let Some(hrp) = &self.hrp {
+            if &prefix != hrp {
+                return Err(anyhow::anyhow!(
+                    "Cosmos: expected a '{}' address, got '{}'",
+                    hrp,
+                    input.expected_address
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Cosmos requires challenge"))?;
+
+        let signature = CosmosSignature::parse(&input.signature_data)
+            .map_err(|e| anyhow::anyhow!("Cosmos: {}", e))?;
+        let public_key = input
+            .public_key
+            .as_ref()
+            .or(signature.public_key.as_ref())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Cosmos: public_key is required (ADR-036 signatures are not recoverable)"
+                )
+            })?;
+        let public_key =
+            parse_cosmos_public_key(public_key).map_err(|e| anyhow::anyhow!("Cosmos: {}", e))?;
+
+        verify_cosmos_signature(
+            &signature,
+            &public_key,
+            challenge,
+            &input.expected_address,
+            self.hrp.as_deref(),
+        )
+    }
+}
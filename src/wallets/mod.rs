@@ -7,6 +7,18 @@
 //!
 //! - **[`xaman`]** - Xaman Wallet (XRPL SignIn) - requires `xaman` feature
 //! - **[`web3auth`]** - Web3Auth wallet - requires `web3auth` feature
+//! - **[`wallet_connect`]** - WalletConnect and EVM wallets (EIP-191, EIP-712) - requires `wallet_connect` feature
+//! - **[`solana`]** - Solana wallets (Phantom, Solflare) and Sign In With Solana (SIWS) - requires `solana` feature
+//! - **[`bitcoin`]** - Bitcoin wallets (BIP-137 / Electrum and BIP-322 signed messages, incl. Taproot) - requires `bitcoin` feature
+//! - **[`cosmos`]** - Cosmos SDK wallets (Keplr, Leap ADR-036 `signArbitrary`) - requires `cosmos` feature
+//! - **[`tron`]** - Tron wallets (TronLink TIP-191 signed messages) - requires `tron` feature
+//! - **[`cardano`]** - Cardano wallets (CIP-30 `signData` COSE signatures) - requires `cardano` feature
+//! - **[`substrate`]** - Polkadot/Substrate wallets (sr25519, ed25519 and ecdsa `signRaw`) - requires `substrate` feature
+//! - **[`near`]** - NEAR wallets (NEP-413 `signMessage`) - requires `near` feature
+//! - **[`stellar`]** - Stellar wallets (SEP-10 challenge transactions) - requires `stellar` feature
+//! - **[`move_chains`]** - Sui and Aptos wallets (personal message signatures) - requires `move_chains` feature
+//! - **[`ton`]** - TON wallets (TON Connect `ton_proof`) - requires `ton` feature
+//! - **[`webauthn`]** - Passkeys and security keys (WebAuthn ES256 assertions) - requires `webauthn` feature
 //!
 //! # Features
 //!
@@ -39,6 +51,50 @@ pub mod xaman;
 #[cfg_attr(docsrs, doc(cfg(feature = "wallet_connect")))]
 pub mod wallet_connect;
 
+#[cfg(feature = "solana")]
+#[cfg_attr(docsrs, doc(cfg(feature = "solana")))]
+pub mod solana;
+
+#[cfg(feature = "bitcoin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitcoin")))]
+pub mod bitcoin;
+
+#[cfg(feature = "cosmos")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cosmos")))]
+pub mod cosmos;
+
+#[cfg(feature = "tron")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tron")))]
+pub mod tron;
+
+#[cfg(feature = "cardano")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cardano")))]
+pub mod cardano;
+
+#[cfg(feature = "substrate")]
+#[cfg_attr(docsrs, doc(cfg(feature = "substrate")))]
+pub mod substrate;
+
+#[cfg(feature = "near")]
+#[cfg_attr(docsrs, doc(cfg(feature = "near")))]
+pub mod near;
+
+#[cfg(feature = "stellar")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stellar")))]
+pub mod stellar;
+
+#[cfg(feature = "move_chains")]
+#[cfg_attr(docsrs, doc(cfg(feature = "move_chains")))]
+pub mod move_chains;
+
+#[cfg(feature = "ton")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ton")))]
+pub mod ton;
+
+#[cfg(feature = "webauthn")]
+#[cfg_attr(docsrs, doc(cfg(feature = "webauthn")))]
+pub mod webauthn;
+
 pub use provider::{VerificationInput, WalletProvider};
 pub use registry::{get_wallet_provider, WalletType};
 
@@ -52,4 +108,50 @@ pub use xaman::XamanProvider;
 
 #[cfg(feature = "wallet_connect")]
 #[cfg_attr(docsrs, doc(cfg(feature = "wallet_connect")))]
-pub use wallet_connect::WalletConnectProvider;
+pub use wallet_connect::{
+    ChecksumPolicy, EvmAddress, EvmProvider, EvmSigningScheme, WalletConnectProvider,
+};
+
+#[cfg(feature = "solana")]
+#[cfg_attr(docsrs, doc(cfg(feature = "solana")))]
+pub use solana::{SiwsProvider, SolanaProvider};
+
+#[cfg(feature = "bitcoin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitcoin")))]
+pub use bitcoin::{BitcoinProvider, BitcoinSigningScheme};
+
+#[cfg(feature = "cosmos")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cosmos")))]
+pub use cosmos::CosmosProvider;
+
+#[cfg(feature = "tron")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tron")))]
+pub use tron::{TronProvider, TronSigningScheme};
+
+#[cfg(feature = "cardano")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cardano")))]
+pub use cardano::{CardanoAddress, CardanoPayloadHashing, CardanoProvider};
+
+#[cfg(feature = "substrate")]
+#[cfg_attr(docsrs, doc(cfg(feature = "substrate")))]
+pub use substrate::{Ss58Address, SubstrateKeyType, SubstrateProvider};
+
+#[cfg(feature = "near")]
+#[cfg_attr(docsrs, doc(cfg(feature = "near")))]
+pub use near::{AccessKeyPermission, InMemoryAccessKeys, NearAccessKeyLookup, NearProvider};
+
+#[cfg(feature = "stellar")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stellar")))]
+pub use stellar::{Sep10Rules, StellarProvider};
+
+#[cfg(feature = "move_chains")]
+#[cfg_attr(docsrs, doc(cfg(feature = "move_chains")))]
+pub use move_chains::{AptosProvider, SuiProvider, SuiSignatureScheme};
+
+#[cfg(feature = "ton")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ton")))]
+pub use ton::{TonAddress, TonProofPolicy, TonProvider};
+
+#[cfg(feature = "webauthn")]
+#[cfg_attr(docsrs, doc(cfg(feature = "webauthn")))]
+pub use webauthn::{WebAuthnPolicy, WebAuthnProvider};
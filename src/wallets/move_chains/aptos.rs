@@ -0,0 +1,277 @@
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha3::{Digest, Sha3_256};
+
+/// Prefix of the message Aptos wallets sign
+pub const APTOS_MESSAGE_PREFIX: &str = "APTOS";
+
+/// Authentication key scheme of single ed25519 keys
+pub const ED25519_SCHEME: u8 = 0x00;
+
+/// Fields of a `signMessage` full message, in signing order
+///
+/// `address`, `application` and `chain_id` are included when the app asked for them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AptosFullMessage {
+    pub address: Option<String>,
+    pub application: Option<String>,
+    pub chain_id: Option<u64>,
+    pub message: String,
+    pub nonce: String,
+}
+
+impl AptosFullMessage {
+    /// Builds the full message that is signed, e.g.
+    /// `APTOS\naddress: 0x1\nmessage: hello\nnonce: 42`
+    pub fn build(&self) -> String {
+        let mut full = format!("{}\n", APTOS_MESSAGE_PREFIX);
+        if let Some(address) = &self.address {
+            full.push_str(&format!("address: {}\n", address));
+        }
+        if let Some(application) = &self.application {
+            full.push_str(&format!("application: {}\n", application));
+        }
+        if let Some(chain_id) = self.chain_id {
+            full.push_str(&format!("chainId: {}\n", chain_id));
+        }
+        full.push_str(&format!("message: {}\nnonce: {}", self.message, self.nonce));
+        full
+    }
+
+    /// Parses a full message back into its fields
+    ///
+    /// The message may span several lines; it ends at the last `\nnonce: `.
+    pub fn parse(full_message: &str) -> anyhow::Result<Self> {
+        let body = full_message
+            .strip_prefix(APTOS_MESSAGE_PREFIX)
+            .and_then(|rest| rest.strip_prefix('\n'))
+            .ok_or_else(|| anyhow::anyhow!("Aptos full message must start with 'APTOS\\n'"))?;
+        let (body, nonce) = body
+            .rsplit_once("\nnonce: ")
+            .ok_or_else(|| anyhow::anyhow!("Aptos full message is missing the nonce"))?;
+        let (headers, message) = if let Some(message) = body.strip_prefix("message: ") {
+            ("", message)
+        } else {
+            body.split_once("\nmessage: ")
+                .ok_or_else(|| anyhow::anyhow!("Aptos full message is missing the message"))?
+        };
+
+        let mut parsed = AptosFullMessage {
+            message: message.to_string(),
+            nonce: nonce.to_string(),
+            ..Default::default()
+        };
+        for line in headers.lines() {
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| anyhow::anyhow!("Invalid Aptos message line '{}'", line))?;
+            match key {
+                "address" => parsed.address = Some(value.to_string()),
+                "application" => parsed.application = Some(value.to_string()),
+                "chainId" => {
+                    parsed.chain_id = Some(
+                        value
+                            .parse()
+                            .map_err(|e| anyhow::anyhow!("Invalid Aptos chainId: {}", e))?,
+                    )
+                }
+                key => return Err(anyhow::anyhow!("Unknown Aptos message field '{}'", key)),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Aptos account address of a single ed25519 key:
+/// `0x` + hex `SHA3-256(public key || 0x00)`
+///
+/// Accounts whose key was rotated keep their original address; those cannot be derived.
+pub fn aptos_address(public_key: &[u8; 32]) -> String {
+    let hash = Sha3_256::new()
+        .chain_update(public_key)
+        .chain_update([ED25519_SCHEME])
+        .finalize();
+    format!("0x{}", hex::encode(hash))
+}
+
+/// Normalizes an Aptos address to `0x` + 64 lowercase hex chars (short forms are zero-padded)
+pub fn normalize_aptos_address(address: &str) -> anyhow::Result<String> {
+    let hex_part = address
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow::anyhow!("Aptos address '{}' must start with 0x", address))?;
+    if hex_part.is_empty()
+        || hex_part.len() > 64
+        || !hex_part.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(anyhow::anyhow!("Invalid Aptos address '{}'", address));
+    }
+    Ok(format!("0x{:0>64}", hex_part.to_lowercase()))
+}
+
+/// Parses a hex ed25519 public key (with or without `0x`)
+pub fn parse_aptos_public_key(public_key: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = hex::decode(public_key.trim().trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid Aptos public key hex: {}", e))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid Aptos public key: expected 32 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Parses a hex ed25519 signature (with or without `0x`)
+pub fn parse_aptos_signature(signature: &str) -> anyhow::Result<[u8; 64]> {
+    let bytes = hex::decode(signature.trim().trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid Aptos signature hex: {}", e))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid ed25519 signature length: expected 64 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Verifies an Aptos `signMessage` signature
+///
+/// - `signature_valid`: the ed25519 signature over the UTF-8 full message is valid under
+///   `verify_strict`, which rejects small-order keys and non-canonical signatures
+/// - `challenge_valid`: the `message` field of the full message is the challenge
+/// - `address_valid`: the key's address is the expected address
+pub fn verify_aptos_signature(
+    signature: &[u8; 64],
+    public_key: &str,
+    full_message: &str,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Aptos verification starting");
+    log::debug!("Public key: {}", public_key);
+    log::debug!("Full message: {}", full_message);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let expected_address = normalize_aptos_address(expected_address)?;
+    let key_bytes = parse_aptos_public_key(public_key)?;
+
+    let derived_address = aptos_address(&key_bytes);
+    let address_valid = derived_address == expected_address;
+    if !address_valid {
+        log::warn!(
+            "Public key address {} does not match the expected address",
+            derived_address
+        );
+    }
+
+    let parsed = AptosFullMessage::parse(full_message)?;
+    let challenge_valid = parsed.message == challenge
+        && parsed
+            .address
+            .as_deref()
+            .map(normalize_aptos_address)
+            .transpose()?
+            .is_none_or(|address| address == expected_address);
+    if !challenge_valid {
+        log::warn!("Signed message does not match the challenge");
+    }
+
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| anyhow::anyhow!("Invalid ed25519 public key: {}", e))?;
+    let signature_valid = match verifying_key
+        .verify_strict(full_message.as_bytes(), &Signature::from_bytes(signature))
+    {
+        Ok(()) => {
+            log::info!("Aptos signature verification successful!");
+            true
+        }
+        Err(e) => {
+            log::warn!("Aptos signature verification failed: {}", e);
+            false
+        }
+    };
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid,
+        signature_valid,
+        derived_address,
+        found_challenge: Some(parsed.message.clone()),
+        sign_in: parse_sign_in_challenge(&parsed.message),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address() {
+        let public_key = parse_aptos_public_key(
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        )
+        .unwrap();
+        assert_eq!(
+            aptos_address(&public_key),
+            "0x63c5215e87770d17b9f4cd47c777e322f4eb152cfd2054c1080fd9d57c48913b"
+        );
+        assert_eq!(
+            normalize_aptos_address("0x1").unwrap(),
+            format!("0x{}1", "0".repeat(63))
+        );
+    }
+
+    #[test]
+    fn test_full_message_roundtrip() {
+        let minimal = AptosFullMessage {
+            message: "hello".to_string(),
+            nonce: "42".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(minimal.build(), "APTOS\nmessage: hello\nnonce: 42");
+
+        let full = AptosFullMessage {
+            address: Some("0x1".to_string()),
+            application: Some("https://example.com".to_string()),
+            chain_id: Some(1),
+            message: "line one\nline two".to_string(),
+            nonce: "abc".to_string(),
+        };
+        assert_eq!(
+            full.build(),
+            "APTOS\naddress: 0x1\napplication: https://example.com\nchainId: 1\nmessage: line one\nline two\nnonce: abc"
+        );
+        assert_eq!(AptosFullMessage::parse(&full.build()).unwrap(), full);
+        assert_eq!(AptosFullMessage::parse(&minimal.build()).unwrap(), minimal);
+        assert!(AptosFullMessage::parse("message: hello\nnonce: 42").is_err());
+    }
+
+    #[test]
+    fn test_small_order_public_key_rejected() {
+        // Identity point as public key and R, with s = 0: passes the cofactorless
+        // equation for any message, so only strict verification rejects it
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&identity);
+        let full_message = AptosFullMessage {
+            message: "hello".to_string(),
+            nonce: "42".to_string(),
+            ..Default::default()
+        }
+        .build();
+
+        let result = verify_aptos_signature(
+            &signature,
+            &hex::encode(identity),
+            &full_message,
+            "hello",
+            &aptos_address(&identity),
+        )
+        .unwrap();
+        assert!(result.address_valid);
+        assert!(!result.signature_valid);
+    }
+}
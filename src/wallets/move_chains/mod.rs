@@ -0,0 +1,51 @@
+//! Move Chains Provider
+//!
+//! This module provides signature verification for wallets of the Move-based chains
+//! Sui and Aptos.
+//!
+//! # Supported Wallets
+//!
+//! - Sui Wallet, Suiet, Phantom and other Sui wallet-standard wallets
+//! - Petra, Martian, Pontem and other Aptos wallet-standard wallets
+//!
+//! # Signature Format
+//!
+//! Sui `signPersonalMessage`:
+//! - Digest: `BLAKE2b-256([3, 0, 0] || BCS(vector<u8> message))` (personal message
+//!   intent followed by the ULEB128-length-prefixed message)
+//! - Signature: base64 of `flag || signature (64) || public key`, with flag `0x00`
+//!   ed25519 (signs the digest), `0x01` secp256k1 or `0x02` secp256r1 (ECDSA over
+//!   `SHA-256(digest)`)
+//! - Address: `0x` + hex `BLAKE2b-256(flag || public key)`
+//!
+//! Aptos `signMessage`:
+//! - Full message: `APTOS\n[address: ..\n][application: ..\n][chainId: ..\n]message: <msg>\nnonce: <nonce>`
+//! - Signature: ed25519 over the UTF-8 full message, hex
+//! - Address: `0x` + hex `SHA3-256(public key || 0x00)` (accounts whose key was
+//!   rotated cannot be derived)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{AptosProvider, VerificationInput, WalletProvider};
+//!
+//! let input = VerificationInput {
+//!     signature_data: r#"{"fullMessage":"APTOS\nmessage: ...\nnonce: 42","signature":"0x..."}"#.to_string(),
+//!     expected_address: "0x63c5215e87770d17b9f4cd47c777e322f4eb152cfd2054c1080fd9d57c48913b".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     public_key: Some("0xd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = AptosProvider::new().with_nonce("42");
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod aptos;
+mod provider;
+pub mod sui;
+
+pub use aptos::AptosFullMessage;
+pub use provider::{AptosProvider, SuiProvider};
+pub use sui::SuiSignatureScheme;
@@ -0,0 +1,259 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::aptos::{
+    normalize_aptos_address, parse_aptos_signature, verify_aptos_signature, AptosFullMessage,
+};
+use super::sui::{normalize_sui_address, verify_sui_signature};
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+/// Provider for Sui wallets signing with `signPersonalMessage`
+///
+/// The signature is the base64 serialized signature (`flag || signature || public key`)
+/// or the `{"bytes","signature"}` JSON returned by the wallet. The signing key is
+/// taken from the signature, so no public key is needed.
+pub struct SuiProvider;
+
+impl WalletProvider for SuiProvider {
+    fn name(&self) -> &str {
+        "Sui"
+    }
+
+    fn description(&self) -> &str {
+        "Sui - signPersonalMessage verification (Sui Wallet, Suiet, Phantom)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Sui: challenge is required for verification"
+            ));
+        }
+
+        // Validate that address is a 0x-prefixed Sui address
+        normalize_sui_address(&input.expected_address)
+            .map_err(|e| anyhow::anyhow!("Sui: {}", e))?;
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Sui requires challenge"))?;
+
+        let signature_data = input.signature_data.trim();
+        if !signature_data.starts_with('{') {
+            return verify_sui_signature(signature_data, challenge, &input.expected_address);
+        }
+
+        // signPersonalMessage output: the signed bytes travel along with the signature
+        let json: serde_json::Value = serde_json::from_str(signature_data)
+            .map_err(|e| anyhow::anyhow!("Failed to parse signPersonalMessage JSON: {}", e))?;
+        let signature = json["signature"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("signPersonalMessage output is missing 'signature'"))?;
+        let mut result = verify_sui_signature(signature, challenge, &input.expected_address)?;
+
+        if let Some(bytes) = json["bytes"].as_str() {
+            let signed = BASE64
+                .decode(bytes)
+                .map_err(|e| anyhow::anyhow!("Sui: 'bytes' is not valid base64: {}", e))?;
+            if signed != challenge.as_bytes() {
+                log::warn!("Wallet signed different bytes than the challenge");
+                result.challenge_valid = false;
+                result.found_challenge = Some(String::from_utf8_lossy(&signed).to_string());
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Provider for Aptos wallets signing with `signMessage`
+///
+/// The signature is the hex ed25519 signature or the `signMessage` JSON output (with
+/// `fullMessage`, `signature` and optionally `publicKey`); the hex public key comes
+/// from the JSON or `public_key`. The nonce the app requested is set with
+/// [`AptosProvider::with_nonce`] and is required: the signed full message must carry
+/// it, and bare signatures are checked over full messages built with it (with and
+/// without the `address` line). This makes the provider library-only.
+#[derive(Debug, Clone, Default)]
+pub struct AptosProvider {
+    nonce: Option<String>,
+    application: Option<String>,
+    chain_id: Option<u64>,
+}
+
+impl AptosProvider {
+    /// Creates a provider; set the nonce with [`with_nonce`](Self::with_nonce) before
+    /// verifying
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the nonce the app asked the wallet to sign
+    pub fn with_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Requires the `application` line (the dapp origin) the app asked for
+    pub fn with_application(mut self, application: impl Into<String>) -> Self {
+        self.application = Some(application.into());
+        self
+    }
+
+    /// Requires the `chainId` line the app asked for
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Full messages a bare signature may have been made over
+    fn candidate_messages(
+        &self,
+        challenge: &str,
+        expected_address: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let nonce = self
+            .nonce
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Aptos: nonce is required for verification"))?;
+        let without_address = AptosFullMessage {
+            address: None,
+            application: self.application.clone(),
+            chain_id: self.chain_id,
+            message: challenge.to_string(),
+            nonce,
+        };
+        let mut candidates = vec![without_address.build()];
+
+        // Wallets print the address as they hold it, usually in its long form
+        let mut addresses = vec![expected_address.to_string()];
+        let normalized = normalize_aptos_address(expected_address)?;
+        if normalized != expected_address {
+            addresses.push(normalized);
+        }
+        for address in addresses {
+            let with_address = AptosFullMessage {
+                address: Some(address),
+                ..without_address.clone()
+            };
+            candidates.push(with_address.build());
+        }
+        Ok(candidates)
+    }
+
+    /// Whether a signed full message carries the nonce, application and chain ID
+    /// this provider expects
+    fn matches_request(&self, full_message: &str) -> anyhow::Result<bool> {
+        let parsed = AptosFullMessage::parse(full_message)?;
+        let matches = self.nonce.as_ref() == Some(&parsed.nonce)
+            && self
+                .application
+                .as_ref()
+                .is_none_or(|application| Some(application) == parsed.application.as_ref())
+            && self
+                .chain_id
+                .is_none_or(|chain_id| Some(chain_id) == parsed.chain_id);
+        Ok(matches)
+    }
+}
+
+impl WalletProvider for AptosProvider {
+    fn name(&self) -> &str {
+        "Aptos"
+    }
+
+    fn description(&self) -> &str {
+        "Aptos - signMessage verification (Petra, Martian, Pontem)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Aptos: challenge is required for verification"
+            ));
+        }
+
+        // Validate that address is a 0x-prefixed Aptos address
+        normalize_aptos_address(&input.expected_address)
+            .map_err(|e| anyhow::anyhow!("Aptos: {}", e))?;
+
+        // Validate that the nonce the app requested is configured
+        if self.nonce.is_none() {
+            return Err(anyhow::anyhow!("Aptos: nonce is required for verification"));
+        }
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Aptos requires challenge"))?;
+
+        let signature_data = input.signature_data.trim();
+        if !signature_data.starts_with('{') {
+            let public_key = input
+                .public_key
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("Aptos: public key is required for verification"))?;
+            let signature = parse_aptos_signature(signature_data)?;
+
+            let mut result = None;
+            for full_message in self.candidate_messages(challenge, &input.expected_address)? {
+                let candidate = verify_aptos_signature(
+                    &signature,
+                    public_key,
+                    &full_message,
+                    challenge,
+                    &input.expected_address,
+                )?;
+                if candidate.signature_valid {
+                    return Ok(candidate);
+                }
+                result.get_or_insert(candidate);
+            }
+            return result.ok_or_else(|| anyhow::anyhow!("Aptos: no message to verify"));
+        }
+
+        // signMessage output: the full message travels along with the signature
+        let json: serde_json::Value = serde_json::from_str(signature_data)
+            .map_err(|e| anyhow::anyhow!("Failed to parse signMessage JSON: {}", e))?;
+        let field = |name: &str| {
+            json[name]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("signMessage output is missing '{}'", name))
+        };
+        let full_message = field("fullMessage")?;
+        let signature = parse_aptos_signature(field("signature")?)?;
+        let public_key = input
+            .public_key
+            .as_deref()
+            .or(json["publicKey"].as_str())
+            .ok_or_else(|| anyhow::anyhow!("Aptos: public key is required for verification"))?;
+
+        let mut result = verify_aptos_signature(
+            &signature,
+            public_key,
+            full_message,
+            challenge,
+            &input.expected_address,
+        )?;
+        if !self.matches_request(full_message)? {
+            log::warn!("Signed message does not carry the requested nonce, application or chain");
+            result.challenge_valid = false;
+        }
+
+        Ok(result)
+    }
+}
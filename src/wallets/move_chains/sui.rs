@@ -0,0 +1,282 @@
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use p256::ecdsa::signature::Verifier as _;
+use sha2::Sha256;
+
+/// Intent of personal messages: scope `PersonalMessage` (3), version 0, app ID Sui (0)
+pub const PERSONAL_MESSAGE_INTENT: [u8; 3] = [3, 0, 0];
+
+/// Signature scheme, the flag byte of serialized signatures and addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuiSignatureScheme {
+    Ed25519,
+    Secp256k1,
+    Secp256r1,
+}
+
+impl SuiSignatureScheme {
+    pub fn flag(self) -> u8 {
+        match self {
+            SuiSignatureScheme::Ed25519 => 0x00,
+            SuiSignatureScheme::Secp256k1 => 0x01,
+            SuiSignatureScheme::Secp256r1 => 0x02,
+        }
+    }
+
+    pub fn from_flag(flag: u8) -> anyhow::Result<Self> {
+        match flag {
+            0x00 => Ok(SuiSignatureScheme::Ed25519),
+            0x01 => Ok(SuiSignatureScheme::Secp256k1),
+            0x02 => Ok(SuiSignatureScheme::Secp256r1),
+            0x03 => Err(anyhow::anyhow!("Sui multisig signatures are not supported")),
+            0x05 => Err(anyhow::anyhow!("Sui zkLogin signatures are not supported")),
+            0x06 => Err(anyhow::anyhow!("Sui passkey signatures are not supported")),
+            flag => Err(anyhow::anyhow!("Unknown Sui signature flag 0x{:02x}", flag)),
+        }
+    }
+
+    /// Length of the public key (ed25519 raw, ECDSA compressed)
+    fn public_key_len(self) -> usize {
+        match self {
+            SuiSignatureScheme::Ed25519 => 32,
+            _ => 33,
+        }
+    }
+}
+
+/// Serialized Sui signature: `flag || signature (64) || public key`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuiSignature {
+    pub scheme: SuiSignatureScheme,
+    pub signature: [u8; 64],
+    pub public_key: Vec<u8>,
+}
+
+impl SuiSignature {
+    /// Parses a base64 serialized signature
+    pub fn parse(signature: &str) -> anyhow::Result<Self> {
+        let bytes = BASE64
+            .decode(signature.trim())
+            .map_err(|e| anyhow::anyhow!("Sui signature is not valid base64: {}", e))?;
+        let (&flag, rest) = bytes
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("Empty Sui signature"))?;
+        let scheme = SuiSignatureScheme::from_flag(flag)?;
+
+        if rest.len() != 64 + scheme.public_key_len() {
+            return Err(anyhow::anyhow!(
+                "Invalid {:?} Sui signature: expected {} bytes, got {}",
+                scheme,
+                1 + 64 + scheme.public_key_len(),
+                bytes.len()
+            ));
+        }
+
+        Ok(SuiSignature {
+            scheme,
+            signature: rest[..64].try_into()?,
+            public_key: rest[64..].to_vec(),
+        })
+    }
+
+    /// Verifies the signature over a 32-byte digest
+    ///
+    /// ed25519 signs the digest itself (checked with `verify_strict`, which rejects
+    /// small-order keys); secp256k1 and secp256r1 sign its SHA-256.
+    pub fn verify(&self, digest: &[u8; 32]) -> bool {
+        match self.scheme {
+            SuiSignatureScheme::Ed25519 => {
+                let Ok(public_key) = <[u8; 32]>::try_from(self.public_key.as_slice()) else {
+                    return false;
+                };
+                ed25519_dalek::VerifyingKey::from_bytes(&public_key).is_ok_and(|key| {
+                    key.verify_strict(
+                        digest,
+                        &ed25519_dalek::Signature::from_bytes(&self.signature),
+                    )
+                    .is_ok()
+                })
+            }
+            SuiSignatureScheme::Secp256k1 => {
+                let hash: [u8; 32] = Sha256::digest(digest).into();
+                let (Ok(public_key), Ok(signature)) = (
+                    secp256k1::PublicKey::from_slice(&self.public_key),
+                    secp256k1::ecdsa::Signature::from_compact(&self.signature),
+                ) else {
+                    return false;
+                };
+                secp256k1::SECP256K1
+                    .verify_ecdsa(
+                        &secp256k1::Message::from_digest(hash),
+                        &signature,
+                        &public_key,
+                    )
+                    .is_ok()
+            }
+            SuiSignatureScheme::Secp256r1 => {
+                let (Ok(public_key), Ok(signature)) = (
+                    p256::ecdsa::VerifyingKey::from_sec1_bytes(&self.public_key),
+                    p256::ecdsa::Signature::from_slice(&self.signature),
+                ) else {
+                    return false;
+                };
+                // Sui requires low-s signatures
+                signature.normalize_s().is_none() && public_key.verify(digest, &signature).is_ok()
+            }
+        }
+    }
+
+    /// Sui address of the signing key
+    pub fn address(&self) -> String {
+        sui_address(self.scheme, &self.public_key)
+    }
+}
+
+/// Sui address of a public key: `0x` + hex `BLAKE2b-256(flag || public key)`
+pub fn sui_address(scheme: SuiSignatureScheme, public_key: &[u8]) -> String {
+    let hash = Blake2b::<U32>::new()
+        .chain_update([scheme.flag()])
+        .chain_update(public_key)
+        .finalize();
+    format!("0x{}", hex::encode(hash))
+}
+
+/// Normalizes a Sui address to `0x` + 64 lowercase hex chars (short forms are zero-padded)
+pub fn normalize_sui_address(address: &str) -> anyhow::Result<String> {
+    let hex_part = address
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow::anyhow!("Sui address '{}' must start with 0x", address))?;
+    if hex_part.is_empty()
+        || hex_part.len() > 64
+        || !hex_part.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(anyhow::anyhow!("Invalid Sui address '{}'", address));
+    }
+    Ok(format!("0x{:0>64}", hex_part.to_lowercase()))
+}
+
+/// Digest signed for a personal message: `BLAKE2b-256(intent || BCS(vector<u8> message))`
+pub fn personal_message_digest(message: &[u8]) -> [u8; 32] {
+    let mut bcs = Vec::with_capacity(message.len() + 5);
+    // ULEB128 length prefix
+    let mut len = message.len();
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            bcs.push(byte);
+            break;
+        }
+        bcs.push(byte | 0x80);
+    }
+    bcs.extend_from_slice(message);
+
+    Blake2b::<U32>::new()
+        .chain_update(PERSONAL_MESSAGE_INTENT)
+        .chain_update(bcs)
+        .finalize()
+        .into()
+}
+
+/// Verifies a Sui `signPersonalMessage` signature
+///
+/// The signing key is carried in the serialized signature; its address must be the
+/// expected address.
+pub fn verify_sui_signature(
+    signature: &str,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Sui verification starting");
+    log::debug!("Signature: {}", signature);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let expected_address = normalize_sui_address(expected_address)?;
+    let signature = SuiSignature::parse(signature)?;
+
+    let derived_address = signature.address();
+    let address_valid = derived_address == expected_address;
+    log::debug!("{:?} key address: {}", signature.scheme, derived_address);
+    if !address_valid {
+        log::warn!("Signing key does not match the expected address");
+    }
+
+    let signature_valid = signature.verify(&personal_message_digest(challenge.as_bytes()));
+    if signature_valid {
+        log::info!("Sui signature verification successful!");
+    } else {
+        log::warn!("Sui signature verification failed");
+    }
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address,
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 8032 test 1 public key
+    const ED25519_PUBLIC_KEY: &str =
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+
+    #[test]
+    fn test_address_and_digest() {
+        let public_key = hex::decode(ED25519_PUBLIC_KEY).unwrap();
+        assert_eq!(
+            sui_address(SuiSignatureScheme::Ed25519, &public_key),
+            "0x304af458e90e97c841685b8cbbc59b909f3e2cf150df590ada4c81452c29737d"
+        );
+        assert_eq!(
+            hex::encode(personal_message_digest(b"hello")),
+            "e0ea06e183a8984cd8dd072440ae2a8c21125d994a9435b7c8c61886bc087d6a"
+        );
+    }
+
+    #[test]
+    fn test_normalize_address() {
+        assert_eq!(
+            normalize_sui_address("0x2").unwrap(),
+            format!("0x{}2", "0".repeat(63))
+        );
+        assert!(normalize_sui_address("2").is_err());
+        assert!(normalize_sui_address(&format!("0x{}", "a".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported_schemes() {
+        let zklogin = BASE64.encode([[0x05].as_slice(), &[0u8; 96]].concat());
+        assert!(SuiSignature::parse(&zklogin).is_err());
+
+        let truncated = BASE64.encode([0u8; 64]);
+        assert!(SuiSignature::parse(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_small_order_public_key_rejected() {
+        // Identity point as public key and R, with s = 0: passes the cofactorless
+        // equation for any digest, so only strict verification rejects it
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&identity);
+
+        let signature = SuiSignature {
+            scheme: SuiSignatureScheme::Ed25519,
+            signature,
+            public_key: identity.to_vec(),
+        };
+        assert!(!signature.verify(&personal_message_digest(b"hello")));
+    }
+}
@@ -0,0 +1,76 @@
+use super::core::ImplicitAccount;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Permission of a NEAR access key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKeyPermission {
+    /// Can sign any transaction for the account (required by NEP-413)
+    FullAccess,
+    /// Limited to calls to a contract; cannot prove account ownership
+    FunctionCall,
+}
+
+/// Looks up access keys of NEAR accounts (`view_access_key`)
+///
+/// Implement this on top of your NEAR RPC client so named accounts can be checked.
+/// Keys are given in their `ed25519:<base58>` form. Unknown keys return `None`.
+pub trait NearAccessKeyLookup: Send + Sync {
+    fn access_key(
+        &self,
+        account_id: &str,
+        public_key: &str,
+    ) -> anyhow::Result<Option<AccessKeyPermission>>;
+}
+
+/// In-process access keys for tests and offline use
+///
+/// Implicit accounts (64 hex chars) own the full-access key they are derived from.
+/// Keys of named accounts must be registered with
+/// [`add_key`](InMemoryAccessKeys::add_key); anything else is unknown.
+#[derive(Debug, Default)]
+pub struct InMemoryAccessKeys {
+    keys: RwLock<HashMap<(String, String), AccessKeyPermission>>,
+}
+
+impl InMemoryAccessKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) an access key of `account_id`
+    pub fn add_key(
+        &self,
+        account_id: &str,
+        public_key: &str,
+        permission: AccessKeyPermission,
+    ) -> anyhow::Result<()> {
+        self.keys
+            .write()
+            .map_err(|_| anyhow::anyhow!("NEAR: access key store lock poisoned"))?
+            .insert((account_id.to_string(), public_key.to_string()), permission);
+        Ok(())
+    }
+}
+
+impl NearAccessKeyLookup for InMemoryAccessKeys {
+    fn access_key(
+        &self,
+        account_id: &str,
+        public_key: &str,
+    ) -> anyhow::Result<Option<AccessKeyPermission>> {
+        let registered = self
+            .keys
+            .read()
+            .map_err(|_| anyhow::anyhow!("NEAR: access key store lock poisoned"))?
+            .get(&(account_id.to_string(), public_key.to_string()))
+            .copied();
+        if registered.is_some() {
+            return Ok(registered);
+        }
+
+        let implicit = ImplicitAccount::parse(account_id)
+            .is_some_and(|account| account.public_key() == public_key);
+        Ok(implicit.then_some(AccessKeyPermission::FullAccess))
+    }
+}
@@ -0,0 +1,379 @@
+use super::access_key::{AccessKeyPermission, NearAccessKeyLookup};
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+/// NEP-413 payload tag: `2^31 + 413`, serialized before the payload
+pub const NEP413_TAG: u32 = (1 << 31) + 413;
+
+/// Prefix of ed25519 public keys in their string form
+pub const ED25519_KEY_PREFIX: &str = "ed25519:";
+
+/// NEP-413 `signMessage` payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nep413Payload {
+    pub message: String,
+    /// 32-byte nonce chosen by the app
+    pub nonce: [u8; 32],
+    /// Recipient the message is meant for (usually the app domain or contract)
+    pub recipient: String,
+    pub callback_url: Option<String>,
+}
+
+impl Nep413Payload {
+    /// Borsh serialization of the tag followed by the payload fields
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = NEP413_TAG.to_le_bytes().to_vec();
+        write_string(&mut out, &self.message);
+        out.extend_from_slice(&self.nonce);
+        write_string(&mut out, &self.recipient);
+        match &self.callback_url {
+            Some(callback_url) => {
+                out.push(1);
+                write_string(&mut out, callback_url);
+            }
+            None => out.push(0),
+        }
+        out
+    }
+
+    /// SHA-256 of the serialized payload, the bytes that are signed
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.serialize()).into()
+    }
+}
+
+/// Borsh string: u32 little-endian length and UTF-8 bytes
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Parses an `ed25519:<base58>` public key (the prefix is optional)
+pub fn parse_near_public_key(public_key: &str) -> anyhow::Result<[u8; 32]> {
+    let encoded = public_key
+        .strip_prefix(ED25519_KEY_PREFIX)
+        .unwrap_or(public_key);
+    if encoded.contains(':') {
+        return Err(anyhow::anyhow!(
+            "Unsupported NEAR key type in '{}': only ed25519 keys can sign messages",
+            public_key
+        ));
+    }
+
+    let bytes = bs58::decode(encoded)
+        .into_vec()
+        .map_err(|e| anyhow::anyhow!("Invalid NEAR public key '{}': {}", public_key, e))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid NEAR public key '{}': expected 32 bytes, got {}",
+            public_key,
+            bytes.len()
+        )
+    })
+}
+
+/// Formats a public key as `ed25519:<base58>`
+pub fn format_near_public_key(public_key: &[u8; 32]) -> String {
+    format!(
+        "{}{}",
+        ED25519_KEY_PREFIX,
+        bs58::encode(public_key).into_string()
+    )
+}
+
+/// Validates a NEAR account ID: 2-64 chars of `a-z`, `0-9` separated by single
+/// `.`, `-` or `_`
+pub fn validate_account_id(account_id: &str) -> anyhow::Result<()> {
+    let valid_length = (2..=64).contains(&account_id.len());
+    let valid_parts = account_id.split(['.', '-', '_']).all(|part| {
+        !part.is_empty()
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    });
+
+    if !valid_length || !valid_parts {
+        return Err(anyhow::anyhow!("Invalid NEAR account ID '{}'", account_id));
+    }
+    Ok(())
+}
+
+/// Implicit account: the account ID is the hex of its ed25519 public key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImplicitAccount([u8; 32]);
+
+impl ImplicitAccount {
+    /// Parses a 64-char lowercase hex account ID
+    pub fn parse(account_id: &str) -> Option<Self> {
+        if account_id.len() != 64 || account_id.bytes().any(|b| b.is_ascii_uppercase()) {
+            return None;
+        }
+        let mut key = [0u8; 32];
+        hex::decode_to_slice(account_id, &mut key).ok()?;
+        Some(ImplicitAccount(key))
+    }
+
+    /// `ed25519:<base58>` form of the account key
+    pub fn public_key(&self) -> String {
+        format_near_public_key(&self.0)
+    }
+}
+
+/// Output of a wallet's `signMessage`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearSignedMessage {
+    pub signature: [u8; 64],
+    pub account_id: Option<String>,
+    pub public_key: Option<String>,
+    /// Payload fields apps may send back along with the signature
+    pub nonce: Option<[u8; 32]>,
+    pub recipient: Option<String>,
+    pub callback_url: Option<String>,
+}
+
+impl NearSignedMessage {
+    /// Parses a base64 signature, or the `{"accountId","publicKey","signature"}` JSON
+    /// returned by `signMessage` (optionally with `nonce`, `recipient` and `callbackUrl`)
+    pub fn parse(signature: &str) -> anyhow::Result<Self> {
+        let signature = signature.trim();
+        if !signature.starts_with('{') {
+            return Ok(NearSignedMessage {
+                signature: decode_signature(signature)?,
+                account_id: None,
+                public_key: None,
+                nonce: None,
+                recipient: None,
+                callback_url: None,
+            });
+        }
+
+        let json: serde_json::Value = serde_json::from_str(signature)
+            .map_err(|e| anyhow::anyhow!("Failed to parse signMessage JSON: {}", e))?;
+        let field = |name: &str| json[name].as_str().map(str::to_string);
+        let encoded = json["signature"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("signMessage output is missing 'signature'"))?;
+
+        Ok(NearSignedMessage {
+            signature: decode_signature(encoded)?,
+            account_id: field("accountId"),
+            public_key: field("publicKey"),
+            nonce: field("nonce")
+                .map(|nonce| decode_nonce(&nonce))
+                .transpose()?,
+            recipient: field("recipient"),
+            callback_url: field("callbackUrl"),
+        })
+    }
+}
+
+/// Decodes a 64-byte signature given as base64 (wallet default) or hex
+fn decode_signature(signature: &str) -> anyhow::Result<[u8; 64]> {
+    let bytes = match hex::decode(signature.trim_start_matches("0x")) {
+        Ok(bytes) if bytes.len() == 64 => bytes,
+        _ => BASE64
+            .decode(signature)
+            .map_err(|e| anyhow::anyhow!("Signature is not valid base64 or hex: {}", e))?,
+    };
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid ed25519 signature length: expected 64 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Decodes a 32-byte nonce given as hex or base64
+pub fn decode_nonce(nonce: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = match hex::decode(nonce.trim_start_matches("0x")) {
+        Ok(bytes) if bytes.len() == 32 => bytes,
+        _ => BASE64
+            .decode(nonce)
+            .map_err(|e| anyhow::anyhow!("Nonce is not valid base64 or hex: {}", e))?,
+    };
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid nonce length: expected 32 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Verifies a NEP-413 `signMessage` signature
+///
+/// - `signature_valid`: the ed25519 signature over `SHA-256(borsh(tag, payload))` is valid
+///   under `verify_strict`, which rejects small-order keys and non-canonical signatures
+/// - `address_valid`: the key is a full-access key of the expected account, according
+///   to `access_keys`
+pub fn verify_near_signature(
+    signature: &[u8; 64],
+    public_key: &str,
+    payload: &Nep413Payload,
+    expected_account: &str,
+    access_keys: &dyn NearAccessKeyLookup,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("NEAR verification starting");
+    log::debug!("Public key: {}", public_key);
+    log::debug!("Challenge: {}", payload.message);
+    log::debug!("Recipient: {}", payload.recipient);
+    log::debug!("Expected Account: {}", expected_account);
+
+    validate_account_id(expected_account)?;
+    let key_bytes = parse_near_public_key(public_key)?;
+    let public_key = format_near_public_key(&key_bytes);
+
+    let permission = access_keys.access_key(expected_account, &public_key)?;
+    let address_valid = permission == Some(AccessKeyPermission::FullAccess);
+    match permission {
+        Some(AccessKeyPermission::FullAccess) => {
+            log::debug!(
+                "{} is a full-access key of {}",
+                public_key,
+                expected_account
+            )
+        }
+        Some(AccessKeyPermission::FunctionCall) => {
+            log::warn!(
+                "{} is only a function-call key of {}",
+                public_key,
+                expected_account
+            )
+        }
+        None => log::warn!(
+            "{} is not an access key of {}",
+            public_key,
+            expected_account
+        ),
+    }
+
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| anyhow::anyhow!("Invalid ed25519 public key: {}", e))?;
+    let signature_valid =
+        match verifying_key.verify_strict(&payload.hash(), &Signature::from_bytes(signature)) {
+            Ok(()) => {
+                log::info!("NEAR signature verification successful!");
+                true
+            }
+            Err(e) => {
+                log::warn!("NEAR signature verification failed: {}", e);
+                false
+            }
+        };
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address: expected_account.to_string(),
+        found_challenge: Some(payload.message.clone()),
+        sign_in: parse_sign_in_challenge(&payload.message),
+        recipient: Some(payload.recipient.clone()),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_serialization() {
+        let payload = Nep413Payload {
+            message: "hi".to_string(),
+            nonce: [0x07; 32],
+            recipient: "app".to_string(),
+            callback_url: None,
+        };
+
+        let expected = [
+            &[0x9d, 0x01, 0x00, 0x80][..], // 2^31 + 413, little-endian
+            &[2, 0, 0, 0],
+            b"hi",
+            &[0x07; 32],
+            &[3, 0, 0, 0],
+            b"app",
+            &[0], // no callback URL
+        ]
+        .concat();
+        assert_eq!(payload.serialize(), expected);
+
+        let with_callback = Nep413Payload {
+            callback_url: Some("https://a.b".to_string()),
+            ..payload
+        };
+        assert!(with_callback
+            .serialize()
+            .ends_with(&[&[1, 11, 0, 0, 0][..], b"https://a.b"].concat()));
+    }
+
+    #[test]
+    fn test_account_ids() {
+        for valid in [
+            "alice.near",
+            "app-1.testnet",
+            "a_b.c",
+            "0x",
+            &"a".repeat(64),
+        ] {
+            assert!(validate_account_id(valid).is_ok(), "{}", valid);
+        }
+        for invalid in [
+            "a",
+            "Alice.near",
+            "alice..near",
+            ".near",
+            "alice.",
+            "a b",
+            &"a".repeat(65),
+        ] {
+            assert!(validate_account_id(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_public_keys_and_implicit_accounts() {
+        let key = [0x42; 32];
+        let formatted = format_near_public_key(&key);
+
+        assert!(formatted.starts_with("ed25519:"));
+        assert_eq!(parse_near_public_key(&formatted).unwrap(), key);
+        assert!(parse_near_public_key("secp256k1:abc").is_err());
+
+        let implicit = ImplicitAccount::parse(&hex::encode(key)).unwrap();
+        assert_eq!(implicit.public_key(), formatted);
+        assert!(ImplicitAccount::parse("alice.near").is_none());
+    }
+
+    #[test]
+    fn test_small_order_public_key_rejected() {
+        // Identity point as public key and R, with s = 0: passes the cofactorless
+        // equation for any payload, so only strict verification rejects it
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&identity);
+        let payload = Nep413Payload {
+            message: "any message".to_string(),
+            nonce: [0x07; 32],
+            recipient: "app".to_string(),
+            callback_url: None,
+        };
+
+        let result = verify_near_signature(
+            &signature,
+            &format_near_public_key(&identity),
+            &payload,
+            &hex::encode(identity),
+            &super::super::access_key::InMemoryAccessKeys::new(),
+        )
+        .unwrap();
+        assert!(result.address_valid);
+        assert!(!result.signature_valid);
+    }
+}
@@ -0,0 +1,50 @@
+//! NEAR Provider
+//!
+//! This module provides signature verification for NEAR wallets using NEP-413
+//! `signMessage`.
+//!
+//! # Supported Wallets
+//!
+//! - MyNearWallet
+//! - Meteor Wallet
+//! - HERE Wallet and other wallet-selector wallets implementing NEP-413
+//!
+//! # Signature Format
+//!
+//! - Payload: borsh of `{ message, nonce: [u8; 32], recipient, callbackUrl: Option }`,
+//!   prefixed with the borsh `u32` tag `2^31 + 413`
+//! - Signature: ed25519 over `SHA-256(tag || payload)`, base64
+//! - Public key: `ed25519:<base58>`
+//! - Address: the account ID (`alice.near` or a 64-hex implicit account); the key must
+//!   be one of its full-access keys
+//!
+//! Access keys of named accounts live on chain: implement [`NearAccessKeyLookup`] with
+//! an RPC client (`view_access_key`). [`InMemoryAccessKeys`] is an offline stand-in
+//! that knows implicit accounts and registered keys.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{NearProvider, VerificationInput, WalletProvider};
+//!
+//! let input = VerificationInput {
+//!     signature_data: r#"{"accountId":"alice.near","publicKey":"ed25519:...","signature":"..."}"#.to_string(),
+//!     expected_address: "alice.near".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = NearProvider::new()
+//!     .with_recipient("example.com")
+//!     .with_nonce([0u8; 32]);
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod access_key;
+pub mod core;
+mod provider;
+
+pub use access_key::{AccessKeyPermission, InMemoryAccessKeys, NearAccessKeyLookup};
+pub use core::Nep413Payload;
+pub use provider::NearProvider;
@@ -0,0 +1,146 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::access_key::{InMemoryAccessKeys, NearAccessKeyLookup};
+use super::core::{validate_account_id, verify_near_signature, NearSignedMessage, Nep413Payload};
+use crate::types::VerificationResult;
+use std::sync::Arc;
+
+/// Provider for NEAR wallets signing with NEP-413 `signMessage`
+///
+/// The signature is the base64 signature or the whole `signMessage` JSON output, the
+/// expected address is the account ID, and the `ed25519:` public key comes from the
+/// JSON or `public_key`. The nonce and recipient the app requested are set with
+/// [`NearProvider::with_nonce`] and [`NearProvider::with_recipient`]; values sent back in
+/// the JSON must match them, or the challenge is invalid. Without them the values from
+/// the JSON are used, so the caller must check the reported
+/// [`recipient`](VerificationResult::recipient). Account ownership is checked through a
+/// [`NearAccessKeyLookup`]; without one, only implicit accounts can be verified.
+#[derive(Clone, Default)]
+pub struct NearProvider {
+    recipient: Option<String>,
+    nonce: Option<[u8; 32]>,
+    callback_url: Option<String>,
+    access_keys: Option<Arc<dyn NearAccessKeyLookup>>,
+}
+
+impl NearProvider {
+    /// Creates a provider that checks implicit accounts offline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the recipient the app asked the wallet to sign for
+    pub fn with_recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.recipient = Some(recipient.into());
+        self
+    }
+
+    /// Sets the 32-byte nonce the app asked the wallet to sign
+    pub fn with_nonce(mut self, nonce: [u8; 32]) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the callback URL the app passed to `signMessage`
+    pub fn with_callback_url(mut self, callback_url: impl Into<String>) -> Self {
+        self.callback_url = Some(callback_url.into());
+        self
+    }
+
+    /// Checks access keys of named accounts, e.g. through NEAR RPC `view_access_key`
+    pub fn with_access_key_lookup(mut self, access_keys: Arc<dyn NearAccessKeyLookup>) -> Self {
+        self.access_keys = Some(access_keys);
+        self
+    }
+}
+
+impl WalletProvider for NearProvider {
+    fn name(&self) -> &str {
+        "NEAR"
+    }
+
+    fn description(&self) -> &str {
+        "NEAR - NEP-413 signMessage verification (MyNearWallet, Meteor, HERE)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "NEAR: challenge is required for verification"
+            ));
+        }
+
+        // Validate that address is a NEAR account ID
+        validate_account_id(&input.expected_address).map_err(|e| anyhow::anyhow!("NEAR: {}", e))?;
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("NEAR requires challenge"))?;
+
+        let signed = NearSignedMessage::parse(&input.signature_data)?;
+        let public_key = input
+            .public_key
+            .as_deref()
+            .or(signed.public_key.as_deref())
+            .ok_or_else(|| anyhow::anyhow!("NEAR: public key is required for verification"))?;
+        let payload = Nep413Payload {
+            message: challenge.clone(),
+            nonce: self
+                .nonce
+                .or(signed.nonce)
+                .ok_or_else(|| anyhow::anyhow!("NEAR: nonce is required for verification"))?,
+            recipient: self
+                .recipient
+                .clone()
+                .or_else(|| signed.recipient.clone())
+                .ok_or_else(|| anyhow::anyhow!("NEAR: recipient is required for verification"))?,
+            callback_url: self.callback_url.clone().or(signed.callback_url),
+        };
+
+        let offline = InMemoryAccessKeys::new();
+        let access_keys = self.access_keys.as_deref().unwrap_or(&offline);
+        let mut result = verify_near_signature(
+            &signed.signature,
+            public_key,
+            &payload,
+            &input.expected_address,
+            access_keys,
+        )?;
+
+        // Payload fields sent back with the signature must be the ones the app requested
+        let nonce_mismatch = matches!(
+            (self.nonce, signed.nonce),
+            (Some(expected), Some(echoed)) if expected != echoed
+        );
+        let recipient_mismatch = matches!(
+            (&self.recipient, &signed.recipient),
+            (Some(expected), Some(echoed)) if expected != echoed
+        );
+        if nonce_mismatch || recipient_mismatch {
+            log::warn!("Nonce or recipient sent back does not match the requested one");
+            result.challenge_valid = false;
+        }
+
+        // The wallet reports the account it signed with
+        if let Some(account_id) = signed.account_id {
+            if account_id != input.expected_address {
+                log::warn!(
+                    "Wallet signed as {}, not {}",
+                    account_id,
+                    input.expected_address
+                );
+                result.address_valid = false;
+                result.derived_address = account_id;
+            }
+        }
+
+        Ok(result)
+    }
+}
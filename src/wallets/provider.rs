@@ -1,7 +1,7 @@
-use crate::types::VerificationResult;
+use crate::types::{MessageEncoding, MessageHashScheme, VerificationResult};
 
 /// Input for signature verification (flexible for different wallets)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct VerificationInput {
     /// Signature or hex blob (format varies by wallet)
     pub signature_data: String,
@@ -9,6 +9,18 @@ pub struct VerificationInput {
     pub expected_address: String,
     /// Challenge that was signed
     pub challenge: Option<String>,
+    /// How the challenge maps to the signed bytes (used by the EVM provider)
+    pub message_encoding: MessageEncoding,
+    /// Hash applied to the challenge before signing (used by the Web3Auth provider)
+    pub hash_scheme: MessageHashScheme,
+    /// Signer's public key (hex), if already known
+    ///
+    /// Providers that support it verify directly against this key (checking that it
+    /// derives to the expected address) instead of recovering it from the signature.
+    /// Required for keys that cannot be recovered, such as Web3Auth ed25519, Cosmos
+    /// ADR-036 (which also accepts base64), the Cardano CIP-30 `COSE_Key`, bare Aptos
+    /// signatures and the registered WebAuthn COSE key.
+    pub public_key: Option<String>,
 }
 
 /// Trait that each wallet provider must implement
@@ -10,7 +10,36 @@ use super::Web3AuthProvider;
 #[cfg(feature = "wallet_connect")]
 use super::WalletConnectProvider;
 
+#[cfg(feature = "solana")]
+use super::SolanaProvider;
+
+#[cfg(feature = "bitcoin")]
+use super::BitcoinProvider;
+
+#[cfg(feature = "cosmos")]
+use super::CosmosProvider;
+
+#[cfg(feature = "tron")]
+use super::TronProvider;
+
+#[cfg(feature = "cardano")]
+use super::CardanoProvider;
+
+#[cfg(feature = "substrate")]
+use super::SubstrateProvider;
+
+#[cfg(feature = "near")]
+use super::NearProvider;
+
+#[cfg(feature = "move_chains")]
+use super::SuiProvider;
+
 /// Supported wallet types
+///
+/// Providers that must be configured by the relying party before they can verify
+/// anything are library-only and have no variant here: `AptosProvider` (nonce),
+/// `SiwsProvider` (domain), `StellarProvider` (server account and home domain),
+/// `TonProvider` (allowed app domains) and `WebAuthnProvider` (RP ID and origins).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WalletType {
     #[cfg(feature = "xaman")]
@@ -19,6 +48,22 @@ pub enum WalletType {
     Web3Auth,
     #[cfg(feature = "wallet_connect")]
     WalletConnect,
+    #[cfg(feature = "solana")]
+    Solana,
+    #[cfg(feature = "bitcoin")]
+    Bitcoin,
+    #[cfg(feature = "cosmos")]
+    Cosmos,
+    #[cfg(feature = "tron")]
+    Tron,
+    #[cfg(feature = "cardano")]
+    Cardano,
+    #[cfg(feature = "substrate")]
+    Substrate,
+    #[cfg(feature = "near")]
+    Near,
+    #[cfg(feature = "move_chains")]
+    Sui,
 }
 
 impl WalletType {
@@ -35,12 +80,28 @@ impl WalletType {
             "web3auth" => Ok(WalletType::Web3Auth),
             #[cfg(feature = "wallet_connect")]
             "wallet_connect" | "walletconnect" => Ok(WalletType::WalletConnect),
+            #[cfg(feature = "solana")]
+            "solana" | "phantom" | "solflare" => Ok(WalletType::Solana),
+            #[cfg(feature = "bitcoin")]
+            "bitcoin" | "btc" | "electrum" => Ok(WalletType::Bitcoin),
+            #[cfg(feature = "cosmos")]
+            "cosmos" | "keplr" | "leap" => Ok(WalletType::Cosmos),
+            #[cfg(feature = "tron")]
+            "tron" | "tronlink" => Ok(WalletType::Tron),
+            #[cfg(feature = "cardano")]
+            "cardano" | "ada" | "cip30" | "eternl" | "nami" | "lace" => Ok(WalletType::Cardano),
+            #[cfg(feature = "substrate")]
+            "substrate" | "polkadot" | "dot" | "kusama" | "talisman" => Ok(WalletType::Substrate),
+            #[cfg(feature = "near")]
+            "near" | "nep413" => Ok(WalletType::Near),
+            #[cfg(feature = "move_chains")]
+            "sui" | "suiet" => Ok(WalletType::Sui),
             _ => Err(format!("Wallet '{}' is not supported or not enabled", s)),
         }
     }
 
     /// List all supported wallets (only those enabled via features)
-    #[allow(clippy::vec_init_then_push)] // Needed due to conditional compilation
+    #[allow(clippy::vec_init_then_push, unused_mut)] // Needed due to conditional compilation
     pub fn supported_wallets() -> Vec<&'static str> {
         let mut wallets = Vec::new();
 
@@ -53,19 +114,62 @@ impl WalletType {
         #[cfg(feature = "wallet_connect")]
         wallets.push("wallet_connect");
 
+        #[cfg(feature = "solana")]
+        wallets.push("solana");
+
+        #[cfg(feature = "bitcoin")]
+        wallets.push("bitcoin");
+
+        #[cfg(feature = "cosmos")]
+        wallets.push("cosmos");
+
+        #[cfg(feature = "tron")]
+        wallets.push("tron");
+
+        #[cfg(feature = "cardano")]
+        wallets.push("cardano");
+
+        #[cfg(feature = "substrate")]
+        wallets.push("substrate");
+
+        #[cfg(feature = "near")]
+        wallets.push("near");
+
+        #[cfg(feature = "move_chains")]
+        wallets.push("sui");
+
         wallets
     }
 }
 
 impl fmt::Display for WalletType {
+    // `f` is unused, and matching by value keeps the match exhaustive, when only
+    // library-only wallets are enabled and there are no variants
+    #[allow(unused_variables)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
+        match *self {
             #[cfg(feature = "xaman")]
             WalletType::Xaman => write!(f, "Xaman"),
             #[cfg(feature = "web3auth")]
             WalletType::Web3Auth => write!(f, "Web3Auth"),
             #[cfg(feature = "wallet_connect")]
             WalletType::WalletConnect => write!(f, "WalletConnect"),
+            #[cfg(feature = "solana")]
+            WalletType::Solana => write!(f, "Solana"),
+            #[cfg(feature = "bitcoin")]
+            WalletType::Bitcoin => write!(f, "Bitcoin"),
+            #[cfg(feature = "cosmos")]
+            WalletType::Cosmos => write!(f, "Cosmos"),
+            #[cfg(feature = "tron")]
+            WalletType::Tron => write!(f, "Tron"),
+            #[cfg(feature = "cardano")]
+            WalletType::Cardano => write!(f, "Cardano"),
+            #[cfg(feature = "substrate")]
+            WalletType::Substrate => write!(f, "Substrate"),
+            #[cfg(feature = "near")]
+            WalletType::Near => write!(f, "NEAR"),
+            #[cfg(feature = "move_chains")]
+            WalletType::Sui => write!(f, "Sui"),
         }
     }
 }
@@ -79,6 +183,22 @@ pub fn get_wallet_provider(wallet_type: WalletType) -> Box<dyn WalletProvider> {
         WalletType::Web3Auth => Box::new(Web3AuthProvider),
         #[cfg(feature = "wallet_connect")]
         WalletType::WalletConnect => Box::new(WalletConnectProvider),
+        #[cfg(feature = "solana")]
+        WalletType::Solana => Box::new(SolanaProvider),
+        #[cfg(feature = "bitcoin")]
+        WalletType::Bitcoin => Box::new(BitcoinProvider::new()),
+        #[cfg(feature = "cosmos")]
+        WalletType::Cosmos => Box::new(CosmosProvider::new()),
+        #[cfg(feature = "tron")]
+        WalletType::Tron => Box::new(TronProvider::new()),
+        #[cfg(feature = "cardano")]
+        WalletType::Cardano => Box::new(CardanoProvider::new()),
+        #[cfg(feature = "substrate")]
+        WalletType::Substrate => Box::new(SubstrateProvider::new()),
+        #[cfg(feature = "near")]
+        WalletType::Near => Box::new(NearProvider::new()),
+        #[cfg(feature = "move_chains")]
+        WalletType::Sui => Box::new(SuiProvider),
     }
 }
 
@@ -0,0 +1,169 @@
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, VerifyingKey};
+
+/// Decodes a base58 Solana address into its 32-byte ed25519 public key
+pub fn decode_solana_address(address: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| anyhow::anyhow!("Invalid Solana address '{}': {}", address, e))?;
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid Solana address '{}': expected 32 bytes, got {}",
+            address,
+            bytes.len()
+        )
+    })
+}
+
+/// Decodes a 64-byte ed25519 signature given as hex (128 chars, optional `0x`),
+/// base58 or base64
+pub fn decode_solana_signature(signature: &str) -> anyhow::Result<[u8; 64]> {
+    let hex_part = signature.strip_prefix("0x").unwrap_or(signature);
+
+    let bytes = if hex_part.len() == 128 && hex_part.chars().all(|c| c.is_ascii_hexdigit()) {
+        hex::decode(hex_part).ok()
+    } else {
+        None
+    }
+    .or_else(|| {
+        bs58::decode(signature)
+            .into_vec()
+            .ok()
+            .filter(|b| b.len() == 64)
+    })
+    .or_else(|| BASE64.decode(signature).ok())
+    .ok_or_else(|| anyhow::anyhow!("Signature is not valid hex, base58 or base64"))?;
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid ed25519 signature length: expected 64 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Verifies a Solana `signMessage` signature over the raw challenge bytes
+///
+/// The public key is the decoded address itself, so there is nothing to recover: the
+/// signature is checked directly against the expected address.
+pub fn verify_solana_signature(
+    signature: &str,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Solana verification starting");
+    log::debug!("Signature: {}", signature);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let public_key = decode_solana_address(expected_address)?;
+    verify_solana_signature_with_key(signature, challenge, &public_key, expected_address)
+}
+
+/// Verifies a Solana `signMessage` signature against a known public key
+///
+/// The key must encode (base58) to the expected address. Signatures are checked with
+/// `verify_strict`, which rejects small-order keys and non-canonical signatures.
+pub fn verify_solana_signature_with_key(
+    signature: &str,
+    challenge: &str,
+    public_key: &[u8; 32],
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    let signature = Signature::from_bytes(&decode_solana_signature(signature)?);
+    let derived_address = bs58::encode(public_key).into_string();
+    let address_valid = derived_address == expected_address;
+
+    log::debug!("Public key address: {}", derived_address);
+    if !address_valid {
+        log::warn!("Public key does not match the expected address");
+    }
+
+    let verifying_key = VerifyingKey::from_bytes(public_key)
+        .map_err(|e| anyhow::anyhow!("Invalid ed25519 public key: {}", e))?;
+
+    let signature_valid = match verifying_key.verify_strict(challenge.as_bytes(), &signature) {
+        Ok(()) => {
+            log::info!("Solana signature verification successful!");
+            true
+        }
+        Err(e) => {
+            log::warn!("Solana signature verification failed: {}", e);
+            false
+        }
+    };
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address,
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_signature_encodings() {
+        let bytes = [0x5a; 64];
+
+        assert_eq!(decode_solana_signature(&hex::encode(bytes)).unwrap(), bytes);
+        assert_eq!(
+            decode_solana_signature(&format!("0x{}", hex::encode(bytes))).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            decode_solana_signature(&bs58::encode(bytes).into_string()).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            decode_solana_signature(&BASE64.encode(bytes)).unwrap(),
+            bytes
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_lengths() {
+        assert!(decode_solana_signature(&bs58::encode([1u8; 32]).into_string()).is_err());
+        assert!(decode_solana_address(&bs58::encode([1u8; 31]).into_string()).is_err());
+        assert!(decode_solana_address("0OIl").is_err());
+    }
+
+    #[test]
+    fn test_decode_system_program_address() {
+        assert_eq!(
+            decode_solana_address("11111111111111111111111111111111").unwrap(),
+            [0u8; 32]
+        );
+    }
+
+    #[test]
+    fn test_small_order_public_key_rejected() {
+        // Identity point as public key and R, with s = 0: passes the cofactorless
+        // equation for any message, so only strict verification rejects it
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let address = bs58::encode(identity).into_string();
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&identity);
+
+        let result = verify_solana_signature_with_key(
+            &hex::encode(signature),
+            "any message",
+            &identity,
+            &address,
+        )
+        .unwrap();
+        assert!(result.address_valid);
+        assert!(!result.signature_valid);
+    }
+}
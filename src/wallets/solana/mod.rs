@@ -0,0 +1,39 @@
+//! Solana Provider
+//!
+//! This module provides signature verification for Solana wallets using ed25519
+//! `signMessage` signatures.
+//!
+//! # Supported Wallets
+//!
+//! - Phantom
+//! - Solflare
+//! - Any wallet implementing the Wallet Standard `solana:signMessage` feature
+//!
+//! # Signature Format
+//!
+//! - Signature: 64-byte ed25519 signature, encoded as base58 (Phantom default), base64 or hex
+//! - Address: base58-encoded 32-byte ed25519 public key
+//! - Message: the raw challenge bytes (no prefix or hashing)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{get_wallet_provider, WalletType, VerificationInput};
+//!
+//! let input = VerificationInput {
+//!     signature_data: "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW".to_string(),
+//!     expected_address: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = get_wallet_provider(WalletType::Solana);
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod core;
+mod provider;
+pub mod siws;
+
+pub use provider::{SiwsProvider, SolanaProvider};
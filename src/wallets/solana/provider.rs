@@ -0,0 +1,175 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::core::{
+    decode_solana_address, verify_solana_signature, verify_solana_signature_with_key,
+};
+use super::siws::{verify_siws_signature, SiwsRules};
+use crate::types::VerificationResult;
+use hex::FromHex;
+use std::time::SystemTime;
+
+/// Provider for Solana wallets (Phantom, Solflare, etc.)
+///
+/// Verifies ed25519 `signMessage` signatures over the raw challenge bytes. The address
+/// is the base58 public key; if [`VerificationInput::public_key`] is set (hex or base58)
+/// it is verified against and must encode to the expected address.
+pub struct SolanaProvider;
+
+impl WalletProvider for SolanaProvider {
+    fn name(&self) -> &str {
+        "Solana"
+    }
+
+    fn description(&self) -> &str {
+        "Solana - ed25519 signMessage verification (Phantom, Solflare)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Solana: challenge is required for verification"
+            ));
+        }
+
+        if input.signature_data.is_empty() {
+            return Err(anyhow::anyhow!("Solana: signature_data is required"));
+        }
+
+        // Validate that address is a base58 ed25519 public key
+        decode_solana_address(&input.expected_address)
+            .map_err(|e| anyhow::anyhow!("Solana: {}", e))?;
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Solana requires challenge"))?;
+
+        match &input.public_key {
+            Some(public_key) => {
+                let public_key = decode_public_key(public_key)?;
+                verify_solana_signature_with_key(
+                    &input.signature_data,
+                    challenge,
+                    &public_key,
+                    &input.expected_address,
+                )
+            }
+            None => {
+                verify_solana_signature(&input.signature_data, challenge, &input.expected_address)
+            }
+        }
+    }
+}
+
+/// Decodes a supplied public key given as hex (64 chars) or base58
+fn decode_public_key(public_key: &str) -> anyhow::Result<[u8; 32]> {
+    match <[u8; 32]>::from_hex(public_key.trim_start_matches("0x")) {
+        Ok(key) => Ok(key),
+        Err(_) => decode_solana_address(public_key)
+            .map_err(|e| anyhow::anyhow!("Solana: invalid public key: {}", e)),
+    }
+}
+
+/// Provider for Sign In With Solana (SIWS) messages
+///
+/// The challenge is the full SIWS message text the wallet signed. The message is
+/// parsed, its address must be the expected address, and it is checked against the
+/// configured domain and the current time (expiration and not-before) before the
+/// ed25519 signature is verified. The domain is required, so this provider is
+/// configured by the application and not available from the CLI registry.
+///
+/// ```rust,no_run
+/// use wallet_signature_verify::wallets::{SiwsProvider, VerificationInput, WalletProvider};
+///
+/// let provider = SiwsProvider::new().with_domain("example.com");
+/// let input = VerificationInput {
+///     signature_data: "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW".to_string(),
+///     expected_address: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+///     challenge: Some("example.com wants you to sign in with your Solana account:\n9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let result = provider.verify(&input)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SiwsProvider {
+    rules: SiwsRules,
+}
+
+impl SiwsProvider {
+    /// Creates a provider that checks times against now; set the domain with
+    /// [`with_domain`](Self::with_domain) before verifying
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires messages to be issued for `domain`
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.rules.domain = Some(domain.to_string());
+        self
+    }
+
+    /// Checks expiration and not-before against `time` instead of the current time
+    pub fn with_time(mut self, time: SystemTime) -> Self {
+        self.rules.time = Some(time);
+        self
+    }
+}
+
+impl WalletProvider for SiwsProvider {
+    fn name(&self) -> &str {
+        "SIWS"
+    }
+
+    fn description(&self) -> &str {
+        "Sign In With Solana - SIWS message and ed25519 signature verification"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "SIWS: challenge (the SIWS message) is required for verification"
+            ));
+        }
+
+        if input.signature_data.is_empty() {
+            return Err(anyhow::anyhow!("SIWS: signature_data is required"));
+        }
+
+        decode_solana_address(&input.expected_address)
+            .map_err(|e| anyhow::anyhow!("SIWS: {}", e))?;
+
+        // Without a domain, a message signed for any other site would be accepted
+        if self.rules.domain.is_none() {
+            return Err(anyhow::anyhow!(
+                "SIWS: expected domain is required for verification"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let message = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("SIWS requires challenge"))?;
+
+        verify_siws_signature(
+            &input.signature_data,
+            message,
+            &input.expected_address,
+            &self.rules,
+        )
+        .map_err(|e| anyhow::anyhow!("SIWS: {}", e))
+    }
+}
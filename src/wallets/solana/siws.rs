@@ -0,0 +1,415 @@
+use super::core::verify_solana_signature;
+use crate::caip::{AccountId, ChainId, SignInMessage};
+use crate::types::VerificationResult;
+use chrono::DateTime;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// First line of every SIWS message, after the domain
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+
+/// Genesis hashes of the public Solana clusters, by SIWS chain ID
+const CLUSTERS: [(&str, &str); 3] = [
+    ("mainnet", "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"),
+    ("devnet", "EtWTRABZaYq6iMfeYKouRu166VU2xqa1"),
+    ("testnet", "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3z"),
+];
+
+/// "Sign In With Solana" message (the SIWS input/output fields Phantom and Solflare sign)
+///
+/// Unlike SIWE, every field except the domain and address is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiwsMessage {
+    /// RFC 3986 authority requesting the sign-in
+    pub domain: String,
+    /// Base58 address performing the sign-in
+    pub address: String,
+    /// Human-readable assertion the user signs
+    pub statement: Option<String>,
+    /// RFC 3986 URI referring to the resource that is the subject of the signing
+    pub uri: Option<String>,
+    /// Message version (currently "1")
+    pub version: Option<String>,
+    /// Cluster: `mainnet`, `devnet`, `testnet`, `localnet` (optionally `solana:`-prefixed)
+    pub chain_id: Option<String>,
+    /// Randomized token to prevent replay attacks
+    pub nonce: Option<String>,
+    /// ISO 8601 timestamp of when the message was generated
+    pub issued_at: Option<String>,
+    /// ISO 8601 timestamp after which the message is no longer valid
+    pub expiration_time: Option<String>,
+    /// ISO 8601 timestamp before which the message is not yet valid
+    pub not_before: Option<String>,
+    /// System-specific identifier for the sign-in request
+    pub request_id: Option<String>,
+    /// URIs the user wishes to have resolved as part of the sign-in
+    pub resources: Vec<String>,
+}
+
+impl SiwsMessage {
+    /// Renders the message text the wallet signs (`createSignInMessageText`)
+    pub fn render(&self) -> String {
+        let mut message = format!("{}{}\n{}", self.domain, HEADER_SUFFIX, self.address);
+
+        if let Some(statement) = &self.statement {
+            message.push_str(&format!("\n\n{}", statement));
+        }
+
+        let mut fields = Vec::new();
+        let optional_fields = [
+            ("URI", &self.uri),
+            ("Version", &self.version),
+            ("Chain ID", &self.chain_id),
+            ("Nonce", &self.nonce),
+            ("Issued At", &self.issued_at),
+            ("Expiration Time", &self.expiration_time),
+            ("Not Before", &self.not_before),
+            ("Request ID", &self.request_id),
+        ];
+        for (key, value) in optional_fields {
+            if let Some(value) = value {
+                fields.push(format!("{}: {}", key, value));
+            }
+        }
+        if !self.resources.is_empty() {
+            fields.push("Resources:".to_string());
+            for resource in &self.resources {
+                fields.push(format!("- {}", resource));
+            }
+        }
+
+        if !fields.is_empty() {
+            message.push_str(&format!("\n\n{}", fields.join("\n")));
+        }
+
+        message
+    }
+
+    /// Parses SIWS message text
+    ///
+    /// The message must be in canonical form: rendering the parsed fields gives back the
+    /// exact text, so nothing the user signed can be silently ignored.
+    pub fn parse(message: &str) -> anyhow::Result<Self> {
+        let mut sections = message.split("\n\n");
+
+        let head = sections
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("SIWS message is empty"))?;
+        let (header, address) = head
+            .split_once('\n')
+            .ok_or_else(|| anyhow::anyhow!("Missing SIWS address line"))?;
+        let domain = header
+            .strip_suffix(HEADER_SUFFIX)
+            .filter(|d| !d.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing SIWS message header"))?;
+
+        let mut siws = SiwsMessage {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            ..Default::default()
+        };
+
+        for section in sections {
+            if is_fields_section(section) {
+                siws.parse_fields(section)?;
+            } else if siws.statement.is_none() {
+                siws.statement = Some(section.to_string());
+            } else {
+                return Err(anyhow::anyhow!("Unexpected SIWS message section"));
+            }
+        }
+
+        if siws.render() != message {
+            return Err(anyhow::anyhow!("SIWS message is not in canonical form"));
+        }
+
+        Ok(siws)
+    }
+
+    fn parse_fields(&mut self, section: &str) -> anyhow::Result<()> {
+        let mut in_resources = false;
+
+        for line in section.lines() {
+            if in_resources {
+                if let Some(resource) = line.strip_prefix("- ") {
+                    self.resources.push(resource.to_string());
+                    continue;
+                }
+                in_resources = false;
+            }
+
+            if line == "Resources:" {
+                in_resources = true;
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| anyhow::anyhow!("Malformed SIWS message line: '{}'", line))?;
+            let value = Some(value.to_string());
+            match key {
+                "URI" => self.uri = value,
+                "Version" => self.version = value,
+                "Chain ID" => self.chain_id = value,
+                "Nonce" => self.nonce = value,
+                "Issued At" => self.issued_at = value,
+                "Expiration Time" => self.expiration_time = value,
+                "Not Before" => self.not_before = value,
+                "Request ID" => self.request_id = value,
+                _ => return Err(anyhow::anyhow!("Unknown SIWS message field: '{}'", key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// CAIP-2 chain of the message's cluster (mainnet when no chain ID is given)
+    ///
+    /// Returns `None` for clusters without a well-known genesis hash (e.g. `localnet`).
+    pub fn caip2_chain_id(&self) -> Option<ChainId> {
+        let cluster = self.chain_id.as_deref().unwrap_or("mainnet");
+        let cluster = cluster.strip_prefix("solana:").unwrap_or(cluster);
+
+        CLUSTERS
+            .iter()
+            .find(|(name, _)| *name == cluster)
+            .map(|(_, genesis_hash)| ChainId::solana(genesis_hash))
+    }
+
+    /// CAIP-122 view of the message, when it has every field CAIP-122 requires
+    pub fn to_sign_in_message(&self) -> Option<SignInMessage> {
+        Some(SignInMessage {
+            domain: self.domain.clone(),
+            account: AccountId::new(self.caip2_chain_id()?, &self.address).ok()?,
+            statement: self.statement.clone(),
+            uri: self.uri.clone()?,
+            version: self.version.clone()?,
+            nonce: self.nonce.clone()?,
+            issued_at: self.issued_at.clone()?,
+            expiration_time: self.expiration_time.clone(),
+            not_before: self.not_before.clone(),
+            request_id: self.request_id.clone(),
+            resources: self.resources.clone(),
+        })
+    }
+}
+
+/// A fields section starts with one of the SIWS field keys
+fn is_fields_section(section: &str) -> bool {
+    const KEYS: [&str; 9] = [
+        "URI: ",
+        "Version: ",
+        "Chain ID: ",
+        "Nonce: ",
+        "Issued At: ",
+        "Expiration Time: ",
+        "Not Before: ",
+        "Request ID: ",
+        "Resources:",
+    ];
+    KEYS.iter().any(|key| section.starts_with(key))
+}
+
+/// Checks applied to a SIWS message before it is accepted
+#[derive(Debug, Clone, Default)]
+pub struct SiwsRules {
+    /// Domain the message must be issued for (required)
+    pub domain: Option<String>,
+    /// Time to check expiration and not-before against (defaults to now)
+    pub time: Option<SystemTime>,
+}
+
+impl SiwsRules {
+    /// Validates the message against these rules and the expected address
+    pub fn validate(&self, message: &SiwsMessage, expected_address: &str) -> anyhow::Result<()> {
+        if message.address != expected_address {
+            return Err(anyhow::anyhow!(
+                "SIWS address {} does not match expected address {}",
+                message.address,
+                expected_address
+            ));
+        }
+
+        let domain = self
+            .domain
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Expected SIWS domain is required"))?;
+        if message.domain != domain {
+            return Err(anyhow::anyhow!(
+                "SIWS domain '{}' does not match expected domain '{}'",
+                message.domain,
+                domain
+            ));
+        }
+
+        let now = self
+            .time
+            .unwrap_or_else(SystemTime::now)
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| anyhow::anyhow!("Invalid verification time: {}", e))?
+            .as_secs() as i64;
+
+        if let Some(issued_at) = &message.issued_at {
+            parse_timestamp("Issued At", issued_at)?;
+        }
+        if let Some(expiration_time) = &message.expiration_time {
+            if now >= parse_timestamp("Expiration Time", expiration_time)? {
+                return Err(anyhow::anyhow!(
+                    "SIWS message expired at {}",
+                    expiration_time
+                ));
+            }
+        }
+        if let Some(not_before) = &message.not_before {
+            if now < parse_timestamp("Not Before", not_before)? {
+                return Err(anyhow::anyhow!(
+                    "SIWS message is not valid before {}",
+                    not_before
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses an ISO 8601 / RFC 3339 timestamp into Unix seconds
+fn parse_timestamp(field: &str, value: &str) -> anyhow::Result<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.timestamp())
+        .map_err(|e| anyhow::anyhow!("Invalid SIWS '{}' timestamp '{}': {}", field, value, e))
+}
+
+/// Verifies a Sign In With Solana message and its ed25519 signature
+///
+/// - `address_valid`: the message address and the signing key are the expected address
+/// - `challenge_valid`: the message passes `rules` (domain, expiration, not-before)
+/// - `signature_valid`: the ed25519 signature over the message text is valid
+pub fn verify_siws_signature(
+    signature: &str,
+    message: &str,
+    expected_address: &str,
+    rules: &SiwsRules,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("SIWS verification starting");
+
+    let siws = SiwsMessage::parse(message)?;
+    log::debug!("SIWS message for {} on {}", siws.address, siws.domain);
+
+    let mut result = verify_solana_signature(signature, message, expected_address)?;
+
+    result.address_valid &= siws.address == expected_address;
+    result.challenge_valid = match rules.validate(&siws, expected_address) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("SIWS message rejected: {}", e);
+            false
+        }
+    };
+    result.sign_in = siws.to_sign_in_message();
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_message() -> SiwsMessage {
+        SiwsMessage {
+            domain: "example.com".to_string(),
+            address: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+            statement: Some("Sign in to Example".to_string()),
+            uri: Some("https://example.com/login".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some("mainnet".to_string()),
+            nonce: Some("32891756".to_string()),
+            issued_at: Some("2025-10-17T12:00:00Z".to_string()),
+            expiration_time: Some("2025-10-17T12:10:00Z".to_string()),
+            not_before: None,
+            request_id: None,
+            resources: vec!["https://example.com/terms".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_render_and_parse_roundtrip() {
+        let message = full_message();
+        let rendered = message.render();
+
+        assert!(rendered.starts_with(
+            "example.com wants you to sign in with your Solana account:\n\
+             9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM\n\nSign in to Example\n\nURI: "
+        ));
+        assert_eq!(SiwsMessage::parse(&rendered).unwrap(), message);
+    }
+
+    #[test]
+    fn test_minimal_message() {
+        let message = SiwsMessage {
+            domain: "example.com".to_string(),
+            address: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+            ..Default::default()
+        };
+        let rendered = message.render();
+
+        assert_eq!(rendered.lines().count(), 2);
+        assert_eq!(SiwsMessage::parse(&rendered).unwrap(), message);
+        assert!(message.to_sign_in_message().is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_non_canonical_text() {
+        let rendered = full_message().render();
+
+        assert!(SiwsMessage::parse(&format!("{}\n", rendered)).is_err());
+        assert!(SiwsMessage::parse(&rendered.replace("Nonce:", "Nonse:")).is_err());
+        assert!(SiwsMessage::parse("example.com:1760706960:afba42ef:login").is_err());
+    }
+
+    #[test]
+    fn test_caip122_view() {
+        let sign_in = full_message().to_sign_in_message().unwrap();
+
+        assert_eq!(
+            sign_in.account.to_string(),
+            "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp:9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let message = full_message();
+        let address = message.address.clone();
+        let at = |rfc3339: &str| {
+            let secs = DateTime::parse_from_rfc3339(rfc3339).unwrap().timestamp() as u64;
+            Some(UNIX_EPOCH + std::time::Duration::from_secs(secs))
+        };
+
+        let rules = SiwsRules {
+            domain: Some("example.com".to_string()),
+            time: at("2025-10-17T12:05:00Z"),
+        };
+        assert!(rules.validate(&message, &address).is_ok());
+        assert!(rules
+            .validate(&message, "11111111111111111111111111111111")
+            .is_err());
+
+        let expired = SiwsRules {
+            time: at("2025-10-17T12:10:00Z"),
+            ..rules.clone()
+        };
+        assert!(expired.validate(&message, &address).is_err());
+
+        let other_domain = SiwsRules {
+            domain: Some("evil.com".to_string()),
+            ..rules.clone()
+        };
+        assert!(other_domain.validate(&message, &address).is_err());
+
+        let any_domain = SiwsRules {
+            domain: None,
+            ..rules
+        };
+        assert!(any_domain.validate(&message, &address).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Stellar Provider
+//!
+//! This module provides verification of SEP-10 Web Authentication challenges, the
+//! Stellar counterpart of XRPL SignIn transactions.
+//!
+//! # Supported Wallets
+//!
+//! - Freighter
+//! - Lobstr, xBull, Albedo and other SEP-10 clients
+//!
+//! # Challenge Format
+//!
+//! - Transaction envelope XDR (base64) built by the server: source account = server
+//!   signing key, sequence number 0, time bounds
+//! - First operation: `ManageData("<home_domain> auth", <64-byte nonce>)` with the
+//!   client account as source
+//! - An optional `client_domain` operation has the client domain's signing key as
+//!   source; other operations (such as `web_auth_domain`) have the server account
+//! - Signatures: ed25519 over `SHA-256(SHA-256(passphrase) || ENVELOPE_TYPE_TX || tx)`,
+//!   by the server, the client domain account (if any) and then by the client; no
+//!   other signatures are allowed
+//! - Address: StrKey account ID (`G...`)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{StellarProvider, VerificationInput, WalletProvider};
+//!
+//! let input = VerificationInput {
+//!     signature_data: "AAAAAgAAAACpn2Fr7GAZ4XOcFvEz+xduBFDK1NDLQP875GtWWlJ0XQAAAMgAAAAA...".to_string(),
+//!     expected_address: "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ".to_string(),
+//!     challenge: None,
+//!     ..Default::default()
+//! };
+//!
+//! let provider = StellarProvider::new()
+//!     .with_server_account("GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF")
+//!     .with_home_domain("example.com")
+//!     .with_web_auth_domain("auth.example.com");
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+mod provider;
+pub mod sep10;
+pub mod strkey;
+pub mod xdr;
+
+pub use provider::StellarProvider;
+pub use sep10::Sep10Rules;
@@ -0,0 +1,97 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::sep10::{verify_stellar_challenge, Sep10Rules};
+use super::strkey::decode_account_id;
+use crate::types::VerificationResult;
+use std::time::SystemTime;
+
+/// Provider for Stellar wallets (SEP-10 Web Authentication)
+///
+/// The signature data is the base64 transaction envelope of the challenge, signed by
+/// the server and then by the client. The challenge, if given, is the nonce of the
+/// first `ManageData` operation. The server account and home domain must be set with
+/// [`StellarProvider::with_server_account`] and [`StellarProvider::with_home_domain`];
+/// without them the challenge is reported invalid, so this provider is not in the CLI
+/// registry.
+#[derive(Debug, Clone, Default)]
+pub struct StellarProvider {
+    rules: Sep10Rules,
+}
+
+impl StellarProvider {
+    /// Creates a provider for the public network that checks time bounds against now;
+    /// set the server account and home domain before verifying
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the challenge to be issued and signed by this server account (`G...`)
+    pub fn with_server_account(mut self, server_account: &str) -> Self {
+        self.rules.server_account = Some(server_account.to_string());
+        self
+    }
+
+    /// Requires the first operation to be `<home_domain> auth`
+    pub fn with_home_domain(mut self, home_domain: &str) -> Self {
+        self.rules.home_domain = Some(home_domain.to_string());
+        self
+    }
+
+    /// Requires a `web_auth_domain` operation with this value
+    pub fn with_web_auth_domain(mut self, web_auth_domain: &str) -> Self {
+        self.rules.web_auth_domain = Some(web_auth_domain.to_string());
+        self
+    }
+
+    /// Sets the network passphrase (see [`TESTNET_NETWORK_PASSPHRASE`](super::sep10::TESTNET_NETWORK_PASSPHRASE))
+    pub fn with_network_passphrase(mut self, network_passphrase: &str) -> Self {
+        self.rules.network_passphrase = Some(network_passphrase.to_string());
+        self
+    }
+
+    /// Checks the time bounds against `time` instead of the current time
+    pub fn with_time(mut self, time: SystemTime) -> Self {
+        self.rules.time = Some(time);
+        self
+    }
+}
+
+impl WalletProvider for StellarProvider {
+    fn name(&self) -> &str {
+        "Stellar"
+    }
+
+    fn description(&self) -> &str {
+        "Stellar - SEP-10 challenge transaction verification (Freighter, Lobstr, xBull)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that signature_data looks like a base64 XDR envelope
+        let envelope = input.signature_data.trim();
+        if envelope.is_empty()
+            || !envelope
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='))
+        {
+            return Err(anyhow::anyhow!(
+                "Stellar: signature_data must be a base64 transaction envelope"
+            ));
+        }
+
+        // Validate that address is a G... account ID
+        decode_account_id(&input.expected_address)
+            .map_err(|e| anyhow::anyhow!("Stellar: {}", e))?;
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        verify_stellar_challenge(
+            &input.signature_data,
+            &input.expected_address,
+            input.challenge.as_deref(),
+            &self.rules,
+        )
+    }
+}
@@ -0,0 +1,312 @@
+use super::strkey::{decode_account_id, encode_account_id};
+use super::xdr::{DecoratedSignature, Memo, OperationBody, TransactionEnvelope};
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use ed25519_dalek::{Signature, VerifyingKey};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Network passphrase of the Stellar public network
+pub const PUBLIC_NETWORK_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+
+/// Network passphrase of the Stellar test network
+pub const TESTNET_NETWORK_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+/// Name of the ManageData operation carrying the web auth domain
+pub const WEB_AUTH_DOMAIN_KEY: &str = "web_auth_domain";
+
+/// Name of the ManageData operation carrying the client domain
+pub const CLIENT_DOMAIN_KEY: &str = "client_domain";
+
+/// Length of the first operation's value: base64 of a 48-byte random nonce
+pub const CHALLENGE_NONCE_LENGTH: usize = 64;
+
+/// SEP-10 rules the challenge transaction is checked against
+///
+/// The server account and home domain are required: a challenge checked without them
+/// is never valid, since anyone can build and sign a challenge of their own.
+#[derive(Debug, Clone, Default)]
+pub struct Sep10Rules {
+    /// Server signing key (`SIGNING_KEY` in stellar.toml)
+    pub server_account: Option<String>,
+    /// Home domain the first operation must be named after (`<home_domain> auth`)
+    pub home_domain: Option<String>,
+    /// Value the `web_auth_domain` operation must have
+    pub web_auth_domain: Option<String>,
+    /// Network passphrase (defaults to the public network)
+    pub network_passphrase: Option<String>,
+    /// Time to check the time bounds against (defaults to now)
+    pub time: Option<SystemTime>,
+}
+
+impl Sep10Rules {
+    fn network_passphrase(&self) -> &str {
+        self.network_passphrase
+            .as_deref()
+            .unwrap_or(PUBLIC_NETWORK_PASSPHRASE)
+    }
+
+    /// Validates the challenge structure, time bounds, domains and signatures
+    ///
+    /// The server and, if the challenge has a `client_domain` operation, that
+    /// operation's source account (the client domain's signing key, looked up by the
+    /// server when it issued the challenge) must have signed. Signatures by any other
+    /// key than these and the client account are rejected.
+    pub fn validate(&self, envelope: &TransactionEnvelope) -> anyhow::Result<()> {
+        let transaction = &envelope.transaction;
+        let server_key = transaction.source_account;
+        let client_key = transaction
+            .operations
+            .first()
+            .and_then(|operation| operation.source_account);
+        let mut client_domain_key = None;
+
+        let server_account = self
+            .server_account
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Server account is required to validate challenges"))?;
+        let expected_home_domain = self
+            .home_domain
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Home domain is required to validate challenges"))?;
+        if decode_account_id(server_account)? != server_key {
+            return Err(anyhow::anyhow!(
+                "Challenge source account {} is not the server account {}",
+                encode_account_id(&server_key),
+                server_account
+            ));
+        }
+
+        if transaction.seq_num != 0 {
+            return Err(anyhow::anyhow!(
+                "Challenge sequence number must be 0, got {}",
+                transaction.seq_num
+            ));
+        }
+        if !matches!(transaction.memo, Memo::None | Memo::Id(_)) {
+            return Err(anyhow::anyhow!("Challenge memo must be empty or an ID"));
+        }
+
+        // Time bounds
+        let time_bounds = transaction
+            .time_bounds
+            .ok_or_else(|| anyhow::anyhow!("Challenge has no time bounds"))?;
+        let now = self
+            .time
+            .unwrap_or_else(SystemTime::now)
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| anyhow::anyhow!("Invalid verification time: {}", e))?
+            .as_secs();
+        if time_bounds.max_time == 0 {
+            return Err(anyhow::anyhow!("Challenge time bounds have no upper bound"));
+        }
+        if now < time_bounds.min_time || now > time_bounds.max_time {
+            return Err(anyhow::anyhow!(
+                "Challenge is only valid between {} and {}, now is {}",
+                time_bounds.min_time,
+                time_bounds.max_time,
+                now
+            ));
+        }
+
+        // Operations: `<home_domain> auth` by the client, `client_domain` by the client
+        // domain account, anything else by the server
+        for (index, operation) in transaction.operations.iter().enumerate() {
+            let OperationBody::ManageData { name, value } = &operation.body;
+
+            if index == 0 {
+                let home_domain = name.strip_suffix(" auth").ok_or_else(|| {
+                    anyhow::anyhow!("First operation '{}' is not '<home_domain> auth'", name)
+                })?;
+                if home_domain != expected_home_domain {
+                    return Err(anyhow::anyhow!(
+                        "Challenge home domain '{}' does not match expected domain '{}'",
+                        home_domain,
+                        expected_home_domain
+                    ));
+                }
+                if value.as_ref().map(Vec::len) != Some(CHALLENGE_NONCE_LENGTH) {
+                    return Err(anyhow::anyhow!(
+                        "First operation value must be a {}-byte nonce",
+                        CHALLENGE_NONCE_LENGTH
+                    ));
+                }
+                continue;
+            }
+
+            if name == CLIENT_DOMAIN_KEY {
+                let source = operation.source_account.ok_or_else(|| {
+                    anyhow::anyhow!("Operation 'client_domain' has no source account")
+                })?;
+                if client_domain_key.replace(source).is_some() {
+                    return Err(anyhow::anyhow!(
+                        "Challenge has more than one client_domain operation"
+                    ));
+                }
+                continue;
+            }
+
+            if operation.source_account != Some(server_key) {
+                return Err(anyhow::anyhow!(
+                    "Operation '{}' must have the server account as source",
+                    name
+                ));
+            }
+            if name == WEB_AUTH_DOMAIN_KEY {
+                if let Some(expected) = &self.web_auth_domain {
+                    if value.as_deref() != Some(expected.as_bytes()) {
+                        return Err(anyhow::anyhow!(
+                            "Challenge web_auth_domain does not match expected '{}'",
+                            expected
+                        ));
+                    }
+                }
+            }
+        }
+        if self.web_auth_domain.is_some()
+            && !transaction.operations.iter().any(|operation| {
+                matches!(&operation.body, OperationBody::ManageData { name, .. } if name == WEB_AUTH_DOMAIN_KEY)
+            })
+        {
+            return Err(anyhow::anyhow!("Challenge has no web_auth_domain operation"));
+        }
+
+        // The server must have signed the challenge it issued, the client domain account
+        // its operation, and nobody else may have signed
+        let hash = envelope.signature_base_hash(self.network_passphrase());
+        if !has_signature(envelope, &server_key, &hash) {
+            return Err(anyhow::anyhow!("Challenge is not signed by the server"));
+        }
+        if let Some(client_domain_key) = client_domain_key {
+            if !has_signature(envelope, &client_domain_key, &hash) {
+                return Err(anyhow::anyhow!(
+                    "Challenge is not signed by the client domain account {}",
+                    encode_account_id(&client_domain_key)
+                ));
+            }
+        }
+        let signers = [Some(server_key), client_key, client_domain_key];
+        for signature in &envelope.signatures {
+            if !signers
+                .iter()
+                .flatten()
+                .any(|key| is_signed_by(signature, key, &hash))
+            {
+                return Err(anyhow::anyhow!(
+                    "Challenge has a signature that is not by the server, client or client domain account"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether one of the envelope signatures is a valid signature of `hash` by `public_key`
+fn has_signature(envelope: &TransactionEnvelope, public_key: &[u8; 32], hash: &[u8; 32]) -> bool {
+    envelope
+        .signatures
+        .iter()
+        .any(|signature| is_signed_by(signature, public_key, hash))
+}
+
+/// Whether `signature` is a valid signature of `hash` by `public_key`
+///
+/// Signatures are checked with `verify_strict`, which rejects small-order keys.
+fn is_signed_by(signature: &DecoratedSignature, public_key: &[u8; 32], hash: &[u8; 32]) -> bool {
+    if signature.hint != public_key[28..] {
+        return false;
+    }
+    let Ok(verifying_key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    Signature::from_slice(&signature.signature)
+        .is_ok_and(|signature| verifying_key.verify_strict(hash, &signature).is_ok())
+}
+
+/// Verifies a client-signed SEP-10 challenge transaction
+///
+/// Like [`verify_xrpl_signin`](crate::verify_xrpl_signin) for XRPL SignIn blobs:
+/// - `address_valid`: the first operation's source account is the expected `G...` address
+/// - `challenge_valid`: the challenge follows the SEP-10 `rules` (including the server
+///   and client domain signatures, and no signature by another key) and, if given, its
+///   nonce is `expected_challenge`
+/// - `signature_valid`: the client signed the transaction
+pub fn verify_stellar_challenge(
+    envelope_base64: &str,
+    expected_address: &str,
+    expected_challenge: Option<&str>,
+    rules: &Sep10Rules,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Starting Stellar SEP-10 verification");
+    log::debug!("Expected address: {}", expected_address);
+    log::debug!("Expected challenge: {:?}", expected_challenge);
+
+    let envelope = TransactionEnvelope::from_base64(envelope_base64)?;
+    log::debug!(
+        "Decoded challenge with {} operations and {} signatures",
+        envelope.transaction.operations.len(),
+        envelope.signatures.len()
+    );
+
+    let first = envelope
+        .transaction
+        .operations
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Challenge transaction has no operations"))?;
+    let client_key = first
+        .source_account
+        .ok_or_else(|| anyhow::anyhow!("First operation has no source account"))?;
+    let OperationBody::ManageData { value, .. } = &first.body;
+
+    // 1. Verify Address
+    let derived_address = encode_account_id(&client_key);
+    let address_valid = derived_address == expected_address;
+
+    log::info!(
+        "Address verification: {} (derived: {})",
+        if address_valid { "VALID" } else { "INVALID" },
+        derived_address
+    );
+
+    // 2. Verify Challenge: SEP-10 rules and nonce
+    let found_challenge = value
+        .as_ref()
+        .map(|value| String::from_utf8_lossy(value).to_string());
+    let rules_valid = match rules.validate(&envelope) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("SEP-10 challenge rejected: {}", e);
+            false
+        }
+    };
+    let nonce_valid =
+        expected_challenge.is_none_or(|expected| found_challenge.as_deref() == Some(expected));
+    let challenge_valid = rules_valid && nonce_valid;
+
+    log::info!(
+        "Challenge verification: {}",
+        if challenge_valid { "VALID" } else { "INVALID" }
+    );
+
+    // 3. Verify Signature
+    let hash = envelope.signature_base_hash(rules.network_passphrase());
+    let signature_valid = has_signature(&envelope, &client_key, &hash);
+
+    log::info!(
+        "Signature verification: {}",
+        if signature_valid { "VALID" } else { "INVALID" }
+    );
+
+    let sign_in = found_challenge.as_deref().and_then(parse_sign_in_challenge);
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid,
+        signature_valid,
+        derived_address,
+        found_challenge,
+        sign_in,
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
@@ -0,0 +1,126 @@
+//! StrKey (SEP-23) encoding of Stellar account IDs (`G...`)
+
+/// RFC 4648 base32 alphabet used by StrKey
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Version byte of ed25519 account IDs (`G...`)
+const VERSION_ACCOUNT_ID: u8 = 6 << 3;
+
+/// CRC16-XModem checksum appended to the payload (little-endian)
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(data: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in data.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|a| *a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Encodes an ed25519 public key as a `G...` account ID
+pub fn encode_account_id(public_key: &[u8; 32]) -> String {
+    let mut payload = vec![VERSION_ACCOUNT_ID];
+    payload.extend_from_slice(public_key);
+    let checksum = crc16_xmodem(&payload);
+    payload.extend_from_slice(&checksum.to_le_bytes());
+    base32_encode(&payload)
+}
+
+/// Decodes a `G...` account ID into its ed25519 public key
+pub fn decode_account_id(account_id: &str) -> anyhow::Result<[u8; 32]> {
+    let invalid =
+        |reason: &str| anyhow::anyhow!("Invalid Stellar account '{}': {}", account_id, reason);
+
+    if account_id.len() != 56 {
+        return Err(invalid("expected 56 characters"));
+    }
+    let data = base32_decode(account_id).ok_or_else(|| invalid("not base32"))?;
+    if data[0] != VERSION_ACCOUNT_ID {
+        return Err(invalid("not an account ID (G...)"));
+    }
+
+    let (payload, checksum) = data.split_at(33);
+    if crc16_xmodem(payload).to_le_bytes() != checksum {
+        return Err(invalid("bad checksum"));
+    }
+
+    let public_key: [u8; 32] = payload[1..].try_into()?;
+    // Reject non-canonical encodings (unused trailing bits set)
+    if encode_account_id(&public_key) != account_id {
+        return Err(invalid("non-canonical encoding"));
+    }
+    Ok(public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sep23_account_id_vectors() {
+        assert_eq!(
+            encode_account_id(&[0u8; 32]),
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"
+        );
+
+        let public_key = "3f0c34bf93ad0d9971d04ccc90f705511c838aad9734a4a2fb0d7a03fc7fe89a";
+        let account_id = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+        assert_eq!(
+            hex::encode(decode_account_id(account_id).unwrap()),
+            public_key
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_account_ids() {
+        // Bad checksum
+        assert!(
+            decode_account_id("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGA").is_err()
+        );
+        // Secret seed (S...)
+        assert!(
+            decode_account_id("SBU2RRGLXH3E5CQHTD3ODLDF2BWDCYUSSBLLZ5GNW7JXHDIYKXZWHOKR").is_err()
+        );
+        // Lowercase
+        assert!(
+            decode_account_id("ga7qynf7sowq3glr2bgmzehxavirza4kvwltjjfc7mgxua74p7ujvsgz").is_err()
+        );
+    }
+}
@@ -0,0 +1,481 @@
+//! XDR decoding of the Stellar transaction envelopes used as SEP-10 challenges
+//!
+//! Only what a challenge can contain is decoded: v1 envelopes (`ENVELOPE_TYPE_TX`)
+//! whose operations are all `ManageData`.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use sha2::{Digest, Sha256};
+
+/// `ENVELOPE_TYPE_TX`, also prefixed to the transaction in the signature base
+pub const ENVELOPE_TYPE_TX: i32 = 2;
+
+/// `MANAGE_DATA` operation type
+const OPERATION_MANAGE_DATA: i32 = 10;
+
+/// Minimum and maximum close time (UNIX seconds, `0` = unbounded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeBounds {
+    pub min_time: u64,
+    pub max_time: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Memo {
+    None,
+    Text(String),
+    Id(u64),
+    Hash([u8; 32]),
+    Return([u8; 32]),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationBody {
+    ManageData {
+        name: String,
+        value: Option<Vec<u8>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    /// ed25519 key of the operation source account, if set (muxed IDs are dropped)
+    pub source_account: Option<[u8; 32]>,
+    pub body: OperationBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    /// ed25519 key of the transaction source account (muxed IDs are dropped)
+    pub source_account: [u8; 32],
+    pub fee: u32,
+    pub seq_num: i64,
+    pub time_bounds: Option<TimeBounds>,
+    pub memo: Memo,
+    pub operations: Vec<Operation>,
+}
+
+/// Signature with the last 4 bytes of the signer's key as hint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoratedSignature {
+    pub hint: [u8; 4],
+    pub signature: Vec<u8>,
+}
+
+/// `TransactionV1Envelope`: the transaction and its signatures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionEnvelope {
+    pub transaction: Transaction,
+    /// Transaction XDR exactly as encoded in the envelope
+    pub transaction_xdr: Vec<u8>,
+    pub signatures: Vec<DecoratedSignature>,
+}
+
+impl Transaction {
+    /// Encodes the transaction XDR
+    ///
+    /// Time bounds are encoded as `PRECOND_TIME`, and muxed account IDs are not kept
+    /// when decoding, so a decoded transaction may not re-encode to the same bytes.
+    pub fn to_xdr(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_account(&mut out, &self.source_account);
+        out.extend_from_slice(&self.fee.to_be_bytes());
+        out.extend_from_slice(&self.seq_num.to_be_bytes());
+        match self.time_bounds {
+            Some(time_bounds) => {
+                out.extend_from_slice(&1i32.to_be_bytes());
+                out.extend_from_slice(&time_bounds.min_time.to_be_bytes());
+                out.extend_from_slice(&time_bounds.max_time.to_be_bytes());
+            }
+            None => out.extend_from_slice(&0i32.to_be_bytes()),
+        }
+        match &self.memo {
+            Memo::None => out.extend_from_slice(&0i32.to_be_bytes()),
+            Memo::Text(text) => {
+                out.extend_from_slice(&1i32.to_be_bytes());
+                write_opaque(&mut out, text.as_bytes());
+            }
+            Memo::Id(id) => {
+                out.extend_from_slice(&2i32.to_be_bytes());
+                out.extend_from_slice(&id.to_be_bytes());
+            }
+            Memo::Hash(hash) => {
+                out.extend_from_slice(&3i32.to_be_bytes());
+                out.extend_from_slice(hash);
+            }
+            Memo::Return(hash) => {
+                out.extend_from_slice(&4i32.to_be_bytes());
+                out.extend_from_slice(hash);
+            }
+        }
+
+        out.extend_from_slice(&(self.operations.len() as u32).to_be_bytes());
+        for operation in &self.operations {
+            match &operation.source_account {
+                Some(source) => {
+                    out.extend_from_slice(&1u32.to_be_bytes());
+                    write_account(&mut out, source);
+                }
+                None => out.extend_from_slice(&0u32.to_be_bytes()),
+            }
+            let OperationBody::ManageData { name, value } = &operation.body;
+            out.extend_from_slice(&OPERATION_MANAGE_DATA.to_be_bytes());
+            write_opaque(&mut out, name.as_bytes());
+            match value {
+                Some(value) => {
+                    out.extend_from_slice(&1u32.to_be_bytes());
+                    write_opaque(&mut out, value);
+                }
+                None => out.extend_from_slice(&0u32.to_be_bytes()),
+            }
+        }
+
+        // ext v0
+        out.extend_from_slice(&0i32.to_be_bytes());
+        out
+    }
+}
+
+/// `MuxedAccount` of type `KEY_TYPE_ED25519`
+fn write_account(out: &mut Vec<u8>, account: &[u8; 32]) {
+    out.extend_from_slice(&0i32.to_be_bytes());
+    out.extend_from_slice(account);
+}
+
+/// Variable-length opaque, padded to a multiple of 4
+fn write_opaque(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+    out.resize(out.len() + (4 - bytes.len() % 4) % 4, 0);
+}
+
+impl TransactionEnvelope {
+    /// Unsigned envelope of a transaction
+    pub fn new(transaction: Transaction) -> Self {
+        TransactionEnvelope {
+            transaction_xdr: transaction.to_xdr(),
+            transaction,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Encodes the envelope XDR
+    pub fn to_xdr(&self) -> Vec<u8> {
+        let mut out = ENVELOPE_TYPE_TX.to_be_bytes().to_vec();
+        out.extend_from_slice(&self.transaction_xdr);
+        out.extend_from_slice(&(self.signatures.len() as u32).to_be_bytes());
+        for signature in &self.signatures {
+            out.extend_from_slice(&signature.hint);
+            write_opaque(&mut out, &signature.signature);
+        }
+        out
+    }
+
+    /// Encodes the envelope XDR as base64, as SEP-10 servers and wallets exchange it
+    pub fn to_base64(&self) -> String {
+        BASE64.encode(self.to_xdr())
+    }
+
+    /// Decodes a base64 envelope XDR
+    pub fn from_base64(envelope: &str) -> anyhow::Result<Self> {
+        let bytes = BASE64
+            .decode(envelope.trim())
+            .map_err(|e| anyhow::anyhow!("Transaction envelope is not valid base64: {}", e))?;
+        Self::from_xdr(&bytes)
+    }
+
+    /// Decodes envelope XDR bytes
+    pub fn from_xdr(data: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = Reader { data };
+
+        let envelope_type = reader.i32()?;
+        if envelope_type != ENVELOPE_TYPE_TX {
+            return Err(anyhow::anyhow!(
+                "Unsupported envelope type {} (expected a v1 transaction envelope)",
+                envelope_type
+            ));
+        }
+
+        let before = reader.data;
+        let transaction = read_transaction(&mut reader)?;
+        let transaction_xdr = before[..before.len() - reader.data.len()].to_vec();
+
+        let signatures = reader.array(20, |reader| {
+            Ok(DecoratedSignature {
+                hint: reader.take(4)?.try_into()?,
+                signature: reader.var_opaque(64)?,
+            })
+        })?;
+        reader.finish()?;
+
+        Ok(TransactionEnvelope {
+            transaction,
+            transaction_xdr,
+            signatures,
+        })
+    }
+
+    /// Hash signed by every signer:
+    /// `SHA-256(SHA-256(passphrase) || ENVELOPE_TYPE_TX || transaction)`
+    pub fn signature_base_hash(&self, network_passphrase: &str) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(Sha256::digest(network_passphrase.as_bytes()))
+            .chain_update(ENVELOPE_TYPE_TX.to_be_bytes())
+            .chain_update(&self.transaction_xdr)
+            .finalize()
+            .into()
+    }
+}
+
+fn read_transaction(reader: &mut Reader) -> anyhow::Result<Transaction> {
+    let source_account = reader.muxed_account()?;
+    let fee = reader.u32()?;
+    let seq_num = reader.i64()?;
+
+    let time_bounds = match reader.i32()? {
+        // PRECOND_NONE
+        0 => None,
+        // PRECOND_TIME
+        1 => Some(reader.time_bounds()?),
+        // PRECOND_V2
+        2 => {
+            let time_bounds = reader.optional(Reader::time_bounds)?;
+            reader.optional(|reader| Ok((reader.u32()?, reader.u32()?)))?; // ledgerBounds
+            reader.optional(Reader::i64)?; // minSeqNum
+            reader.u64()?; // minSeqAge
+            reader.u32()?; // minSeqLedgerGap
+            reader.array(2, Reader::signer_key)?; // extraSigners
+            time_bounds
+        }
+        precondition => {
+            return Err(anyhow::anyhow!(
+                "Unknown transaction precondition type {}",
+                precondition
+            ))
+        }
+    };
+
+    let memo = match reader.i32()? {
+        0 => Memo::None,
+        1 => Memo::Text(reader.string(28)?),
+        2 => Memo::Id(reader.u64()?),
+        3 => Memo::Hash(reader.take(32)?.try_into()?),
+        4 => Memo::Return(reader.take(32)?.try_into()?),
+        memo => return Err(anyhow::anyhow!("Unknown memo type {}", memo)),
+    };
+
+    let operations = reader.array(100, |reader| {
+        let source_account = reader.optional(Reader::muxed_account)?;
+        let body = match reader.i32()? {
+            OPERATION_MANAGE_DATA => OperationBody::ManageData {
+                name: reader.string(64)?,
+                value: reader.optional(|reader| reader.var_opaque(64))?,
+            },
+            operation => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported operation type {} (challenges only contain ManageData)",
+                    operation
+                ))
+            }
+        };
+        Ok(Operation {
+            source_account,
+            body,
+        })
+    })?;
+
+    // Transaction ext: only v0 (no Soroban data)
+    if reader.i32()? != 0 {
+        return Err(anyhow::anyhow!("Unsupported transaction extension"));
+    }
+
+    Ok(Transaction {
+        source_account,
+        fee,
+        seq_num,
+        time_bounds,
+        memo,
+        operations,
+    })
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(anyhow::anyhow!("Unexpected end of XDR data"));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn i32(&mut self) -> anyhow::Result<i32> {
+        Ok(i32::from_be_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn i64(&mut self) -> anyhow::Result<i64> {
+        Ok(i64::from_be_bytes(self.take(8)?.try_into()?))
+    }
+
+    /// Variable-length opaque of at most `max` bytes, padded to a multiple of 4
+    fn var_opaque(&mut self, max: usize) -> anyhow::Result<Vec<u8>> {
+        let len = self.u32()? as usize;
+        if len > max {
+            return Err(anyhow::anyhow!(
+                "XDR opaque of {} bytes exceeds {}",
+                len,
+                max
+            ));
+        }
+        let bytes = self.take(len)?.to_vec();
+        if self.take((4 - len % 4) % 4)?.iter().any(|b| *b != 0) {
+            return Err(anyhow::anyhow!("Non-zero XDR padding"));
+        }
+        Ok(bytes)
+    }
+
+    fn string(&mut self, max: usize) -> anyhow::Result<String> {
+        String::from_utf8(self.var_opaque(max)?)
+            .map_err(|e| anyhow::anyhow!("Invalid UTF-8 in XDR string: {}", e))
+    }
+
+    /// Optional value: a boolean flag followed by the value when set
+    fn optional<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Option<T>> {
+        match self.u32()? {
+            0 => Ok(None),
+            1 => read(self).map(Some),
+            flag => Err(anyhow::anyhow!("Invalid XDR optional flag {}", flag)),
+        }
+    }
+
+    /// Array of at most `max` elements
+    fn array<T>(
+        &mut self,
+        max: usize,
+        mut read: impl FnMut(&mut Self) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Vec<T>> {
+        let len = self.u32()? as usize;
+        if len > max {
+            return Err(anyhow::anyhow!(
+                "XDR array of {} elements exceeds {}",
+                len,
+                max
+            ));
+        }
+        (0..len).map(|_| read(self)).collect()
+    }
+
+    fn time_bounds(&mut self) -> anyhow::Result<TimeBounds> {
+        Ok(TimeBounds {
+            min_time: self.u64()?,
+            max_time: self.u64()?,
+        })
+    }
+
+    /// `MuxedAccount`: an ed25519 key, optionally with a 64-bit ID
+    fn muxed_account(&mut self) -> anyhow::Result<[u8; 32]> {
+        match self.i32()? {
+            // KEY_TYPE_ED25519
+            0 => Ok(self.take(32)?.try_into()?),
+            // KEY_TYPE_MUXED_ED25519
+            0x100 => {
+                self.u64()?;
+                Ok(self.take(32)?.try_into()?)
+            }
+            key_type => Err(anyhow::anyhow!("Unknown account key type {}", key_type)),
+        }
+    }
+
+    fn signer_key(&mut self) -> anyhow::Result<()> {
+        match self.i32()? {
+            // ed25519, pre-auth transaction, hash(x)
+            0..=2 => self.take(32).map(|_| ()),
+            // ed25519 signed payload
+            3 => {
+                self.take(32)?;
+                self.var_opaque(64).map(|_| ())
+            }
+            key_type => Err(anyhow::anyhow!("Unknown signer key type {}", key_type)),
+        }
+    }
+
+    fn finish(&self) -> anyhow::Result<()> {
+        if !self.data.is_empty() {
+            return Err(anyhow::anyhow!("{} trailing bytes", self.data.len()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge_transaction() -> Transaction {
+        Transaction {
+            source_account: [0x11; 32],
+            fee: 200,
+            seq_num: 0,
+            time_bounds: Some(TimeBounds {
+                min_time: 1_760_706_960,
+                max_time: 1_760_707_860,
+            }),
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: Some([0x22; 32]),
+                body: OperationBody::ManageData {
+                    name: "example.com auth".to_string(),
+                    value: Some(vec![b'n'; 64]),
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn test_envelope_roundtrip() {
+        let mut envelope = TransactionEnvelope::new(challenge_transaction());
+        envelope.transaction.memo = Memo::Id(7);
+        envelope.transaction_xdr = envelope.transaction.to_xdr();
+        envelope.signatures.push(DecoratedSignature {
+            hint: [0x11; 4],
+            signature: vec![0x33; 64],
+        });
+
+        let decoded = TransactionEnvelope::from_base64(&envelope.to_base64()).unwrap();
+        assert_eq!(decoded, envelope);
+        // Source account: KEY_TYPE_ED25519 then the key
+        assert_eq!(
+            decoded.transaction_xdr[..8],
+            [0, 0, 0, 0, 0x11, 0x11, 0x11, 0x11]
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_envelopes() {
+        let envelope = TransactionEnvelope::new(challenge_transaction()).to_xdr();
+
+        // Trailing bytes
+        let trailing = [envelope.as_slice(), &[0, 0, 0, 0]].concat();
+        assert!(TransactionEnvelope::from_xdr(&trailing).is_err());
+        // Fee bump envelope (ENVELOPE_TYPE_TX_FEE_BUMP)
+        let mut fee_bump = envelope.clone();
+        fee_bump[3] = 5;
+        assert!(TransactionEnvelope::from_xdr(&fee_bump).is_err());
+        // Truncated
+        assert!(TransactionEnvelope::from_xdr(&envelope[..envelope.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use blake2::{Blake2b512, Digest};
+use std::fmt;
+use std::str::FromStr;
+
+/// Prefix hashed with the payload to compute SS58 checksums
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+
+/// Largest network prefix SS58 can encode (14 bits)
+pub const MAX_SS58_PREFIX: u16 = 0x3fff;
+
+/// Well-known SS58 network prefixes
+pub mod ss58_prefix {
+    /// Polkadot relay chain (`1...`)
+    pub const POLKADOT: u16 = 0;
+    /// Kusama relay chain (`C...` to `J...`)
+    pub const KUSAMA: u16 = 2;
+    /// Generic Substrate chains (`5...`)
+    pub const SUBSTRATE: u16 = 42;
+}
+
+/// SS58 address of a 32-byte Substrate account ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ss58Address {
+    /// Network prefix (0 Polkadot, 2 Kusama, 42 generic Substrate, ...)
+    pub prefix: u16,
+    /// Account ID: the sr25519/ed25519 public key, or the BLAKE2b-256 of an ecdsa key
+    pub account: [u8; 32],
+}
+
+impl Ss58Address {
+    pub fn new(prefix: u16, account: [u8; 32]) -> Self {
+        Ss58Address { prefix, account }
+    }
+
+    /// Prefix bytes: one byte below 64, two bytes up to 16383
+    fn prefix_bytes(&self) -> Vec<u8> {
+        let prefix = self.prefix;
+        if prefix < 64 {
+            vec![prefix as u8]
+        } else {
+            vec![
+                ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+                ((prefix >> 8) as u8) | (((prefix & 0b11) as u8) << 6),
+            ]
+        }
+    }
+}
+
+/// First two bytes of `BLAKE2b-512("SS58PRE" || prefix || account)`
+fn ss58_checksum(payload: &[u8]) -> [u8; 2] {
+    let hash = Blake2b512::new()
+        .chain_update(SS58_CHECKSUM_PREFIX)
+        .chain_update(payload)
+        .finalize();
+    [hash[0], hash[1]]
+}
+
+impl FromStr for Ss58Address {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = bs58::decode(s)
+            .into_vec()
+            .map_err(|e| anyhow::anyhow!("Invalid SS58 address '{}': {}", s, e))?;
+
+        let (prefix, prefix_len) = match data.first() {
+            Some(&first) if first < 64 => (first as u16, 1),
+            Some(&first) if first < 128 && data.len() > 1 => {
+                let lower = (first << 2) | (data[1] >> 6);
+                let upper = data[1] & 0b0011_1111;
+                (lower as u16 | ((upper as u16) << 8), 2)
+            }
+            _ => return Err(anyhow::anyhow!("Invalid SS58 address '{}': bad prefix", s)),
+        };
+
+        // prefix || 32-byte account || 2-byte checksum
+        if data.len() != prefix_len + 32 + 2 {
+            return Err(anyhow::anyhow!(
+                "Invalid SS58 address '{}': expected a 32-byte account, got {} bytes",
+                s,
+                data.len().saturating_sub(prefix_len + 2)
+            ));
+        }
+        let (payload, checksum) = data.split_at(prefix_len + 32);
+        if ss58_checksum(payload) != checksum {
+            return Err(anyhow::anyhow!(
+                "Invalid SS58 address '{}': bad checksum",
+                s
+            ));
+        }
+
+        Ok(Ss58Address {
+            prefix,
+            account: payload[prefix_len..].try_into()?,
+        })
+    }
+}
+
+impl fmt::Display for Ss58Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut payload = self.prefix_bytes();
+        payload.extend_from_slice(&self.account);
+        let checksum = ss58_checksum(&payload);
+        payload.extend_from_slice(&checksum);
+        write!(f, "{}", bs58::encode(payload).into_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// sr25519 public key of the `//Alice` development account
+    const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    fn alice(prefix: u16) -> String {
+        let account = hex::decode(ALICE).unwrap().try_into().unwrap();
+        Ss58Address::new(prefix, account).to_string()
+    }
+
+    #[test]
+    fn test_alice_addresses() {
+        assert_eq!(
+            alice(ss58_prefix::SUBSTRATE),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert_eq!(
+            alice(ss58_prefix::POLKADOT),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(
+            alice(ss58_prefix::KUSAMA),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"
+        );
+    }
+
+    #[test]
+    fn test_parse_roundtrip_with_two_byte_prefix() {
+        for prefix in [0, 2, 42, 63, 64, 1284, MAX_SS58_PREFIX] {
+            let address: Ss58Address = alice(prefix).parse().unwrap();
+            assert_eq!(address.prefix, prefix);
+            assert_eq!(hex::encode(address.account), ALICE);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_addresses() {
+        // Bad checksum
+        assert!("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"
+            .parse::<Ss58Address>()
+            .is_err());
+        // Solana address: base58 without prefix or checksum
+        assert!("11111111111111111111111111111111"
+            .parse::<Ss58Address>()
+            .is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use super::address::Ss58Address;
+use crate::caip::parse_sign_in_challenge;
+use crate::types::{MessageEncoding, VerificationResult};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ed25519_dalek::VerifyingKey;
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, SECP256K1,
+};
+
+/// Signing context of Substrate sr25519 signatures
+pub const SR25519_SIGNING_CONTEXT: &[u8] = b"substrate";
+
+/// Wrapper polkadot.js `signRaw` adds around `bytes` payloads
+pub const BYTES_WRAPPER_START: &[u8] = b"<Bytes>";
+pub const BYTES_WRAPPER_END: &[u8] = b"</Bytes>";
+
+/// Key type of the signing account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubstrateKeyType {
+    /// Detect from the signature (sr25519 and ed25519 for 64 bytes, ecdsa for 65)
+    #[default]
+    Auto,
+    /// Schnorrkel sr25519 (polkadot.js and Talisman default)
+    Sr25519,
+    Ed25519,
+    /// secp256k1 over BLAKE2b-256 of the message; the account is BLAKE2b-256 of the
+    /// compressed key
+    Ecdsa,
+}
+
+/// BLAKE2b-256, used for ecdsa message hashes and account IDs
+pub fn blake2b_256(data: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(data).into()
+}
+
+/// Wraps a message in `<Bytes>...</Bytes>` like polkadot.js `signRaw` does
+pub fn wrap_bytes(message: &[u8]) -> Vec<u8> {
+    [BYTES_WRAPPER_START, message, BYTES_WRAPPER_END].concat()
+}
+
+/// Messages the wallet may have signed: the `<Bytes>`-wrapped form first, then the raw
+/// bytes. `0x`-prefixed challenges are hex-decoded (`signRaw` takes hex data).
+fn message_candidates(challenge: &str) -> anyhow::Result<Vec<Vec<u8>>> {
+    let (bytes, _) = MessageEncoding::Auto.decode(challenge)?;
+    if bytes.starts_with(BYTES_WRAPPER_START) && bytes.ends_with(BYTES_WRAPPER_END) {
+        return Ok(vec![bytes]);
+    }
+    Ok(vec![wrap_bytes(&bytes), bytes])
+}
+
+/// Decodes a hex signature into the (key type, signature bytes) pairs it can be
+///
+/// 64 bytes are sr25519 or ed25519 and 65 bytes a recoverable ecdsa signature. A
+/// `MultiSignature` encoding prefixes the type: `0x00` ed25519, `0x01` sr25519,
+/// `0x02` ecdsa.
+pub fn signature_candidates(
+    signature_hex: &str,
+    key_type: SubstrateKeyType,
+) -> anyhow::Result<Vec<(SubstrateKeyType, Vec<u8>)>> {
+    let bytes = hex::decode(signature_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Signature is not valid hex: {}", e))?;
+
+    let mut candidates = match bytes.len() {
+        64 => vec![
+            (SubstrateKeyType::Sr25519, bytes.clone()),
+            (SubstrateKeyType::Ed25519, bytes),
+        ],
+        65 => {
+            let mut candidates = vec![(SubstrateKeyType::Ecdsa, bytes.clone())];
+            match bytes[0] {
+                0x00 => candidates.push((SubstrateKeyType::Ed25519, bytes[1..].to_vec())),
+                0x01 => candidates.push((SubstrateKeyType::Sr25519, bytes[1..].to_vec())),
+                _ => {}
+            }
+            candidates
+        }
+        66 if bytes[0] == 0x02 => vec![(SubstrateKeyType::Ecdsa, bytes[1..].to_vec())],
+        len => {
+            return Err(anyhow::anyhow!(
+                "Invalid signature length: expected 64 or 65 bytes (plus type byte), got {}",
+                len
+            ))
+        }
+    };
+
+    if key_type != SubstrateKeyType::Auto {
+        candidates.retain(|(candidate, _)| *candidate == key_type);
+        if candidates.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Signature is not a {:?} signature",
+                key_type
+            ));
+        }
+    }
+    Ok(candidates)
+}
+
+/// Verifies a Substrate sr25519 signature (`"substrate"` signing context)
+pub fn verify_sr25519(account: &[u8; 32], message: &[u8], signature: &[u8]) -> bool {
+    let (Ok(public_key), Ok(signature)) = (
+        schnorrkel::PublicKey::from_bytes(account),
+        schnorrkel::Signature::from_bytes(signature),
+    ) else {
+        return false;
+    };
+    public_key
+        .verify_simple(SR25519_SIGNING_CONTEXT, message, &signature)
+        .is_ok()
+}
+
+/// Verifies an ed25519 signature by the account key, rejecting small-order keys and
+/// non-canonical signatures (`verify_strict`)
+pub fn verify_ed25519(account: &[u8; 32], message: &[u8], signature: &[u8]) -> bool {
+    let (Ok(verifying_key), Ok(signature)) = (
+        VerifyingKey::from_bytes(account),
+        ed25519_dalek::Signature::from_slice(signature),
+    ) else {
+        return false;
+    };
+    verifying_key.verify_strict(message, &signature).is_ok()
+}
+
+/// Recovers the account ID (BLAKE2b-256 of the compressed key) of a 65-byte
+/// `r || s || v` ecdsa signature over `BLAKE2b-256(message)`
+pub fn recover_ecdsa_account(message: &[u8], signature: &[u8]) -> Option<[u8; 32]> {
+    let recovery_id = match signature.get(64)? {
+        v @ 0..=1 => *v,
+        v @ 27..=28 => v - 27,
+        _ => return None,
+    };
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32).ok()?;
+    let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id).ok()?;
+
+    let public_key = SECP256K1
+        .recover_ecdsa(&Message::from_digest(blake2b_256(message)), &signature)
+        .ok()?;
+    Some(blake2b_256(&public_key.serialize()))
+}
+
+/// Verifies a polkadot.js `signRaw` signature
+///
+/// Each signature interpretation (see [`signature_candidates`]) is checked against the
+/// `<Bytes>`-wrapped and the raw challenge. sr25519 and ed25519 accounts are the public
+/// key itself; ecdsa signers are recovered and compared with the account.
+/// When `ss58_prefix` is set, the address must use that network prefix.
+pub fn verify_substrate_signature(
+    signature_hex: &str,
+    challenge: &str,
+    expected_address: &str,
+    ss58_prefix: Option<u16>,
+    key_type: SubstrateKeyType,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Substrate verification starting");
+    log::debug!("Signature: {}", signature_hex);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let expected: Ss58Address = expected_address.parse()?;
+    if let Some(prefix) = ss58_prefix.filter(|prefix| *prefix != expected.prefix) {
+        return Err(anyhow::anyhow!(
+            "SS58 address prefix {} does not match the expected network prefix {}",
+            expected.prefix,
+            prefix
+        ));
+    }
+
+    let candidates = signature_candidates(signature_hex, key_type)?;
+    let messages = message_candidates(challenge)?;
+
+    let mut matched = None;
+    let mut recovered_account = None;
+    'search: for (candidate, signature) in &candidates {
+        for (index, message) in messages.iter().enumerate() {
+            let valid = match candidate {
+                SubstrateKeyType::Sr25519 => verify_sr25519(&expected.account, message, signature),
+                SubstrateKeyType::Ed25519 => verify_ed25519(&expected.account, message, signature),
+                _ => match recover_ecdsa_account(message, signature) {
+                    Some(account) if account == expected.account => true,
+                    Some(account) => {
+                        recovered_account.get_or_insert(account);
+                        false
+                    }
+                    None => false,
+                },
+            };
+
+            if valid {
+                matched = Some((*candidate, index == 0 && messages.len() > 1));
+                break 'search;
+            }
+        }
+    }
+
+    // sr25519/ed25519 accounts are the key itself; an ecdsa signer is recovered
+    let ecdsa_only = candidates
+        .iter()
+        .all(|(candidate, _)| *candidate == SubstrateKeyType::Ecdsa);
+    let (address_valid, derived_account) = match (matched, recovered_account) {
+        (Some(_), _) => (true, expected.account),
+        (None, Some(account)) if ecdsa_only => (false, account),
+        (None, _) => (!ecdsa_only, expected.account),
+    };
+    let derived_address = Ss58Address::new(expected.prefix, derived_account).to_string();
+
+    let signature_valid = match matched {
+        Some((candidate, wrapped)) => {
+            log::info!(
+                "Substrate {:?} signature verification successful ({} message)!",
+                candidate,
+                if wrapped { "<Bytes>-wrapped" } else { "raw" }
+            );
+            true
+        }
+        None => {
+            log::warn!("Substrate signature verification failed");
+            false
+        }
+    };
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address,
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_candidates() {
+        assert_eq!(
+            message_candidates("hello").unwrap(),
+            vec![b"<Bytes>hello</Bytes>".to_vec(), b"hello".to_vec()]
+        );
+        // polkadot.js passes signRaw data as hex
+        assert_eq!(
+            message_candidates("0x68656c6c6f").unwrap(),
+            vec![b"<Bytes>hello</Bytes>".to_vec(), b"hello".to_vec()]
+        );
+        assert_eq!(
+            message_candidates("<Bytes>hello</Bytes>").unwrap(),
+            vec![b"<Bytes>hello</Bytes>".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_signature_candidates() {
+        let raw = hex::encode([0x11; 64]);
+        let kinds = |signature: &str, key_type| {
+            signature_candidates(signature, key_type)
+                .unwrap()
+                .into_iter()
+                .map(|(kind, bytes)| (kind, bytes.len()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds(&raw, SubstrateKeyType::Auto),
+            vec![
+                (SubstrateKeyType::Sr25519, 64),
+                (SubstrateKeyType::Ed25519, 64)
+            ]
+        );
+        // MultiSignature::Sr25519
+        assert_eq!(
+            kinds(&format!("0x01{}", raw), SubstrateKeyType::Sr25519),
+            vec![(SubstrateKeyType::Sr25519, 64)]
+        );
+        // MultiSignature::Ecdsa
+        assert_eq!(
+            kinds(&format!("02{}1b", raw), SubstrateKeyType::Auto),
+            vec![(SubstrateKeyType::Ecdsa, 65)]
+        );
+        assert!(signature_candidates(&raw, SubstrateKeyType::Ecdsa).is_err());
+        assert!(signature_candidates(&hex::encode([0x11; 63]), SubstrateKeyType::Auto).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+//! Substrate Provider
+//!
+//! This module provides signature verification for Polkadot and other Substrate
+//! chains using the extension `signRaw` API.
+//!
+//! # Supported Wallets
+//!
+//! - polkadot.js extension
+//! - Talisman
+//! - SubWallet, Nova and other `signRaw` wallets
+//!
+//! # Signature Format
+//!
+//! - Message: `<Bytes>` + challenge + `</Bytes>` (what polkadot.js signs for `bytes`
+//!   payloads), or the bare challenge
+//! - sr25519: 64-byte schnorrkel signature with the `"substrate"` signing context
+//! - ed25519: 64-byte signature over the message
+//! - ecdsa: 65-byte `r || s || v` over `BLAKE2b-256(message)`
+//! - Signatures may carry a `MultiSignature` type byte (`0x00` ed25519, `0x01` sr25519,
+//!   `0x02` ecdsa)
+//! - Address: SS58 (`base58(prefix || account || checksum)`) where the account is the
+//!   sr25519/ed25519 public key or the BLAKE2b-256 of the compressed ecdsa key
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{get_wallet_provider, WalletType, VerificationInput};
+//!
+//! let input = VerificationInput {
+//!     signature_data: "0x2aeaa98e26062cf65161c68c5cb7aa31...".to_string(),
+//!     expected_address: "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = get_wallet_provider(WalletType::Substrate);
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod address;
+pub mod core;
+mod provider;
+
+pub use address::{ss58_prefix, Ss58Address};
+pub use core::SubstrateKeyType;
+pub use provider::SubstrateProvider;
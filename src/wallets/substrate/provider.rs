@@ -0,0 +1,90 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::address::Ss58Address;
+use super::core::{verify_substrate_signature, SubstrateKeyType};
+use crate::types::VerificationResult;
+
+/// Provider for Polkadot and Substrate wallets signing with `signRaw`
+///
+/// Verifies sr25519, ed25519 and ecdsa signatures against SS58 addresses, over the
+/// challenge with or without the `<Bytes>...</Bytes>` wrapper polkadot.js adds. Any
+/// network prefix is accepted unless one is set with [`SubstrateProvider::with_ss58_prefix`].
+#[derive(Debug, Clone, Default)]
+pub struct SubstrateProvider {
+    ss58_prefix: Option<u16>,
+    key_type: SubstrateKeyType,
+}
+
+impl SubstrateProvider {
+    /// Creates a provider accepting any network prefix and key type
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires addresses of the given SS58 network (0 Polkadot, 2 Kusama, 42 Substrate)
+    pub fn with_ss58_prefix(mut self, ss58_prefix: u16) -> Self {
+        self.ss58_prefix = Some(ss58_prefix);
+        self
+    }
+
+    /// Restricts verification to one key type instead of detecting it
+    pub fn with_key_type(mut self, key_type: SubstrateKeyType) -> Self {
+        self.key_type = key_type;
+        self
+    }
+}
+
+impl WalletProvider for SubstrateProvider {
+    fn name(&self) -> &str {
+        "Substrate"
+    }
+
+    fn description(&self) -> &str {
+        "Polkadot/Substrate - sr25519, ed25519 and ecdsa signRaw verification (polkadot.js, Talisman)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Substrate: challenge is required for verification"
+            ));
+        }
+
+        // Validate that signature_data is hex
+        if hex::decode(input.signature_data.trim_start_matches("0x")).is_err() {
+            return Err(anyhow::anyhow!("Substrate: signature must be hex"));
+        }
+
+        // Validate that address is SS58 on the configured network
+        let address: Ss58Address = input
+            .expected_address
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Substrate: {}", e))?;
+        if let Some(prefix) = self.ss58_prefix.filter(|prefix| *prefix != address.prefix) {
+            return Err(anyhow::anyhow!(
+                "Substrate: address has SS58 prefix {}, expected {}",
+                address.prefix,
+                prefix
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Substrate requires challenge"))?;
+
+        verify_substrate_signature(
+            &input.signature_data,
+            challenge,
+            &input.expected_address,
+            self.ss58_prefix,
+            self.key_type,
+        )
+    }
+}
@@ -0,0 +1,173 @@
+use base64::{
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE as BASE64_URL},
+    Engine as _,
+};
+use std::fmt;
+use std::str::FromStr;
+
+/// Tag of bounceable user-friendly addresses (`EQ...`)
+const BOUNCEABLE_TAG: u8 = 0x11;
+
+/// Tag of non-bounceable user-friendly addresses (`UQ...`)
+const NON_BOUNCEABLE_TAG: u8 = 0x51;
+
+/// Flag added to the tag of testnet-only addresses
+const TESTNET_FLAG: u8 = 0x80;
+
+/// TON account address: workchain and 256-bit account hash
+///
+/// Parses the raw form (`0:<hex>`) and the 48-char user-friendly form (base64 or
+/// base64url, e.g. `EQ...` / `UQ...`); displays the raw form, which is what TON
+/// Connect reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TonAddress {
+    pub workchain: i32,
+    pub hash: [u8; 32],
+}
+
+impl TonAddress {
+    /// User-friendly form: base64url of `tag || workchain || hash || CRC16`
+    pub fn to_friendly(&self, bounceable: bool, testnet: bool) -> String {
+        let mut tag = if bounceable {
+            BOUNCEABLE_TAG
+        } else {
+            NON_BOUNCEABLE_TAG
+        };
+        if testnet {
+            tag |= TESTNET_FLAG;
+        }
+
+        let mut bytes = vec![tag, self.workchain as i8 as u8];
+        bytes.extend_from_slice(&self.hash);
+        let checksum = crc16(&bytes);
+        bytes.extend_from_slice(&checksum.to_be_bytes());
+        BASE64_URL.encode(bytes)
+    }
+
+    fn from_raw(address: &str, workchain: &str, hash: &str) -> anyhow::Result<Self> {
+        let workchain = workchain
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid TON address workchain in '{}'", address))?;
+        let mut account = [0u8; 32];
+        hex::decode_to_slice(hash, &mut account)
+            .map_err(|_| anyhow::anyhow!("Invalid TON address hash in '{}'", address))?;
+        Ok(TonAddress {
+            workchain,
+            hash: account,
+        })
+    }
+
+    fn from_friendly(address: &str) -> anyhow::Result<Self> {
+        let bytes = BASE64_URL
+            .decode(address)
+            .or_else(|_| BASE64.decode(address))
+            .map_err(|_| anyhow::anyhow!("Invalid TON address '{}'", address))?;
+        if bytes.len() != 36 {
+            return Err(anyhow::anyhow!("Invalid TON address '{}'", address));
+        }
+        if bytes[0] & !TESTNET_FLAG != BOUNCEABLE_TAG
+            && bytes[0] & !TESTNET_FLAG != NON_BOUNCEABLE_TAG
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid TON address tag 0x{:02x} in '{}'",
+                bytes[0],
+                address
+            ));
+        }
+        if crc16(&bytes[..34]).to_be_bytes() != bytes[34..] {
+            return Err(anyhow::anyhow!(
+                "Invalid TON address checksum in '{}'",
+                address
+            ));
+        }
+
+        Ok(TonAddress {
+            workchain: bytes[1] as i8 as i32,
+            hash: bytes[2..34].try_into()?,
+        })
+    }
+}
+
+impl FromStr for TonAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(address: &str) -> anyhow::Result<Self> {
+        match address.split_once(':') {
+            Some((workchain, hash)) => Self::from_raw(address, workchain, hash),
+            None => Self::from_friendly(address),
+        }
+    }
+}
+
+impl fmt::Display for TonAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.workchain, hex::encode(self.hash))
+    }
+}
+
+/// CRC-16/XMODEM, the user-friendly address checksum
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_friendly_addresses() {
+        let zero = TonAddress {
+            workchain: 0,
+            hash: [0u8; 32],
+        };
+        assert_eq!(
+            zero.to_friendly(true, false),
+            "EQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAM9c"
+        );
+        assert_eq!(
+            zero.to_friendly(false, false),
+            "UQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAJKZ"
+        );
+
+        let masterchain: TonAddress = "Ef8AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAADAU"
+            .parse()
+            .unwrap();
+        assert_eq!(masterchain.workchain, -1);
+        assert_eq!(masterchain.to_string(), format!("-1:{}", "0".repeat(64)));
+
+        assert!("EQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAM9d"
+            .parse::<TonAddress>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_raw_addresses() {
+        let raw = format!("0:{}", "ab".repeat(32));
+        let address: TonAddress = raw.parse().unwrap();
+        assert_eq!(address.hash, [0xab; 32]);
+        assert_eq!(address.to_string(), raw);
+        assert_eq!(
+            address
+                .to_friendly(true, false)
+                .parse::<TonAddress>()
+                .unwrap(),
+            address
+        );
+
+        assert!("0:abcd".parse::<TonAddress>().is_err());
+        assert!(format!("x:{}", "ab".repeat(32))
+            .parse::<TonAddress>()
+            .is_err());
+    }
+}
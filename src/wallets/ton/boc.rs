@@ -0,0 +1,523 @@
+//! Minimal TVM cells and bag-of-cells (BoC) serialization
+//!
+//! Supports ordinary (level 0) cells, which is all wallet state inits use. Exotic
+//! cells (pruned branches, library references, Merkle proofs) are rejected.
+
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Magic prefix of serialized bags of cells
+pub const BOC_MAGIC: [u8; 4] = [0xb5, 0xee, 0x9c, 0x72];
+
+/// Maximum number of data bits in a cell
+pub const MAX_CELL_BITS: usize = 1023;
+
+/// Maximum number of references of a cell
+pub const MAX_CELL_REFS: usize = 4;
+
+/// Maximum number of cells accepted in a bag of cells
+const MAX_BOC_CELLS: usize = 4096;
+
+/// Ordinary cell: up to 1023 data bits and up to 4 references
+///
+/// The representation hash and depth are computed on construction, so shared
+/// subtrees are only hashed once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    data: Vec<u8>,
+    bit_len: usize,
+    refs: Vec<Arc<Cell>>,
+    hash: [u8; 32],
+    depth: u16,
+}
+
+impl Cell {
+    /// Creates a cell from its data bits (`bit_len` bits of `data`, big-endian) and
+    /// references
+    pub fn new(data: &[u8], bit_len: usize, refs: Vec<Arc<Cell>>) -> anyhow::Result<Self> {
+        if bit_len > MAX_CELL_BITS || data.len() * 8 < bit_len {
+            return Err(anyhow::anyhow!(
+                "Invalid cell data length: {} bits",
+                bit_len
+            ));
+        }
+        if refs.len() > MAX_CELL_REFS {
+            return Err(anyhow::anyhow!("Cell has {} references", refs.len()));
+        }
+
+        // Keep only the data bits, zeroing the rest of the last byte
+        let mut data = data[..bit_len.div_ceil(8)].to_vec();
+        if bit_len % 8 != 0 {
+            if let Some(last) = data.last_mut() {
+                *last &= 0xffu8 << (8 - bit_len % 8);
+            }
+        }
+
+        let depth = refs.iter().map(|cell| cell.depth + 1).max().unwrap_or(0);
+        let mut cell = Cell {
+            data,
+            bit_len,
+            refs,
+            hash: [0u8; 32],
+            depth,
+        };
+        cell.hash = cell.representation_hash();
+        Ok(cell)
+    }
+
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    pub fn refs(&self) -> &[Arc<Cell>] {
+        &self.refs
+    }
+
+    /// Representation hash, which is also the account address of a state init
+    pub fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// Reads the cell from the start
+    pub fn parser(&self) -> CellParser<'_> {
+        CellParser {
+            cell: self,
+            bit: 0,
+            next_ref: 0,
+        }
+    }
+
+    /// Descriptor bytes: reference count, then data length in half-bytes
+    fn descriptors(&self) -> [u8; 2] {
+        [
+            self.refs.len() as u8,
+            (self.bit_len / 8 + self.bit_len.div_ceil(8)) as u8,
+        ]
+    }
+
+    /// Data with the completion tag (a 1 bit) appended to an incomplete last byte
+    fn padded_data(&self) -> Vec<u8> {
+        let mut data = self.data.clone();
+        if self.bit_len % 8 != 0 {
+            if let Some(last) = data.last_mut() {
+                *last |= 0x80 >> (self.bit_len % 8);
+            }
+        }
+        data
+    }
+
+    /// `SHA-256(descriptors || padded data || ref depths || ref hashes)`
+    fn representation_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.descriptors());
+        hasher.update(self.padded_data());
+        for cell in &self.refs {
+            hasher.update(cell.depth.to_be_bytes());
+        }
+        for cell in &self.refs {
+            hasher.update(cell.hash);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Parses a bag of cells with a single root
+    pub fn from_boc(boc: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = ByteReader { data: boc, pos: 0 };
+        if reader.take(4)? != BOC_MAGIC {
+            return Err(anyhow::anyhow!("Not a bag of cells (bad magic)"));
+        }
+
+        let flags = reader.byte()?;
+        let has_index = flags & 0x80 != 0;
+        let has_crc = flags & 0x40 != 0;
+        let ref_size = (flags & 0x07) as usize;
+        let offset_size = reader.byte()? as usize;
+        if !(1..=4).contains(&ref_size) || !(1..=8).contains(&offset_size) {
+            return Err(anyhow::anyhow!("Invalid bag of cells header"));
+        }
+
+        let cell_count = reader.uint(ref_size)? as usize;
+        let root_count = reader.uint(ref_size)?;
+        let absent_count = reader.uint(ref_size)?;
+        let cells_size = reader.uint(offset_size)? as usize;
+        if root_count != 1 || absent_count != 0 {
+            return Err(anyhow::anyhow!(
+                "Bag of cells must have exactly one root and no absent cells"
+            ));
+        }
+        if cell_count == 0 || cell_count > MAX_BOC_CELLS {
+            return Err(anyhow::anyhow!(
+                "Invalid bag of cells size: {} cells",
+                cell_count
+            ));
+        }
+        let root_index = reader.uint(ref_size)? as usize;
+        if has_index {
+            reader.take(cell_count * offset_size)?;
+        }
+
+        if cells_size > reader.data.len() - reader.pos {
+            return Err(anyhow::anyhow!(
+                "Bag of cells size {} exceeds its data",
+                cells_size
+            ));
+        }
+        let cells_end = reader
+            .pos
+            .checked_add(cells_size)
+            .ok_or_else(|| anyhow::anyhow!("Invalid bag of cells size"))?;
+        let mut raw_cells = Vec::with_capacity(cell_count);
+        for index in 0..cell_count {
+            let d1 = reader.byte()?;
+            let d2 = reader.byte()?;
+            if d1 & 0x08 != 0 || d1 >> 5 != 0 {
+                return Err(anyhow::anyhow!("Exotic cells are not supported"));
+            }
+            let ref_count = (d1 & 0x07) as usize;
+            if ref_count > MAX_CELL_REFS {
+                return Err(anyhow::anyhow!(
+                    "Cell {} has {} references",
+                    index,
+                    ref_count
+                ));
+            }
+
+            let data = reader.take(d2.div_ceil(2) as usize)?;
+            let bit_len = if d2 % 2 == 0 {
+                data.len() * 8
+            } else {
+                // The last byte ends with the completion tag
+                let last = *data.last().unwrap_or(&0);
+                if last == 0 {
+                    return Err(anyhow::anyhow!(
+                        "Cell {} is missing its completion tag",
+                        index
+                    ));
+                }
+                data.len() * 8 - last.trailing_zeros() as usize - 1
+            };
+
+            let mut refs = Vec::with_capacity(ref_count);
+            for _ in 0..ref_count {
+                let child = reader.uint(ref_size)? as usize;
+                // References always point forward, which rules out cycles
+                if child <= index || child >= cell_count {
+                    return Err(anyhow::anyhow!("Cell {} has an invalid reference", index));
+                }
+                refs.push(child);
+            }
+            raw_cells.push((data, bit_len, refs));
+        }
+        if reader.pos != cells_end {
+            return Err(anyhow::anyhow!(
+                "Bag of cells size does not match its cells"
+            ));
+        }
+
+        if has_crc {
+            let expected = u32::from_le_bytes(reader.take(4)?.try_into()?);
+            if crc32c(&boc[..reader.pos - 4]) != expected {
+                return Err(anyhow::anyhow!("Bag of cells checksum mismatch"));
+            }
+        }
+        if reader.pos != boc.len() {
+            return Err(anyhow::anyhow!("Trailing bytes after bag of cells"));
+        }
+
+        // Build children first
+        let mut cells: Vec<Option<Arc<Cell>>> = vec![None; cell_count];
+        for (index, (data, bit_len, refs)) in raw_cells.into_iter().enumerate().rev() {
+            let refs = refs
+                .iter()
+                .map(|&child| cells[child].clone())
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| anyhow::anyhow!("Cell {} has an invalid reference", index))?;
+            cells[index] = Some(Arc::new(Cell::new(data, bit_len, refs)?));
+        }
+
+        cells
+            .get(root_index)
+            .cloned()
+            .flatten()
+            .map(Arc::unwrap_or_clone)
+            .ok_or_else(|| anyhow::anyhow!("Invalid bag of cells root"))
+    }
+
+    /// Serializes the cell as a bag of cells (no index, no checksum)
+    pub fn to_boc(&self) -> Vec<u8> {
+        // Parents before children; identical cells are stored once
+        fn visit<'a>(cell: &'a Cell, order: &mut Vec<&'a Cell>, seen: &mut HashSet<[u8; 32]>) {
+            if seen.insert(cell.hash) {
+                for child in &cell.refs {
+                    visit(child, order, seen);
+                }
+                order.push(cell);
+            }
+        }
+        let mut order = Vec::new();
+        visit(self, &mut order, &mut HashSet::new());
+        order.reverse();
+        let indexes: HashMap<[u8; 32], usize> = order
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (cell.hash, index))
+            .collect();
+
+        let ref_size = byte_len(order.len() as u64);
+        let mut cells = Vec::new();
+        for cell in &order {
+            cells.extend_from_slice(&cell.descriptors());
+            cells.extend_from_slice(&cell.padded_data());
+            for child in &cell.refs {
+                write_uint(&mut cells, indexes[&child.hash] as u64, ref_size);
+            }
+        }
+        let offset_size = byte_len(cells.len() as u64);
+
+        let mut boc = BOC_MAGIC.to_vec();
+        boc.push(ref_size as u8);
+        boc.push(offset_size as u8);
+        write_uint(&mut boc, order.len() as u64, ref_size); // cells
+        write_uint(&mut boc, 1, ref_size); // roots
+        write_uint(&mut boc, 0, ref_size); // absent
+        write_uint(&mut boc, cells.len() as u64, offset_size);
+        write_uint(&mut boc, 0, ref_size); // root index
+        boc.extend_from_slice(&cells);
+        boc
+    }
+}
+
+/// Sequential reader over the bits and references of a cell
+pub struct CellParser<'a> {
+    cell: &'a Cell,
+    bit: usize,
+    next_ref: usize,
+}
+
+impl CellParser<'_> {
+    /// Number of data bits left
+    pub fn remaining_bits(&self) -> usize {
+        self.cell.bit_len - self.bit
+    }
+
+    pub fn load_bit(&mut self) -> anyhow::Result<bool> {
+        if self.remaining_bits() == 0 {
+            return Err(anyhow::anyhow!("Cell underflow"));
+        }
+        let bit = self.cell.data[self.bit / 8] & (0x80 >> (self.bit % 8)) != 0;
+        self.bit += 1;
+        Ok(bit)
+    }
+
+    /// Loads a big-endian unsigned integer of up to 64 bits
+    pub fn load_uint(&mut self, bits: usize) -> anyhow::Result<u64> {
+        if bits > 64 {
+            return Err(anyhow::anyhow!("Cannot load a {}-bit integer", bits));
+        }
+        (0..bits).try_fold(0u64, |value, _| Ok(value << 1 | self.load_bit()? as u64))
+    }
+
+    /// Loads `N` whole bytes
+    pub fn load_bytes<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        let mut bytes = [0u8; N];
+        for byte in bytes.iter_mut() {
+            *byte = self.load_uint(8)? as u8;
+        }
+        Ok(bytes)
+    }
+
+    pub fn load_ref(&mut self) -> anyhow::Result<&Arc<Cell>> {
+        let cell = self
+            .cell
+            .refs
+            .get(self.next_ref)
+            .ok_or_else(|| anyhow::anyhow!("Cell has no more references"))?;
+        self.next_ref += 1;
+        Ok(cell)
+    }
+}
+
+/// Incremental builder of a cell
+#[derive(Debug, Clone, Default)]
+pub struct CellBuilder {
+    data: Vec<u8>,
+    bit_len: usize,
+    refs: Vec<Arc<Cell>>,
+}
+
+impl CellBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn store_bit(mut self, bit: bool) -> Self {
+        if self.bit_len % 8 == 0 {
+            self.data.push(0);
+        }
+        if bit {
+            if let Some(last) = self.data.last_mut() {
+                *last |= 0x80 >> (self.bit_len % 8);
+            }
+        }
+        self.bit_len += 1;
+        self
+    }
+
+    /// Stores the low `bits` bits of `value`, big-endian
+    pub fn store_uint(self, value: u64, bits: usize) -> Self {
+        (0..bits).rev().fold(self, |builder, bit| {
+            builder.store_bit(value >> bit & 1 == 1)
+        })
+    }
+
+    pub fn store_bytes(self, bytes: &[u8]) -> Self {
+        bytes
+            .iter()
+            .fold(self, |builder, &byte| builder.store_uint(byte as u64, 8))
+    }
+
+    pub fn store_ref(mut self, cell: Cell) -> Self {
+        self.refs.push(Arc::new(cell));
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<Cell> {
+        Cell::new(&self.data, self.bit_len, self.refs)
+    }
+}
+
+/// Big-endian reader over the bytes of a bag of cells
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        let bytes = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| self.data.get(self.pos..end))
+            .ok_or_else(|| anyhow::anyhow!("Unexpected end of bag of cells"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> anyhow::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn uint(&mut self, size: usize) -> anyhow::Result<u64> {
+        Ok(self
+            .take(size)?
+            .iter()
+            .fold(0u64, |value, &byte| value << 8 | byte as u64))
+    }
+}
+
+/// Number of bytes needed to store `value` (at least 1)
+fn byte_len(value: u64) -> usize {
+    (64 - value.leading_zeros() as usize).div_ceil(8).max(1)
+}
+
+fn write_uint(out: &mut Vec<u8>, value: u64, size: usize) {
+    out.extend_from_slice(&value.to_be_bytes()[8 - size..]);
+}
+
+/// CRC-32C (Castagnoli), the bag of cells checksum
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0x82f6_3b78
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// State init with a dummy code cell and a wallet v3/v4 style data cell
+    fn state_init() -> Cell {
+        let code = CellBuilder::new()
+            .store_uint(0xdeadbeef, 32)
+            .build()
+            .unwrap();
+        let data = CellBuilder::new()
+            .store_uint(0, 32)
+            .store_uint(698983191, 32)
+            .store_bytes(&[0x42; 32])
+            .store_bit(false)
+            .build()
+            .unwrap();
+        CellBuilder::new()
+            .store_uint(0b00110, 5)
+            .store_ref(code)
+            .store_ref(data)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cell_hashes() {
+        assert_eq!(
+            hex::encode(CellBuilder::new().build().unwrap().hash()),
+            "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7"
+        );
+        assert_eq!(
+            hex::encode(state_init().hash()),
+            "2a044c4e06803de3a983ff2a9e88090d9bd379980fea7bd6e3dc07a5e8fb76a8"
+        );
+    }
+
+    #[test]
+    fn test_boc_roundtrip() {
+        let cell = state_init();
+        let boc = cell.to_boc();
+        assert_eq!(Cell::from_boc(&boc).unwrap(), cell);
+
+        let mut parser = cell.parser();
+        assert_eq!(parser.load_uint(5).unwrap(), 0b00110);
+        assert_eq!(parser.load_ref().unwrap().bit_len(), 32);
+        let data = parser.load_ref().unwrap();
+        let mut data = data.parser();
+        assert_eq!(data.load_uint(64).unwrap(), 698983191);
+        assert_eq!(data.load_bytes::<32>().unwrap(), [0x42; 32]);
+        assert!(!data.load_bit().unwrap());
+        assert!(data.load_bit().is_err());
+    }
+
+    #[test]
+    fn test_boc_checksum_and_errors() {
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+
+        // Same bag of cells with the checksum flag and a CRC-32C trailer
+        let mut boc = state_init().to_boc();
+        boc[4] |= 0x40;
+        let crc = crc32c(&boc);
+        boc.extend_from_slice(&crc.to_le_bytes());
+        assert_eq!(Cell::from_boc(&boc).unwrap(), state_init());
+
+        let last = boc.len() - 1;
+        boc[last] ^= 0x01;
+        assert!(Cell::from_boc(&boc).is_err());
+        assert!(Cell::from_boc(&[0xb5, 0xee, 0x9c]).is_err());
+        assert!(Cell::from_boc(b"not a bag of cells").is_err());
+    }
+
+    #[test]
+    fn test_boc_oversized_cells_size() {
+        // One cell whose declared cells size is u64::MAX, which must not overflow
+        let mut boc = hex::decode("b5ee9c72010801010000ffffffffffffffff00").unwrap();
+        boc.extend_from_slice(&[0x00; 8]);
+        assert!(Cell::from_boc(&boc).is_err());
+    }
+}
@@ -0,0 +1,439 @@
+use super::address::TonAddress;
+use super::boc::Cell;
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Prefix of the `ton_proof` message
+pub const TON_PROOF_PREFIX: &[u8] = b"ton-proof-item-v2/";
+
+/// Prefix of the signed hash, after `0xffff`
+pub const TON_CONNECT_PREFIX: &[u8] = b"ton-connect";
+
+/// Default maximum age of a proof
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Tolerated clock skew for proofs timestamped in the future
+const MAX_CLOCK_SKEW: u64 = 60;
+
+/// Bit offsets of the public key in the data cell of standard wallet contracts
+///
+/// v1/v2: `seqno:32`; v3/v4: `seqno:32 subwallet:32`; v5: `signature_allowed:1
+/// seqno:32 wallet_id:32`; highload v2: `subwallet:32 last_cleaned:64`; highload v3:
+/// key first.
+const WALLET_KEY_OFFSETS: [usize; 5] = [32, 64, 65, 96, 0];
+
+/// `ton_proof` item returned by the wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TonProof {
+    /// Unix time the proof was signed at
+    pub timestamp: u64,
+    /// App domain the wallet connected to
+    pub domain: String,
+    /// Payload the app asked to sign (the challenge)
+    pub payload: String,
+    pub signature: [u8; 64],
+}
+
+impl TonProof {
+    /// Message of the proof for `address`:
+    /// `prefix || workchain (u32 BE) || hash || domain length (u32 LE) || domain ||
+    /// timestamp (u64 LE) || payload`
+    pub fn message(&self, address: &TonAddress) -> Vec<u8> {
+        let mut message = TON_PROOF_PREFIX.to_vec();
+        message.extend_from_slice(&address.workchain.to_be_bytes());
+        message.extend_from_slice(&address.hash);
+        message.extend_from_slice(&(self.domain.len() as u32).to_le_bytes());
+        message.extend_from_slice(self.domain.as_bytes());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message.extend_from_slice(self.payload.as_bytes());
+        message
+    }
+
+    /// Hash the wallet signs: `SHA-256(0xffff || "ton-connect" || SHA-256(message))`
+    pub fn signing_hash(&self, address: &TonAddress) -> [u8; 32] {
+        Sha256::new()
+            .chain_update([0xff, 0xff])
+            .chain_update(TON_CONNECT_PREFIX)
+            .chain_update(Sha256::digest(self.message(address)))
+            .finalize()
+            .into()
+    }
+}
+
+/// TON Connect login data: the `ton_proof` item along with the account it proves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TonConnectProof {
+    pub proof: TonProof,
+    /// Raw account address
+    pub address: Option<String>,
+    /// Hex public key reported by the wallet
+    pub public_key: Option<String>,
+    /// Base64 bag of cells of the wallet's state init
+    pub state_init: Option<String>,
+}
+
+impl TonConnectProof {
+    /// Parses the JSON a TON Connect app sends to its backend, either
+    /// `{"address","public_key","proof":{...}}` or the bare `proof` object
+    ///
+    /// Both `state_init` and the SDK's `walletStateInit`/`publicKey` names are accepted.
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        let json: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| anyhow::anyhow!("Failed to parse ton_proof JSON: {}", e))?;
+        let proof = if json["proof"].is_object() {
+            &json["proof"]
+        } else {
+            &json
+        };
+        let field = |value: &serde_json::Value, names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| value[name].as_str().map(str::to_string))
+        };
+
+        let domain = proof["domain"]["value"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("ton_proof is missing 'domain.value'"))?;
+        if let Some(length) = proof["domain"]["lengthBytes"].as_u64() {
+            if length != domain.len() as u64 {
+                return Err(anyhow::anyhow!(
+                    "ton_proof domain length {} does not match '{}'",
+                    length,
+                    domain
+                ));
+            }
+        }
+        let signature = proof["signature"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("ton_proof is missing 'signature'"))?;
+
+        Ok(TonConnectProof {
+            proof: TonProof {
+                timestamp: proof["timestamp"]
+                    .as_u64()
+                    .ok_or_else(|| anyhow::anyhow!("ton_proof is missing 'timestamp'"))?,
+                domain: domain.to_string(),
+                payload: field(proof, &["payload"])
+                    .ok_or_else(|| anyhow::anyhow!("ton_proof is missing 'payload'"))?,
+                signature: decode_signature(signature)?,
+            },
+            address: field(&json, &["address"]),
+            public_key: field(&json, &["public_key", "publicKey"]),
+            state_init: field(proof, &["state_init", "stateInit"])
+                .or_else(|| field(&json, &["walletStateInit", "state_init"])),
+        })
+    }
+}
+
+/// Decodes a 64-byte signature given as base64 (TON Connect default) or hex
+fn decode_signature(signature: &str) -> anyhow::Result<[u8; 64]> {
+    let bytes = match hex::decode(signature.trim_start_matches("0x")) {
+        Ok(bytes) if bytes.len() == 64 => bytes,
+        _ => BASE64
+            .decode(signature)
+            .map_err(|e| anyhow::anyhow!("Signature is not valid base64 or hex: {}", e))?,
+    };
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid ed25519 signature length: expected 64 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Parses a hex ed25519 public key (with or without `0x`)
+pub fn parse_ton_public_key(public_key: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = hex::decode(public_key.trim().trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid TON public key hex: {}", e))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow::anyhow!(
+            "Invalid TON public key: expected 32 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+/// Rules the proof's domain and timestamp are checked against
+#[derive(Debug, Clone, Default)]
+pub struct TonProofPolicy {
+    /// Domains the proof may be made for (at least one is required)
+    pub allowed_domains: Vec<String>,
+    /// Maximum age of the proof (defaults to [`DEFAULT_MAX_AGE`])
+    pub max_age: Option<Duration>,
+    /// Time to check the timestamp against (defaults to now)
+    pub time: Option<SystemTime>,
+}
+
+impl TonProofPolicy {
+    /// Validates the proof's domain and timestamp
+    pub fn validate(&self, proof: &TonProof) -> anyhow::Result<()> {
+        if self.allowed_domains.is_empty() {
+            return Err(anyhow::anyhow!("No allowed domain is configured"));
+        }
+        if !self.allowed_domains.contains(&proof.domain) {
+            return Err(anyhow::anyhow!(
+                "Proof domain '{}' is not allowed",
+                proof.domain
+            ));
+        }
+
+        let now = self
+            .time
+            .unwrap_or_else(SystemTime::now)
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| anyhow::anyhow!("Invalid verification time: {}", e))?
+            .as_secs();
+        let max_age = self.max_age.unwrap_or(DEFAULT_MAX_AGE).as_secs();
+        if proof.timestamp.saturating_add(max_age) < now {
+            return Err(anyhow::anyhow!(
+                "Proof timestamp {} is older than {} seconds",
+                proof.timestamp,
+                max_age
+            ));
+        }
+        if proof.timestamp > now.saturating_add(MAX_CLOCK_SKEW) {
+            return Err(anyhow::anyhow!(
+                "Proof timestamp {} is in the future, now is {}",
+                proof.timestamp,
+                now
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Wallet state init: its hash (the account hash of the address it deploys to) and the
+/// public keys its data cell may hold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletStateInit {
+    pub hash: [u8; 32],
+    pub public_keys: Vec<[u8; 32]>,
+}
+
+impl WalletStateInit {
+    /// Parses a base64 bag of cells holding a `StateInit`:
+    /// `split_depth:(Maybe (## 5)) special:(Maybe TickTock) code:(Maybe ^Cell)
+    /// data:(Maybe ^Cell) library:(Maybe ^Cell)`
+    pub fn parse(state_init: &str) -> anyhow::Result<Self> {
+        let boc = BASE64
+            .decode(state_init.trim())
+            .map_err(|e| anyhow::anyhow!("State init is not valid base64: {}", e))?;
+        let root = Cell::from_boc(&boc)?;
+
+        let mut parser = root.parser();
+        if parser.load_bit()? {
+            parser.load_uint(5)?; // split_depth
+        }
+        if parser.load_bit()? {
+            parser.load_uint(2)?; // tick, tock
+        }
+        if parser.load_bit()? {
+            parser.load_ref()?; // code
+        }
+        let data = if parser.load_bit()? {
+            parser.load_ref()?.clone()
+        } else {
+            return Err(anyhow::anyhow!("State init has no data cell"));
+        };
+
+        // Which layout applies depends on the wallet code; the signature decides
+        let public_keys = WALLET_KEY_OFFSETS
+            .iter()
+            .filter(|&&offset| offset + 256 <= data.bit_len())
+            .map(|&offset| {
+                let mut parser = data.parser();
+                parser.load_uint(offset % 64)?;
+                for _ in 0..offset / 64 {
+                    parser.load_uint(64)?;
+                }
+                parser.load_bytes::<32>()
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(WalletStateInit {
+            hash: root.hash(),
+            public_keys,
+        })
+    }
+}
+
+/// Verifies a TON Connect `ton_proof`
+///
+/// The public key comes from `state_init` when given: its hash must be the account
+/// hash of the expected address, and the signing key must be in its data cell (and be
+/// `public_key`, if also given). Without a state init, `public_key` is trusted as the
+/// account's key, so it must come from a trusted source such as the wallet's
+/// `get_public_key` get-method.
+///
+/// - `signature_valid`: the ed25519 signature over the proof hash is valid under
+///   `verify_strict`, which rejects small-order keys and non-canonical signatures
+/// - `challenge_valid`: the payload is the expected challenge, and the domain and
+///   timestamp satisfy `policy` (which must allow at least one domain)
+/// - `address_valid`: the key belongs to the expected address as described above
+pub fn verify_ton_proof(
+    proof: &TonProof,
+    expected_address: &str,
+    expected_challenge: &str,
+    state_init: Option<&str>,
+    public_key: Option<&str>,
+    policy: &TonProofPolicy,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("TON verification starting");
+    log::debug!("Domain: {}", proof.domain);
+    log::debug!("Timestamp: {}", proof.timestamp);
+    log::debug!("Payload: {}", proof.payload);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let address: TonAddress = expected_address.parse()?;
+    let public_key = public_key.map(parse_ton_public_key).transpose()?;
+
+    // 1. Signing keys of the address
+    let (mut address_valid, derived_address, mut keys) = match state_init {
+        Some(state_init) => {
+            let state_init = WalletStateInit::parse(state_init)?;
+            let derived = TonAddress {
+                workchain: address.workchain,
+                hash: state_init.hash,
+            };
+            (
+                derived == address,
+                derived.to_string(),
+                state_init.public_keys,
+            )
+        }
+        None => {
+            let public_key = public_key.ok_or_else(|| {
+                anyhow::anyhow!("TON: a state init or public key is required for verification")
+            })?;
+            (true, address.to_string(), vec![public_key])
+        }
+    };
+    if let Some(public_key) = public_key {
+        keys.retain(|key| *key == public_key);
+        if keys.is_empty() {
+            log::warn!("Public key is not in the wallet state init");
+            address_valid = false;
+        }
+    }
+    if !address_valid {
+        log::warn!("State init deploys to {}, not {}", derived_address, address);
+    }
+
+    // 2. Challenge, domain and timestamp
+    let mut challenge_valid = proof.payload == expected_challenge;
+    if !challenge_valid {
+        log::warn!("Proof payload does not match the challenge");
+    }
+    if let Err(e) = policy.validate(proof) {
+        log::warn!("ton_proof rejected: {}", e);
+        challenge_valid = false;
+    }
+
+    // 3. Signature by one of the keys
+    let hash = proof.signing_hash(&address);
+    let signature = Signature::from_bytes(&proof.signature);
+    let signature_valid = keys.iter().any(|key| {
+        VerifyingKey::from_bytes(key).is_ok_and(|key| key.verify_strict(&hash, &signature).is_ok())
+    });
+    if signature_valid {
+        log::info!("TON signature verification successful!");
+    } else {
+        log::warn!("TON signature verification failed");
+    }
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid,
+        signature_valid,
+        derived_address,
+        found_challenge: Some(proof.payload.clone()),
+        sign_in: parse_sign_in_challenge(&proof.payload),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallets::ton::boc::CellBuilder;
+
+    fn proof(timestamp: u64, domain: &str) -> TonProof {
+        TonProof {
+            timestamp,
+            domain: domain.to_string(),
+            payload: "abc".to_string(),
+            signature: [0u8; 64],
+        }
+    }
+
+    #[test]
+    fn test_proof_message() {
+        let address = TonAddress {
+            workchain: -1,
+            hash: [0x11; 32],
+        };
+        let message = proof(0x0102, "a.io").message(&address);
+
+        let expected = [
+            TON_PROOF_PREFIX,
+            &[0xff, 0xff, 0xff, 0xff],
+            &[0x11; 32],
+            &[4, 0, 0, 0],
+            b"a.io",
+            &[0x02, 0x01, 0, 0, 0, 0, 0, 0],
+            b"abc",
+        ]
+        .concat();
+        assert_eq!(message, expected);
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = TonProofPolicy {
+            allowed_domains: vec!["example.com".to_string()],
+            max_age: Some(Duration::from_secs(300)),
+            time: Some(UNIX_EPOCH + Duration::from_secs(10_000)),
+        };
+
+        assert!(policy.validate(&proof(9_800, "example.com")).is_ok());
+        assert!(policy.validate(&proof(10_030, "example.com")).is_ok());
+        assert!(policy.validate(&proof(9_600, "example.com")).is_err());
+        assert!(policy.validate(&proof(10_100, "example.com")).is_err());
+        assert!(policy.validate(&proof(9_800, "evil.com")).is_err());
+    }
+
+    #[test]
+    fn test_state_init_keys() {
+        let data = CellBuilder::new()
+            .store_uint(7, 32)
+            .store_uint(698983191, 32)
+            .store_bytes(&[0x42; 32])
+            .store_bit(false)
+            .build()
+            .unwrap();
+        let state_init = CellBuilder::new()
+            .store_uint(0b00110, 5)
+            .store_ref(CellBuilder::new().store_uint(1, 8).build().unwrap())
+            .store_ref(data)
+            .build()
+            .unwrap();
+
+        let parsed = WalletStateInit::parse(&BASE64.encode(state_init.to_boc())).unwrap();
+        assert_eq!(parsed.hash, state_init.hash());
+        assert!(parsed.public_keys.contains(&[0x42; 32]));
+
+        let no_data = CellBuilder::new()
+            .store_uint(0b00100, 5)
+            .store_ref(CellBuilder::new().build().unwrap())
+            .build()
+            .unwrap();
+        assert!(WalletStateInit::parse(&BASE64.encode(no_data.to_boc())).is_err());
+    }
+}
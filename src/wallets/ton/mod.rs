@@ -0,0 +1,49 @@
+//! TON Provider
+//!
+//! This module provides verification of TON Connect `ton_proof` items, which wallets
+//! sign when connecting to an app.
+//!
+//! # Supported Wallets
+//!
+//! - Tonkeeper
+//! - MyTonWallet, Tonhub and other TON Connect wallets
+//!
+//! # Signature Format
+//!
+//! - Message: `"ton-proof-item-v2/" || workchain (u32 BE) || address hash ||
+//!   domain length (u32 LE) || domain || timestamp (u64 LE) || payload`
+//! - Signature: ed25519 over `SHA-256(0xffff || "ton-connect" || SHA-256(message))`,
+//!   base64
+//! - Public key: read from the wallet's state init (a base64 bag of cells whose hash
+//!   is the address hash), or supplied by the caller
+//! - Address: raw (`0:<hex>`) or user-friendly (`EQ...`, `UQ...`)
+//!
+//! The domain and timestamp are checked against a [`TonProofPolicy`]; the payload is
+//! the challenge. Verification is offline: keys of deployed wallets without a state
+//! init must be looked up by the caller (`get_public_key`).
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{TonProvider, VerificationInput, WalletProvider};
+//!
+//! let input = VerificationInput {
+//!     signature_data: r#"{"address":"0:...","proof":{"timestamp":1760706960,"domain":{"lengthBytes":11,"value":"example.com"},"payload":"afba42ef","signature":"...","state_init":"te6cc..."}}"#.to_string(),
+//!     expected_address: "0:b2a1ecf5545e076cd36ae516ea7ebdf32aea008caa2b84af9866becb208895ad".to_string(),
+//!     challenge: Some("afba42ef".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = TonProvider::new().with_allowed_domain("example.com");
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod address;
+pub mod boc;
+pub mod core;
+mod provider;
+
+pub use address::TonAddress;
+pub use core::{TonProof, TonProofPolicy};
+pub use provider::TonProvider;
@@ -0,0 +1,120 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::address::TonAddress;
+use super::core::{verify_ton_proof, TonConnectProof, TonProofPolicy};
+use crate::types::VerificationResult;
+use std::time::{Duration, SystemTime};
+
+/// Provider for TON wallets (TON Connect `ton_proof`)
+///
+/// The signature data is the JSON the app receives from TON Connect: the `ton_proof`
+/// item, optionally wrapped with the account's `address`, `public_key` and state init.
+/// The key is taken from the wallet state init (checked against the address) or from
+/// `public_key`, which is then trusted; no network access is needed. The challenge is
+/// the proof payload and is required, as is at least one allowed app domain, so this
+/// provider is configured by the application and not available from the CLI registry.
+#[derive(Debug, Clone, Default)]
+pub struct TonProvider {
+    policy: TonProofPolicy,
+}
+
+impl TonProvider {
+    /// Creates a provider that accepts proofs up to 15 minutes old
+    ///
+    /// No domain is allowed until [`with_allowed_domain`](Self::with_allowed_domain) is
+    /// called; verification fails without one.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows proofs made for this app domain
+    pub fn with_allowed_domain(mut self, domain: &str) -> Self {
+        self.policy.allowed_domains.push(domain.to_string());
+        self
+    }
+
+    /// Sets the maximum age of a proof
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.policy.max_age = Some(max_age);
+        self
+    }
+
+    /// Checks the proof timestamp against `time` instead of the current time
+    pub fn with_time(mut self, time: SystemTime) -> Self {
+        self.policy.time = Some(time);
+        self
+    }
+}
+
+impl WalletProvider for TonProvider {
+    fn name(&self) -> &str {
+        "TON"
+    }
+
+    fn description(&self) -> &str {
+        "TON - TON Connect ton_proof verification (Tonkeeper, MyTonWallet, Tonhub)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that signature_data is the ton_proof JSON
+        if !input.signature_data.trim_start().starts_with('{') {
+            return Err(anyhow::anyhow!(
+                "TON: signature_data must be the ton_proof JSON"
+            ));
+        }
+
+        // Validate that address is a raw or user-friendly TON address
+        input
+            .expected_address
+            .parse::<TonAddress>()
+            .map_err(|e| anyhow::anyhow!("TON: {}", e))?;
+
+        // The payload binds the proof to this login, the domain to this app
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "TON: challenge (the proof payload) is required for verification"
+            ));
+        }
+        if self.policy.allowed_domains.is_empty() {
+            return Err(anyhow::anyhow!(
+                "TON: at least one allowed domain is required for verification"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let signed = TonConnectProof::parse(&input.signature_data)?;
+        let challenge = input.challenge.as_deref().unwrap_or_default();
+
+        // The wallet's own key claim only selects a key from its state init
+        let public_key = match &signed.state_init {
+            Some(_) => input.public_key.as_deref().or(signed.public_key.as_deref()),
+            None => input.public_key.as_deref(),
+        };
+        let mut result = verify_ton_proof(
+            &signed.proof,
+            &input.expected_address,
+            challenge,
+            signed.state_init.as_deref(),
+            public_key,
+            &self.policy,
+        )?;
+
+        // The wallet reports the account it connected with
+        if let Some(address) = signed.address {
+            if address.parse::<TonAddress>()? != input.expected_address.parse::<TonAddress>()? {
+                log::warn!(
+                    "Wallet connected as {}, not {}",
+                    address,
+                    input.expected_address
+                );
+                result.address_valid = false;
+            }
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,207 @@
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::address::evm_account_id;
+use crate::types::{SignatureEncoding, VerificationResult};
+use ethers_core::utils::keccak256;
+use hex::FromHex;
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, SECP256K1,
+};
+
+/// TIP-191 prefix, followed by the message length (v2) or a fixed `32` (v1)
+pub const TRON_MESSAGE_PREFIX: &str = "\x19TRON Signed Message:\n";
+
+/// Version byte of Tron mainnet addresses (`T...` in base58)
+pub const TRON_ADDRESS_PREFIX: u8 = 0x41;
+
+/// How TronLink produced the signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TronSigningScheme {
+    /// Try `signMessageV2` first, then the v1 format if the challenge is hex
+    #[default]
+    Auto,
+    /// `signMessageV2`: `keccak256(prefix || len(message) || message)`
+    SignMessageV2,
+    /// Legacy `sign`/`signMessage`: the challenge is hex and hashed as
+    /// `keccak256(prefix || "32" || bytes)`, whatever its length
+    SignMessage,
+}
+
+/// `signMessageV2` hash: `keccak256("\x19TRON Signed Message:\n" || len || message)`
+pub fn tron_message_hash_v2(message: &[u8]) -> [u8; 32] {
+    let mut data = format!("{}{}", TRON_MESSAGE_PREFIX, message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(data)
+}
+
+/// Legacy `signMessage` hash of a hex message: `keccak256("\x19TRON Signed Message:\n32" || bytes)`
+pub fn tron_message_hash_v1(message_hex: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = Vec::from_hex(message_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("v1 messages must be hex: {}", e))?;
+
+    let mut data = format!("{}32", TRON_MESSAGE_PREFIX).into_bytes();
+    data.extend_from_slice(&bytes);
+    Ok(keccak256(data))
+}
+
+/// Base58check Tron address of a secp256k1 key: `0x41 || last 20 bytes of keccak256(key)`
+pub fn tron_address(pubkey: &[u8]) -> anyhow::Result<String> {
+    let mut payload = vec![TRON_ADDRESS_PREFIX];
+    payload.extend_from_slice(&evm_account_id(pubkey)?);
+    Ok(bs58::encode(payload).with_check().into_string())
+}
+
+/// Decodes a base58check `T...` address into its 20-byte account ID
+pub fn decode_tron_address(address: &str) -> anyhow::Result<[u8; 20]> {
+    let payload = bs58::decode(address)
+        .with_check(Some(TRON_ADDRESS_PREFIX))
+        .into_vec()
+        .map_err(|e| anyhow::anyhow!("Invalid Tron address '{}': {}", address, e))?;
+
+    payload[1..].try_into().map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid Tron address '{}': expected 21-byte payload, got {}",
+            address,
+            payload.len()
+        )
+    })
+}
+
+/// Parses a 65-byte hex `r || s || v` signature with `v` = 27/28 or 0/1
+pub fn parse_tron_signature(
+    signature_hex: &str,
+) -> anyhow::Result<(RecoverableSignature, SignatureEncoding)> {
+    let bytes = <[u8; 65]>::from_hex(signature_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Signature must be 65 bytes of hex: {}", e))?;
+
+    let (recovery_id, encoding) = match bytes[64] {
+        v @ 0..=1 => (v, SignatureEncoding::RecoverableRaw),
+        v @ 27..=28 => (v - 27, SignatureEncoding::RecoverableLegacy),
+        v => return Err(anyhow::anyhow!("Invalid recovery value v = {}", v)),
+    };
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32)
+        .map_err(|e| anyhow::anyhow!("Invalid recovery ID: {}", e))?;
+    let signature = RecoverableSignature::from_compact(&bytes[..64], recovery_id)
+        .map_err(|e| anyhow::anyhow!("Invalid signature: {}", e))?;
+
+    Ok((signature, encoding))
+}
+
+/// Recovers the Tron address that signed `hash`
+fn recover_tron_address(signature: &RecoverableSignature, hash: [u8; 32]) -> Option<String> {
+    let public_key = SECP256K1
+        .recover_ecdsa(&Message::from_digest(hash), signature)
+        .ok()?;
+    tron_address(&public_key.serialize_uncompressed()).ok()
+}
+
+/// Verifies a TronLink (TIP-191) message signature
+///
+/// The signer is recovered from the signature for each hash the scheme allows and
+/// compared with the expected `T...` address.
+pub fn verify_tron_signature(
+    signature_hex: &str,
+    challenge: &str,
+    expected_address: &str,
+    scheme: TronSigningScheme,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Tron verification starting");
+    log::debug!("Signature: {}", signature_hex);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    decode_tron_address(expected_address)?;
+    let (signature, encoding) = parse_tron_signature(signature_hex)?;
+
+    let mut candidates = Vec::new();
+    if scheme != TronSigningScheme::SignMessage {
+        candidates.push((
+            TronSigningScheme::SignMessageV2,
+            tron_message_hash_v2(challenge.as_bytes()),
+        ));
+    }
+    match (scheme, tron_message_hash_v1(challenge)) {
+        (TronSigningScheme::SignMessage, hash) => {
+            candidates.push((TronSigningScheme::SignMessage, hash?))
+        }
+        (TronSigningScheme::Auto, Ok(hash)) => {
+            candidates.push((TronSigningScheme::SignMessage, hash))
+        }
+        _ => {}
+    }
+
+    let mut derived_address = String::new();
+    for (candidate, hash) in candidates {
+        let Some(recovered) = recover_tron_address(&signature, hash) else {
+            continue;
+        };
+        log::debug!("Recovered address ({:?}): {}", candidate, recovered);
+
+        if recovered == expected_address {
+            log::info!("Tron signature verification successful ({:?})", candidate);
+            derived_address = recovered;
+            break;
+        }
+        if derived_address.is_empty() {
+            derived_address = recovered;
+        }
+    }
+
+    let address_valid = derived_address == expected_address;
+    if !address_valid {
+        log::warn!("Signature verification failed!");
+        log::warn!("Recovered address does not match expected address");
+    }
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true,          // Challenge is what we signed
+        signature_valid: address_valid, // If address matches, signature is valid
+        derived_address,
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        signature_encoding: Some(encoding),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Uncompressed public key of the secp256k1 private key `1`
+    const GENERATOR_UNCOMPRESSED: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    #[test]
+    fn test_tron_address_of_generator_key() {
+        let pubkey = hex::decode(GENERATOR_UNCOMPRESSED).unwrap();
+        let address = tron_address(&pubkey).unwrap();
+
+        // Same account as EVM address 0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf
+        assert_eq!(address, "TMVQGm1qAQYVdetCeGRRkTWYYrLXuHK2HC");
+        assert_eq!(
+            hex::encode(decode_tron_address(&address).unwrap()),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_other_addresses() {
+        // Bitcoin P2PKH of the same key: valid base58check, wrong version byte
+        assert!(decode_tron_address("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH").is_err());
+        assert!(decode_tron_address("TMVQGm1qAQYVdetCeGRRkTWYYrLXuHK2HD").is_err());
+        assert!(decode_tron_address("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf").is_err());
+    }
+
+    #[test]
+    fn test_message_hash_prefixes() {
+        let mut v2 = b"\x19TRON Signed Message:\n5hello".to_vec();
+        assert_eq!(tron_message_hash_v2(b"hello"), keccak256(&v2));
+
+        v2 = b"\x19TRON Signed Message:\n32".to_vec();
+        v2.extend_from_slice(&[0xab; 4]);
+        assert_eq!(tron_message_hash_v1("0xabababab").unwrap(), keccak256(&v2));
+        assert!(tron_message_hash_v1("hello").is_err());
+    }
+}
@@ -0,0 +1,40 @@
+//! Tron Provider
+//!
+//! This module provides signature verification for Tron wallets using TIP-191
+//! message signing.
+//!
+//! # Supported Wallets
+//!
+//! - TronLink
+//! - Any wallet exposing TronWeb `signMessageV2` or the legacy `sign`
+//!
+//! # Signature Format
+//!
+//! - `signMessageV2`: `keccak256("\x19TRON Signed Message:\n" || len(message) || message)`
+//! - Legacy v1: the message is hex and hashed as
+//!   `keccak256("\x19TRON Signed Message:\n32" || bytes)`
+//! - Signature: 65 bytes hex `r || s || v` (`v` = 27/28 or 0/1), the key is recovered
+//! - Address: base58check of `0x41 || last 20 bytes of keccak256(public key)` (`T...`)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{get_wallet_provider, WalletType, VerificationInput};
+//!
+//! let input = VerificationInput {
+//!     signature_data: "0x5c8a3b4e...1b".to_string(),
+//!     expected_address: "TMVQGm1qAQYVdetCeGRRkTWYYrLXuHK2HC".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = get_wallet_provider(WalletType::Tron);
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod core;
+mod provider;
+
+pub use core::TronSigningScheme;
+pub use provider::TronProvider;
@@ -0,0 +1,76 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::core::{decode_tron_address, verify_tron_signature, TronSigningScheme};
+use crate::types::VerificationResult;
+
+/// Provider for Tron wallets (TronLink) signing with TIP-191
+///
+/// Signatures are 65-byte hex `r || s || v` and the signer is recovered from them, like
+/// EVM `personal_sign`, but with the `\x19TRON Signed Message:\n` prefix and base58check
+/// `T...` addresses. Both `signMessageV2` and the legacy v1 format (hex challenges only)
+/// are accepted by default; [`TronProvider::with_signing_scheme`] pins one of them.
+#[derive(Debug, Clone, Default)]
+pub struct TronProvider {
+    signing_scheme: TronSigningScheme,
+}
+
+impl TronProvider {
+    /// Creates a provider accepting both v1 and v2 signatures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the signing scheme (TronWeb function) the wallet used
+    pub fn with_signing_scheme(mut self, signing_scheme: TronSigningScheme) -> Self {
+        self.signing_scheme = signing_scheme;
+        self
+    }
+}
+
+impl WalletProvider for TronProvider {
+    fn name(&self) -> &str {
+        "Tron"
+    }
+
+    fn description(&self) -> &str {
+        "Tron - TIP-191 signMessage / signMessageV2 verification (TronLink)"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "Tron: challenge is required for verification"
+            ));
+        }
+
+        // Validate that signature_data is 65 bytes of hex (r + s + v)
+        let signature = input.signature_data.trim_start_matches("0x");
+        if signature.len() != 130 {
+            return Err(anyhow::anyhow!(
+                "Tron: signature must be 130 hex chars (65 bytes), got {}",
+                signature.len()
+            ));
+        }
+
+        // Validate that address is a base58check T-address
+        decode_tron_address(&input.expected_address).map_err(|e| anyhow::anyhow!("Tron: {}", e))?;
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Tron requires challenge"))?;
+
+        verify_tron_signature(
+            &input.signature_data,
+            challenge,
+            &input.expected_address,
+            self.signing_scheme,
+        )
+    }
+}
@@ -0,0 +1,143 @@
+use ethers_core::{types::Address, utils::to_checksum};
+use std::fmt;
+use std::str::FromStr;
+
+/// How strictly EIP-55 mixed-case checksums are enforced when parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumPolicy {
+    /// Accept any casing without checking the checksum
+    Ignore,
+    /// All-lowercase and all-uppercase are accepted; mixed case must be a valid checksum
+    #[default]
+    ValidateMixedCase,
+    /// Only the exact EIP-55 checksummed form is accepted
+    Require,
+}
+
+/// EVM address parsed from text, rendered in EIP-55 checksummed form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EvmAddress(Address);
+
+impl EvmAddress {
+    /// Parses a `0x`-prefixed (or bare) 40-hex-char address under the given checksum policy
+    pub fn parse(s: &str, policy: ChecksumPolicy) -> anyhow::Result<Self> {
+        let hex_part = s.strip_prefix("0x").unwrap_or(s);
+
+        if hex_part.len() != 40 || !hex_part.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow::anyhow!(
+                "Invalid EVM address '{}': expected 0x + 40 hex chars",
+                s
+            ));
+        }
+
+        let mut bytes = [0u8; 20];
+        hex::decode_to_slice(hex_part, &mut bytes)
+            .map_err(|e| anyhow::anyhow!("Invalid EVM address '{}': {}", s, e))?;
+        let address = EvmAddress(Address::from(bytes));
+
+        let has_lower = hex_part.chars().any(|c| c.is_ascii_lowercase());
+        let has_upper = hex_part.chars().any(|c| c.is_ascii_uppercase());
+        let checksum_ok = address.to_checksum()[2..] == *hex_part;
+
+        match policy {
+            ChecksumPolicy::Ignore => {}
+            ChecksumPolicy::ValidateMixedCase if has_lower && has_upper && !checksum_ok => {
+                return Err(anyhow::anyhow!(
+                    "Invalid EIP-55 checksum for address '{}' (expected {})",
+                    s,
+                    address
+                ));
+            }
+            ChecksumPolicy::Require if !checksum_ok => {
+                return Err(anyhow::anyhow!(
+                    "Address '{}' is not EIP-55 checksummed (expected {})",
+                    s,
+                    address
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(address)
+    }
+
+    /// EIP-55 checksummed form (`0x` + mixed-case hex)
+    pub fn to_checksum(&self) -> String {
+        to_checksum(&self.0, None)
+    }
+
+    /// Underlying 20-byte address
+    pub fn as_address(&self) -> Address {
+        self.0
+    }
+}
+
+impl From<Address> for EvmAddress {
+    fn from(address: Address) -> Self {
+        EvmAddress(address)
+    }
+}
+
+impl FromStr for EvmAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EvmAddress::parse(s, ChecksumPolicy::default())
+    }
+}
+
+impl fmt::Display for EvmAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_checksum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn test_checksum_rendering() {
+        let address =
+            EvmAddress::parse(&CHECKSUMMED.to_lowercase(), ChecksumPolicy::Ignore).unwrap();
+        assert_eq!(address.to_checksum(), CHECKSUMMED);
+        assert_eq!(address.to_string(), CHECKSUMMED);
+    }
+
+    #[test]
+    fn test_mixed_case_policy() {
+        // Single-case forms carry no checksum and are accepted
+        assert!(EvmAddress::parse(
+            &CHECKSUMMED.to_lowercase(),
+            ChecksumPolicy::ValidateMixedCase
+        )
+        .is_ok());
+        assert!(EvmAddress::parse(
+            &format!("0x{}", CHECKSUMMED[2..].to_uppercase()),
+            ChecksumPolicy::ValidateMixedCase
+        )
+        .is_ok());
+        assert!(EvmAddress::parse(CHECKSUMMED, ChecksumPolicy::ValidateMixedCase).is_ok());
+
+        // Flipping the case of one letter breaks the checksum
+        let typo = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+        assert!(EvmAddress::parse(typo, ChecksumPolicy::ValidateMixedCase).is_err());
+        assert!(EvmAddress::parse(typo, ChecksumPolicy::Ignore).is_ok());
+    }
+
+    #[test]
+    fn test_require_policy() {
+        assert!(EvmAddress::parse(CHECKSUMMED, ChecksumPolicy::Require).is_ok());
+        assert!(EvmAddress::parse(&CHECKSUMMED.to_lowercase(), ChecksumPolicy::Require).is_err());
+    }
+
+    #[test]
+    fn test_invalid_length_and_hex() {
+        assert!(EvmAddress::parse("0x1234", ChecksumPolicy::Ignore).is_err());
+        assert!(
+            EvmAddress::parse(&format!("0x{}", "z".repeat(40)), ChecksumPolicy::Ignore).is_err()
+        );
+    }
+}
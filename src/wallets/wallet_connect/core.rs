@@ -1,66 +1,153 @@
-use crate::types::VerificationResult;
-use ethers_core::{types::Signature, utils::hash_message};
+use super::address::{ChecksumPolicy, EvmAddress};
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::address::evm_account_id;
+use crate::crypto::derive_addresses;
+use crate::types::{MessageEncoding, SignatureEncoding, VerificationResult};
+use ethers_core::{
+    types::{Address, Signature, H256, U256},
+    utils::hash_message,
+};
 use hex::FromHex;
+use secp256k1::{ecdsa::Signature as EcdsaSignature, Message, PublicKey, Secp256k1};
 
 /// Verifies an EVM (Ethereum-style) signature
 ///
 /// This function verifies signatures from EVM-compatible wallets like WalletConnect, MetaMask, and Bifrost.
-/// It uses EIP-191 personal_sign message format.
+/// It uses EIP-191 personal_sign message format over the UTF-8 challenge text.
+/// A mixed-case `expected_address` with a wrong EIP-55 checksum yields
+/// `address_valid = false`.
 pub fn verify_evm_signature(
     signature_hex: &str,
     challenge: &str,
     expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    verify_evm_signature_with_encoding(
+        signature_hex,
+        challenge,
+        expected_address,
+        MessageEncoding::Utf8,
+    )
+}
+
+/// Verifies an EIP-191 personal_sign signature, decoding the challenge with `message_encoding`
+///
+/// Dapps that call `personal_sign` with a `0x`-prefixed hex string get the decoded bytes
+/// signed; use [`MessageEncoding::HexBytes`] or [`MessageEncoding::Auto`] for those.
+pub fn verify_evm_signature_with_encoding(
+    signature_hex: &str,
+    challenge: &str,
+    expected_address: &str,
+    message_encoding: MessageEncoding,
 ) -> anyhow::Result<VerificationResult> {
     log::debug!("EVM verification starting");
     log::debug!("Signature: {}", signature_hex);
     log::debug!("Challenge: {}", challenge);
     log::debug!("Expected Address: {}", expected_address);
 
+    let (signature, encoding) = parse_evm_signature(signature_hex)?;
+    let (message, message_encoding) = message_encoding.decode(challenge)?;
+    log::debug!("Message encoding: {:?}", message_encoding);
+
+    // Hash the message using EIP-191 format (what personal_sign does)
+    // This adds the prefix: "\x19Ethereum Signed Message:\n" + len(message) + message
+    let message_hash = hash_message(message);
+    log::debug!("Message hash: 0x{}", hex::encode(message_hash.as_bytes()));
+
+    let mut result = verify_evm_digest(
+        &signature,
+        encoding,
+        message_hash,
+        challenge,
+        expected_address,
+    )?;
+    result.message_encoding = Some(message_encoding);
+    Ok(result)
+}
+
+/// Parses an EVM signature, with or without `0x` prefix, normalizing `v` to 27/28
+///
+/// Accepted encodings:
+/// - 65 bytes `r || s || v` with `v` = 0/1 or 27/28
+/// - `r || s || v` with an EIP-155 `v` = chain_id * 2 + 35/36 (big-endian, up to 8 bytes)
+/// - 64 bytes EIP-2098 compact `r || yParityAndS`
+pub fn parse_evm_signature(signature_hex: &str) -> anyhow::Result<(Signature, SignatureEncoding)> {
     // Remove 0x prefix if present
     let signature_hex = signature_hex.trim_start_matches("0x");
-    let expected_address = expected_address.trim_start_matches("0x");
 
-    // Parse signature (65 bytes: r(32) + s(32) + v(1))
     let signature_bytes = Vec::from_hex(signature_hex)
         .map_err(|e| anyhow::anyhow!("Failed to decode signature hex: {}", e))?;
 
-    if signature_bytes.len() != 65 {
+    if !(64..=72).contains(&signature_bytes.len()) {
         return Err(anyhow::anyhow!(
-            "Invalid signature length: expected 65 bytes, got {}",
+            "Invalid signature length: expected 64 to 72 bytes (r || s plus a v of up to 8 bytes), got {}",
             signature_bytes.len()
         ));
     }
 
-    // Parse the signature using ethers
-    let signature: Signature = signature_bytes
-        .as_slice()
-        .try_into()
-        .map_err(|e| anyhow::anyhow!("Failed to parse signature: {:?}", e))?;
+    let r = U256::from_big_endian(&signature_bytes[..32]);
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature_bytes[32..64]);
+
+    let (y_parity, encoding) = if signature_bytes.len() == 64 {
+        // EIP-2098: the top bit of s carries the y parity
+        let y_parity = s_bytes[0] >> 7;
+        s_bytes[0] &= 0x7f;
+        (y_parity as u64, SignatureEncoding::Eip2098)
+    } else {
+        let v = signature_bytes[64..]
+            .iter()
+            .fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        match v {
+            0 | 1 => (v, SignatureEncoding::RecoverableRaw),
+            27 | 28 => (v - 27, SignatureEncoding::RecoverableLegacy),
+            v if v >= 35 => (
+                (v - 35) % 2,
+                SignatureEncoding::Eip155 {
+                    chain_id: (v - 35) / 2,
+                },
+            ),
+            _ => return Err(anyhow::anyhow!("Invalid signature v value: {}", v)),
+        }
+    };
+
+    let signature = Signature {
+        r,
+        s: U256::from_big_endian(&s_bytes),
+        v: 27 + y_parity,
+    };
 
     log::debug!("Signature parsed successfully");
-    log::debug!("  v: {}", signature.v);
+    log::debug!("  encoding: {:?}", encoding);
+    log::debug!("  v (normalized): {}", signature.v);
 
-    // Hash the message using EIP-191 format (what personal_sign does)
-    // This adds the prefix: "\x19Ethereum Signed Message:\n" + len(message) + message
-    let message_hash = hash_message(challenge.as_bytes());
-    log::debug!("Message hash: 0x{}", hex::encode(message_hash.as_bytes()));
+    Ok((signature, encoding))
+}
+
+/// Recovers the signer of `message_hash` and compares it with the expected address
+pub(crate) fn verify_evm_digest(
+    signature: &Signature,
+    encoding: SignatureEncoding,
+    message_hash: H256,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    let (expected_address, checksum_valid) = parse_expected_address(expected_address)?;
 
     // Recover the address from the signature
-    let recovered_address = signature
+    let recovered_address: EvmAddress = signature
         .recover(message_hash)
-        .map_err(|e| anyhow::anyhow!("Failed to recover address from signature: {}", e))?;
+        .map_err(|e| anyhow::anyhow!("Failed to recover address from signature: {}", e))?
+        .into();
 
-    let recovered_address_str = format!("{:?}", recovered_address).to_lowercase();
-    let expected_address_str = format!("0x{}", expected_address).to_lowercase();
+    log::debug!("Recovered address: {}", recovered_address);
+    log::debug!("Expected address:  {}", expected_address);
 
-    log::debug!("Recovered address: {}", recovered_address_str);
-    log::debug!("Expected address:  {}", expected_address_str);
-
-    let address_valid = recovered_address_str == expected_address_str;
+    let signature_valid = recovered_address == expected_address;
+    let address_valid = signature_valid && checksum_valid;
 
     if address_valid {
         log::info!("Signature verification successful!");
-        log::info!("Address matches: {}", recovered_address_str);
+        log::info!("Address matches: {}", recovered_address);
     } else {
         log::warn!("Signature verification failed!");
         log::warn!("Recovered address does not match expected address");
@@ -68,11 +155,111 @@ pub fn verify_evm_signature(
 
     Ok(VerificationResult {
         address_valid,
-        challenge_valid: true,          // Challenge is what we signed
-        signature_valid: address_valid, // If address matches, signature is valid
-        derived_address: recovered_address_str,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,       // If the recovered signer matches, the signature is valid
+        derived_address: recovered_address.to_checksum(),
         found_challenge: Some(challenge.to_string()),
-    })
+        sign_in: parse_sign_in_challenge(challenge),
+        signature_encoding: Some(encoding),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+/// Parses the expected address, returning whether its EIP-55 checksum holds
+///
+/// A mixed-case address with a wrong checksum is reported as an address mismatch rather
+/// than an error, so callers see it in the result like any other wrong address.
+fn parse_expected_address(expected_address: &str) -> anyhow::Result<(EvmAddress, bool)> {
+    let address = EvmAddress::parse(expected_address, ChecksumPolicy::Ignore)?;
+    let checksum_valid =
+        EvmAddress::parse(expected_address, ChecksumPolicy::ValidateMixedCase).is_ok();
+    if !checksum_valid {
+        log::warn!(
+            "Expected address {} has an invalid EIP-55 checksum (expected {})",
+            expected_address,
+            address
+        );
+    }
+    Ok((address, checksum_valid))
+}
+
+/// Parses a secp256k1 public key: 33-byte compressed, 65-byte uncompressed, or the
+/// 64-byte uncompressed form without `0x04` prefix that Ethereum tooling often uses
+pub fn parse_evm_public_key(public_key_hex: &str) -> anyhow::Result<PublicKey> {
+    let mut bytes = Vec::from_hex(public_key_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode public key hex: {}", e))?;
+    if bytes.len() == 64 {
+        bytes.insert(0, 0x04);
+    }
+
+    PublicKey::from_slice(&bytes)
+        .map_err(|e| anyhow::anyhow!("Invalid secp256k1 public key: {}", e))
+}
+
+/// EVM address of a public key: last 20 bytes of Keccak-256 over the uncompressed key
+fn address_from_public_key(public_key: &PublicKey) -> anyhow::Result<EvmAddress> {
+    Ok(Address::from(evm_account_id(&public_key.serialize())?).into())
+}
+
+/// Verifies `message_hash` directly against a supplied public key, skipping recovery
+///
+/// The key must derive to the expected address and the signature's `(r, s)` must verify
+/// under it.
+pub(crate) fn verify_evm_digest_with_public_key(
+    signature: &Signature,
+    encoding: SignatureEncoding,
+    message_hash: H256,
+    public_key: &PublicKey,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    let (expected_address, checksum_valid) = parse_expected_address(expected_address)?;
+    let key_address = address_from_public_key(public_key)?;
+    let address_valid = key_address == expected_address && checksum_valid;
+
+    log::debug!("Public key address: {}", key_address);
+    log::debug!("Expected address:   {}", expected_address);
+    if !address_valid {
+        log::warn!("Public key does not derive to the expected address");
+    }
+
+    let mut compact = [0u8; 64];
+    signature.r.to_big_endian(&mut compact[..32]);
+    signature.s.to_big_endian(&mut compact[32..]);
+
+    let signature_valid = match EcdsaSignature::from_compact(&compact) {
+        Ok(mut ecdsa_signature) => {
+            // Recovery accepts high-s signatures, so direct verification does too
+            ecdsa_signature.normalize_s();
+            let message = Message::from_digest(message_hash.0);
+            Secp256k1::verification_only()
+                .verify_ecdsa(&message, &ecdsa_signature, public_key)
+                .is_ok()
+        }
+        Err(e) => {
+            log::warn!("Failed to parse signature for verification: {:?}", e);
+            false
+        }
+    };
+
+    log::info!(
+        "Signature verification against supplied key: {}",
+        if signature_valid { "VALID" } else { "INVALID" }
+    );
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address: key_address.to_checksum(),
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        signature_encoding: Some(encoding),
+        derived_addresses: Some(derive_addresses(&public_key.serialize())),
+        ..Default::default()
+    }
+    .check_sign_in_account())
 }
 
 #[cfg(test)]
@@ -99,4 +286,42 @@ mod tests {
             }
         }
     }
+
+    const SIGNATURE: &str = "e5092134a1e3a91dafe7095916466a00d93fa01c540914fc3a010c05220281eb1f8fbcb34ce784875cd4a01cabef782c3c0f7e33d508410e957fb01c1c5b10071b";
+
+    #[test]
+    fn test_parse_normalizes_v_encodings() {
+        let rs = &SIGNATURE[..128];
+
+        let (legacy, encoding) = parse_evm_signature(SIGNATURE).unwrap();
+        assert_eq!(encoding, SignatureEncoding::RecoverableLegacy);
+        assert_eq!(legacy.v, 27);
+
+        let (raw, encoding) = parse_evm_signature(&format!("{}00", rs)).unwrap();
+        assert_eq!(encoding, SignatureEncoding::RecoverableRaw);
+        assert_eq!(raw, legacy);
+
+        // Chain 1: v = 1 * 2 + 35 + parity(0) = 37
+        let (eip155, encoding) = parse_evm_signature(&format!("{}25", rs)).unwrap();
+        assert_eq!(encoding, SignatureEncoding::Eip155 { chain_id: 1 });
+        assert_eq!(eip155, legacy);
+
+        // Chain 137 needs a two-byte v: 137 * 2 + 35 = 309 = 0x0135
+        let (polygon, encoding) = parse_evm_signature(&format!("{}0135", rs)).unwrap();
+        assert_eq!(encoding.chain_id(), Some(137));
+        assert_eq!(polygon, legacy);
+    }
+
+    #[test]
+    fn test_parse_eip2098_compact() {
+        // y parity 0: the compact form is just r || s
+        let (compact, encoding) = parse_evm_signature(&SIGNATURE[..128]).unwrap();
+        assert_eq!(encoding, SignatureEncoding::Eip2098);
+        assert_eq!(compact, parse_evm_signature(SIGNATURE).unwrap().0);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_v() {
+        assert!(parse_evm_signature(&format!("{}1d", &SIGNATURE[..128])).is_err());
+    }
 }
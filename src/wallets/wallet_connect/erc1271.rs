@@ -0,0 +1,287 @@
+use super::address::EvmAddress;
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use ethers_core::{
+    abi::{self, ParamType, Token},
+    types::{Address, Signature, H256},
+};
+use hex::FromHex;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+/// `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`, also the EIP-1271 magic value
+pub const ERC1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Read-only contract call (`eth_call`) against the latest block
+///
+/// Implement this on top of your JSON-RPC client to let the verifier query
+/// smart-contract wallets. Calls to addresses without code should return empty data.
+pub trait EthCall: Send + Sync {
+    fn eth_call(&self, to: Address, data: &[u8]) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Asks a smart-contract wallet whether it accepts a signature (EIP-1271)
+pub trait Erc1271Checker: Send + Sync {
+    fn is_valid_signature(
+        &self,
+        contract: Address,
+        hash: H256,
+        signature: &[u8],
+    ) -> anyhow::Result<bool>;
+}
+
+/// Any [`EthCall`] backend can check EIP-1271 signatures by calling
+/// `isValidSignature(bytes32,bytes)` on the contract
+impl<T: EthCall + ?Sized> Erc1271Checker for T {
+    fn is_valid_signature(
+        &self,
+        contract: Address,
+        hash: H256,
+        signature: &[u8],
+    ) -> anyhow::Result<bool> {
+        let calldata = encode_is_valid_signature(hash, signature);
+        let output = self.eth_call(contract, &calldata)?;
+        log::debug!("isValidSignature returned: 0x{}", hex::encode(&output));
+
+        Ok(is_magic_value(&output))
+    }
+}
+
+/// ABI-encodes a call to `isValidSignature(bytes32,bytes)`
+pub fn encode_is_valid_signature(hash: H256, signature: &[u8]) -> Vec<u8> {
+    let mut calldata = ERC1271_MAGIC_VALUE.to_vec();
+    calldata.extend(abi::encode(&[
+        Token::FixedBytes(hash.as_bytes().to_vec()),
+        Token::Bytes(signature.to_vec()),
+    ]));
+    calldata
+}
+
+/// Checks that an `isValidSignature` return value (bytes4, left-aligned in 32 bytes)
+/// is the EIP-1271 magic value
+pub(crate) fn is_magic_value(output: &[u8]) -> bool {
+    output.len() >= 32 && output[..4] == ERC1271_MAGIC_VALUE
+}
+
+/// Verifies a smart-contract wallet signature by calling `isValidSignature` on the
+/// expected address
+pub fn verify_erc1271_signature(
+    checker: &dyn Erc1271Checker,
+    signature_hex: &str,
+    message_hash: H256,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("EIP-1271 verification starting");
+    log::debug!("Contract: {}", expected_address);
+    log::debug!("Message hash: 0x{}", hex::encode(message_hash.as_bytes()));
+
+    let signature = Vec::from_hex(signature_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode signature hex: {}", e))?;
+    let contract: EvmAddress = expected_address.parse()?;
+
+    let valid = checker.is_valid_signature(contract.as_address(), message_hash, &signature)?;
+
+    if valid {
+        log::info!("Contract wallet accepted the signature (EIP-1271)");
+    } else {
+        log::warn!("Contract wallet rejected the signature (EIP-1271)");
+    }
+
+    Ok(VerificationResult {
+        address_valid: valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid: valid,
+        derived_address: contract.to_checksum(),
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+/// Registered factory deployments: (factory, calldata) -> (deployed address, contract)
+type Deployments = HashMap<(Address, Vec<u8>), (Address, ContractWallet)>;
+
+/// Minimal multisig-style contract wallet held by [`InMemoryEvmState`]
+#[derive(Debug, Clone, Default)]
+pub struct ContractWallet {
+    /// EOAs whose ECDSA signature over the hash is accepted
+    pub owners: Vec<Address>,
+    /// Hashes approved on-chain without a signature (like Safe's `signMessage`)
+    pub approved_hashes: HashSet<H256>,
+}
+
+/// In-process EVM state for tests and offline use
+///
+/// Contracts answer `isValidSignature(bytes32,bytes)` the way a Safe-like wallet
+/// would: the hash is accepted if it was pre-approved or if the signature is a
+/// 65-byte ECDSA signature by one of the owners. Addresses without a registered
+/// contract return empty data, like an EOA over JSON-RPC.
+///
+/// Factory deployments can be registered with
+/// [`register_deployment`](InMemoryEvmState::register_deployment) to simulate
+/// counterfactual (EIP-6492) wallets.
+#[derive(Debug, Default)]
+pub struct InMemoryEvmState {
+    contracts: RwLock<HashMap<Address, ContractWallet>>,
+    deployments: RwLock<Deployments>,
+}
+
+impl InMemoryEvmState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deploys (or replaces) a contract wallet at `address`
+    pub fn deploy(&self, address: Address, wallet: ContractWallet) {
+        self.contracts
+            .write()
+            .expect("EVM state lock poisoned")
+            .insert(address, wallet);
+    }
+
+    /// Returns true if a contract is deployed at `address`
+    pub fn has_code(&self, address: Address) -> bool {
+        self.contracts
+            .read()
+            .expect("EVM state lock poisoned")
+            .contains_key(&address)
+    }
+
+    /// Contract deployed at `address`, if any
+    pub(crate) fn contract_at(&self, address: Address) -> Option<ContractWallet> {
+        self.contracts
+            .read()
+            .expect("EVM state lock poisoned")
+            .get(&address)
+            .cloned()
+    }
+
+    /// Makes a call of `factory` with `factory_calldata` deploy `wallet` at `address`
+    ///
+    /// The deployment only happens inside simulations; the contract is not persisted.
+    pub fn register_deployment(
+        &self,
+        factory: Address,
+        factory_calldata: Vec<u8>,
+        address: Address,
+        wallet: ContractWallet,
+    ) {
+        self.deployments
+            .write()
+            .expect("EVM state lock poisoned")
+            .insert((factory, factory_calldata), (address, wallet));
+    }
+
+    /// Simulates the factory call, returning the deployed contract (if any)
+    pub(crate) fn simulate_deployment(
+        &self,
+        factory: Address,
+        factory_calldata: &[u8],
+    ) -> anyhow::Result<(Address, ContractWallet)> {
+        self.deployments
+            .read()
+            .expect("EVM state lock poisoned")
+            .get(&(factory, factory_calldata.to_vec()))
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("execution reverted: factory call failed"))
+    }
+
+    /// Executes an `isValidSignature` call against a contract (or returns empty data for an EOA)
+    pub(crate) fn call_contract(
+        wallet: Option<&ContractWallet>,
+        data: &[u8],
+    ) -> anyhow::Result<Vec<u8>> {
+        let Some(wallet) = wallet else {
+            return Ok(Vec::new());
+        };
+
+        if data.len() < 4 || data[..4] != ERC1271_MAGIC_VALUE {
+            return Err(anyhow::anyhow!("execution reverted: unknown selector"));
+        }
+
+        let tokens = abi::decode(&[ParamType::FixedBytes(32), ParamType::Bytes], &data[4..])
+            .map_err(|e| anyhow::anyhow!("execution reverted: {}", e))?;
+        let (hash, signature) = match (&tokens[0], &tokens[1]) {
+            (Token::FixedBytes(hash), Token::Bytes(signature)) => {
+                (H256::from_slice(hash), signature)
+            }
+            _ => return Err(anyhow::anyhow!("execution reverted: bad arguments")),
+        };
+
+        let mut output = [0u8; 32];
+        if Self::contract_accepts(wallet, hash, signature) {
+            output[..4].copy_from_slice(&ERC1271_MAGIC_VALUE);
+        } else {
+            output[..4].copy_from_slice(&[0xff; 4]);
+        }
+        Ok(output.to_vec())
+    }
+
+    fn contract_accepts(wallet: &ContractWallet, hash: H256, signature: &[u8]) -> bool {
+        if wallet.approved_hashes.contains(&hash) {
+            return true;
+        }
+
+        let Ok(signature) = Signature::try_from(signature) else {
+            return false;
+        };
+        signature
+            .recover(hash)
+            .map(|signer| wallet.owners.contains(&signer))
+            .unwrap_or(false)
+    }
+}
+
+impl EthCall for InMemoryEvmState {
+    fn eth_call(&self, to: Address, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Self::call_contract(self.contract_at(to).as_ref(), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_is_valid_signature_layout() {
+        let calldata = encode_is_valid_signature(H256::repeat_byte(0xaa), &[1, 2, 3]);
+
+        assert_eq!(calldata[..4], ERC1271_MAGIC_VALUE);
+        assert_eq!(calldata[4..36], [0xaa; 32]);
+        // Offset of the dynamic `bytes` argument, then its length and padded data
+        assert_eq!(calldata[67], 0x40);
+        assert_eq!(calldata[99], 3);
+        assert_eq!(calldata.len(), 4 + 32 * 4);
+    }
+
+    #[test]
+    fn test_in_memory_state_approved_hash() {
+        let state = InMemoryEvmState::new();
+        let contract = Address::repeat_byte(0x42);
+        let hash = H256::repeat_byte(0x01);
+
+        state.deploy(
+            contract,
+            ContractWallet {
+                owners: Vec::new(),
+                approved_hashes: HashSet::from([hash]),
+            },
+        );
+
+        assert!(state.is_valid_signature(contract, hash, &[]).unwrap());
+        assert!(!state
+            .is_valid_signature(contract, H256::repeat_byte(0x02), &[])
+            .unwrap());
+    }
+
+    #[test]
+    fn test_in_memory_state_eoa_is_not_a_contract_wallet() {
+        let state = InMemoryEvmState::new();
+
+        assert!(!state
+            .is_valid_signature(Address::repeat_byte(0x42), H256::zero(), &[0u8; 65])
+            .unwrap());
+    }
+}
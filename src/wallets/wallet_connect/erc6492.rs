@@ -0,0 +1,205 @@
+use super::address::EvmAddress;
+use super::erc1271::{encode_is_valid_signature, is_magic_value, InMemoryEvmState};
+use crate::caip::parse_sign_in_challenge;
+use crate::types::VerificationResult;
+use ethers_core::{
+    abi::{self, ParamType, Token},
+    types::{Address, H256},
+};
+use hex::FromHex;
+
+/// 32-byte suffix that marks an EIP-6492 wrapped signature
+pub const ERC6492_MAGIC_SUFFIX: [u8; 32] = [
+    0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+    0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+];
+
+/// Unwrapped EIP-6492 envelope: `abi.encode(factory, factoryCalldata, signature) ++ magic`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc6492Signature {
+    /// Factory that deploys the smart account
+    pub factory: Address,
+    /// Calldata for the factory call that deploys the account
+    pub factory_calldata: Vec<u8>,
+    /// Signature to pass to `isValidSignature` once the account exists
+    pub inner_signature: Vec<u8>,
+}
+
+impl Erc6492Signature {
+    /// Detects and unwraps an EIP-6492 envelope, returning `None` for plain signatures
+    pub fn unwrap_signature(signature: &[u8]) -> anyhow::Result<Option<Self>> {
+        let Some(encoded) = signature.strip_suffix(&ERC6492_MAGIC_SUFFIX) else {
+            return Ok(None);
+        };
+
+        let tokens = abi::decode(
+            &[ParamType::Address, ParamType::Bytes, ParamType::Bytes],
+            encoded,
+        )
+        .map_err(|e| anyhow::anyhow!("Malformed EIP-6492 envelope: {}", e))?;
+
+        match (&tokens[0], &tokens[1], &tokens[2]) {
+            (Token::Address(factory), Token::Bytes(calldata), Token::Bytes(inner)) => {
+                Ok(Some(Erc6492Signature {
+                    factory: *factory,
+                    factory_calldata: calldata.clone(),
+                    inner_signature: inner.clone(),
+                }))
+            }
+            _ => Err(anyhow::anyhow!("Malformed EIP-6492 envelope")),
+        }
+    }
+
+    /// Wraps a signature in an EIP-6492 envelope
+    pub fn wrap(&self) -> Vec<u8> {
+        let mut wrapped = abi::encode(&[
+            Token::Address(self.factory),
+            Token::Bytes(self.factory_calldata.clone()),
+            Token::Bytes(self.inner_signature.clone()),
+        ]);
+        wrapped.extend_from_slice(&ERC6492_MAGIC_SUFFIX);
+        wrapped
+    }
+}
+
+/// Returns true if the hex signature ends with the EIP-6492 magic suffix
+pub fn is_erc6492_signature(signature_hex: &str) -> bool {
+    signature_hex
+        .trim_start_matches("0x")
+        .to_lowercase()
+        .ends_with(&hex::encode(ERC6492_MAGIC_SUFFIX))
+}
+
+/// Simulates EVM calls against a state where the account may first be deployed
+///
+/// Implement this with `eth_call` state overrides, a deployless universal validator
+/// contract, or a local EVM. Nothing is persisted on chain.
+pub trait EvmSimulator: Send + Sync {
+    /// Calls `factory` with `factory_calldata` if `to` has no code yet, then calls
+    /// `to` with `data` in the same simulated state and returns its output
+    fn simulate_deploy_and_call(
+        &self,
+        factory: Address,
+        factory_calldata: &[u8],
+        to: Address,
+        data: &[u8],
+    ) -> anyhow::Result<Vec<u8>>;
+}
+
+impl EvmSimulator for InMemoryEvmState {
+    fn simulate_deploy_and_call(
+        &self,
+        factory: Address,
+        factory_calldata: &[u8],
+        to: Address,
+        data: &[u8],
+    ) -> anyhow::Result<Vec<u8>> {
+        let contract = match self.contract_at(to) {
+            Some(contract) => Some(contract),
+            None => {
+                let (deployed_at, contract) =
+                    self.simulate_deployment(factory, factory_calldata)?;
+                (deployed_at == to).then_some(contract)
+            }
+        };
+        Self::call_contract(contract.as_ref(), data)
+    }
+}
+
+/// Verifies an EIP-6492 signature from a (possibly undeployed) smart account
+///
+/// The envelope is unwrapped, the account is deployed in simulation through its
+/// factory, and `isValidSignature` is called with the inner signature.
+pub fn verify_erc6492_signature(
+    simulator: &dyn EvmSimulator,
+    signature_hex: &str,
+    message_hash: H256,
+    challenge: &str,
+    expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("EIP-6492 verification starting");
+    log::debug!("Account: {}", expected_address);
+    log::debug!("Message hash: 0x{}", hex::encode(message_hash.as_bytes()));
+
+    let signature = Vec::from_hex(signature_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode signature hex: {}", e))?;
+    let envelope = Erc6492Signature::unwrap_signature(&signature)?
+        .ok_or_else(|| anyhow::anyhow!("Signature is not an EIP-6492 envelope"))?;
+    let account: EvmAddress = expected_address.parse()?;
+
+    log::debug!("Factory: {:?}", envelope.factory);
+    log::debug!(
+        "Factory calldata: 0x{}",
+        hex::encode(&envelope.factory_calldata)
+    );
+    log::debug!(
+        "Inner signature: 0x{}",
+        hex::encode(&envelope.inner_signature)
+    );
+
+    let output = simulator.simulate_deploy_and_call(
+        envelope.factory,
+        &envelope.factory_calldata,
+        account.as_address(),
+        &encode_is_valid_signature(message_hash, &envelope.inner_signature),
+    )?;
+    let valid = is_magic_value(&output);
+
+    if valid {
+        log::info!("Counterfactual account accepted the signature (EIP-6492)");
+    } else {
+        log::warn!("Counterfactual account rejected the signature (EIP-6492)");
+    }
+
+    Ok(VerificationResult {
+        address_valid: valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid: valid,
+        derived_address: account.to_checksum(),
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_unwrap_roundtrip() {
+        let envelope = Erc6492Signature {
+            factory: Address::repeat_byte(0xfa),
+            factory_calldata: vec![0xde, 0xad, 0xbe, 0xef],
+            inner_signature: vec![0x11; 65],
+        };
+
+        let wrapped = envelope.wrap();
+        assert!(wrapped.ends_with(&ERC6492_MAGIC_SUFFIX));
+        assert!(is_erc6492_signature(&format!(
+            "0x{}",
+            hex::encode(&wrapped)
+        )));
+        assert_eq!(
+            Erc6492Signature::unwrap_signature(&wrapped).unwrap(),
+            Some(envelope)
+        );
+    }
+
+    #[test]
+    fn test_plain_signature_is_not_unwrapped() {
+        assert_eq!(
+            Erc6492Signature::unwrap_signature(&[0x11; 65]).unwrap(),
+            None
+        );
+        assert!(!is_erc6492_signature(&hex::encode([0x11; 65])));
+    }
+
+    #[test]
+    fn test_malformed_envelope_is_rejected() {
+        let mut malformed = vec![0x01, 0x02];
+        malformed.extend_from_slice(&ERC6492_MAGIC_SUFFIX);
+        assert!(Erc6492Signature::unwrap_signature(&malformed).is_err());
+    }
+}
@@ -16,6 +16,22 @@
 //! - Message is prefixed with: `\x19Ethereum Signed Message:\n{length}{message}`
 //! - Signature is 65 bytes: r (32 bytes) + s (32 bytes) + v (1 byte)
 //! - Recovery ID (v) is used to recover the public key
+//! - `v` may be 0/1, 27/28 or EIP-155 chain-encoded (`chain_id * 2 + 35/36`), and 64-byte
+//!   EIP-2098 compact signatures are accepted; the detected encoding (and EIP-155 chain ID)
+//!   is reported in [`VerificationResult::signature_encoding`](crate::types::VerificationResult::signature_encoding)
+//!
+//! EIP-712 typed data (`eth_signTypedData_v4`) is supported through
+//! [`EvmSigningScheme::TypedData`]: the challenge is the typed-data JSON and the digest is
+//! `keccak256(0x1901 || domainSeparator || hashStruct(message))`.
+//!
+//! Smart-contract wallets (Safe, etc.) are supported through EIP-1271: plug an
+//! [`erc1271::EthCall`] backend into [`EvmProvider::with_erc1271_checker`] and
+//! the provider falls back to `isValidSignature` when ECDSA recovery doesn't match.
+//! [`erc1271::InMemoryEvmState`] is an in-process stand-in for tests.
+//!
+//! Counterfactual (not yet deployed) ERC-4337 accounts sign with EIP-6492 envelopes.
+//! These are unwrapped and verified through an [`erc6492::EvmSimulator`] configured with
+//! [`EvmProvider::with_erc6492_simulator`].
 //!
 //! # Example
 //!
@@ -26,6 +42,7 @@
 //!     signature_data: "0xe5092134a1e3a91dafe7095916466a00d93fa01c540914fc3a010c05220281eb1f8fbcb34ce784875cd4a01cabef782c3c0f7e33d508410e957fb01c1c5b10071b".to_string(),
 //!     expected_address: "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651".to_string(),
 //!     challenge: Some("nuff.tech:1760706960:afba42ef-fbb7-4504-8915-583046d6eb26:login:0x33f9D9f0348c1a4Bace2ad839903bBD47F430651".to_string()),
+//!     ..Default::default()
 //! };
 //!
 //! let provider = get_wallet_provider(WalletType::WalletConnect);
@@ -35,7 +52,12 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+pub mod address;
 pub mod core;
+pub mod eip712;
+pub mod erc1271;
+pub mod erc6492;
 mod provider;
 
-pub use provider::WalletConnectProvider;
+pub use address::{ChecksumPolicy, EvmAddress};
+pub use provider::{EvmProvider, EvmSigningScheme, WalletConnectProvider};
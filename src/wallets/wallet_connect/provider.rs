@@ -1,6 +1,25 @@
 use super::super::provider::{VerificationInput, WalletProvider};
-use super::core::verify_evm_signature;
-use crate::types::VerificationResult;
+use super::address::{ChecksumPolicy, EvmAddress};
+use super::core::{
+    parse_evm_public_key, parse_evm_signature, verify_evm_digest_with_public_key,
+    verify_evm_signature_with_encoding,
+};
+use super::eip712::{hash_typed_data, parse_typed_data, verify_eip712_signature};
+use super::erc1271::{verify_erc1271_signature, Erc1271Checker};
+use super::erc6492::{is_erc6492_signature, verify_erc6492_signature, EvmSimulator};
+use crate::types::{MessageEncoding, VerificationResult};
+use ethers_core::{types::H256, utils::hash_message};
+use std::sync::Arc;
+
+/// How the EVM wallet produced the signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvmSigningScheme {
+    /// EIP-191 `personal_sign` over the challenge text
+    #[default]
+    PersonalSign,
+    /// EIP-712 `eth_signTypedData_v4`; the challenge is the typed-data JSON
+    TypedData,
+}
 
 /// Provider for WalletConnect and other EVM-compatible wallets
 ///
@@ -12,9 +31,24 @@ use crate::types::VerificationResult;
 /// # Signature Format
 ///
 /// Uses EIP-191 personal_sign format:
-/// - Signature: 65 bytes (r + s + v) = 130 hex characters
-/// - Address: Ethereum address (0x + 40 hex characters)
-/// - Message: Any UTF-8 string (hashed with EIP-191 prefix)
+/// - Signature: 65 bytes (r + s + v) = 130 hex characters, with `v` as 0/1, 27/28 or an
+///   EIP-155 chain-encoded value; or 64 bytes EIP-2098 compact (r + yParityAndS)
+/// - Address: Ethereum address (0x + 40 hex characters); mixed-case addresses must pass
+///   their EIP-55 checksum (see [`ChecksumPolicy`]). Results report the checksummed form.
+/// - Message: Any UTF-8 string (hashed with EIP-191 prefix). Set
+///   [`VerificationInput::message_encoding`] to [`MessageEncoding::HexBytes`] or
+///   [`MessageEncoding::Auto`] when the dapp passed a `0x`-prefixed hex string to
+///   `personal_sign`, so the decoded bytes are verified; the result reports which
+///   interpretation was used.
+///
+/// Typed data, EIP-55 checksum policies, contract wallets and counterfactual accounts
+/// need configuration; use [`EvmProvider`] for those.
+///
+/// # Supplied Public Key
+///
+/// When [`VerificationInput::public_key`] is set (33-, 64- or 65-byte secp256k1 key), the
+/// signature is verified directly against it instead of recovering the signer, and the
+/// key must derive to the expected address. There is no EIP-1271 fallback in this mode.
 ///
 /// # Example
 ///
@@ -25,6 +59,7 @@ use crate::types::VerificationResult;
 ///     signature_data: "0xe5092134a1e3a91dafe7095916466a00...".to_string(),
 ///     expected_address: "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651".to_string(),
 ///     challenge: Some("nuff.tech:1760706960:uuid:login:0x...".to_string()),
+///     ..Default::default()
 /// };
 ///
 /// let provider = get_wallet_provider(WalletType::WalletConnect);
@@ -35,6 +70,7 @@ use crate::types::VerificationResult;
 /// }
 /// # Ok::<(), anyhow::Error>(())
 /// ```
+#[derive(Debug, Clone, Copy, Default)]
 pub struct WalletConnectProvider;
 
 impl WalletProvider for WalletConnectProvider {
@@ -46,6 +82,123 @@ impl WalletProvider for WalletConnectProvider {
         "WalletConnect - EVM-compatible wallet signature verification (Ethereum-style signatures)"
     }
 
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        EvmProvider::new().validate_input(input)
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        EvmProvider::new().verify(input)
+    }
+}
+
+/// Configurable provider for EVM wallets
+///
+/// Verifies the same signatures as [`WalletConnectProvider`], which is this provider with
+/// its defaults, and adds the options below.
+///
+/// # Typed Data (EIP-712)
+///
+/// With [`EvmSigningScheme::TypedData`], the challenge is instead the EIP-712 typed-data
+/// JSON (domain, types, primaryType, message) passed to `eth_signTypedData_v4`.
+///
+/// # Smart-Contract Wallets (EIP-1271)
+///
+/// Contract wallets such as Safe cannot produce a recoverable ECDSA signature. When an
+/// [`Erc1271Checker`] is configured, signatures whose recovered signer does not match
+/// are passed to `isValidSignature(bytes32,bytes)` on the expected address, and
+/// signatures of any length are accepted.
+///
+/// # Counterfactual Smart Accounts (EIP-6492)
+///
+/// ERC-4337 accounts can sign before they are deployed by wrapping the signature in an
+/// EIP-6492 envelope (factory, factory calldata, inner signature, `0x6492…` suffix).
+/// Such signatures are verified through the configured [`EvmSimulator`], which deploys
+/// the account in simulation and calls `isValidSignature`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use wallet_signature_verify::wallets::{
+///     EvmProvider, EvmSigningScheme, VerificationInput, WalletProvider,
+/// };
+///
+/// let input = VerificationInput {
+///     signature_data: "0x1c2a...".to_string(),
+///     expected_address: "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651".to_string(),
+///     challenge: Some(r#"{"domain":{...},"types":{...},"primaryType":"Login","message":{...}}"#.to_string()),
+///     ..Default::default()
+/// };
+///
+/// let provider = EvmProvider::new().with_signing_scheme(EvmSigningScheme::TypedData);
+/// let result = provider.verify(&input)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct EvmProvider {
+    signing_scheme: EvmSigningScheme,
+    erc1271_checker: Option<Arc<dyn Erc1271Checker>>,
+    erc6492_simulator: Option<Arc<dyn EvmSimulator>>,
+    checksum_policy: ChecksumPolicy,
+}
+
+impl EvmProvider {
+    /// Creates a provider for EIP-191 `personal_sign` signatures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the signing scheme used to hash the challenge
+    pub fn with_signing_scheme(mut self, signing_scheme: EvmSigningScheme) -> Self {
+        self.signing_scheme = signing_scheme;
+        self
+    }
+
+    /// Sets how strictly EIP-55 checksums of `expected_address` are enforced
+    pub fn with_checksum_policy(mut self, checksum_policy: ChecksumPolicy) -> Self {
+        self.checksum_policy = checksum_policy;
+        self
+    }
+
+    /// Falls back to EIP-1271 `isValidSignature` when ECDSA recovery doesn't match
+    pub fn with_erc1271_checker(mut self, checker: Arc<dyn Erc1271Checker>) -> Self {
+        self.erc1271_checker = Some(checker);
+        self
+    }
+
+    /// Verifies EIP-6492 wrapped signatures by simulating deployment and `isValidSignature`
+    pub fn with_erc6492_simulator(mut self, simulator: Arc<dyn EvmSimulator>) -> Self {
+        self.erc6492_simulator = Some(simulator);
+        self
+    }
+
+    /// Hash the wallet signed, according to the signing scheme, and the resolved
+    /// message encoding (personal_sign only)
+    fn message_hash(
+        &self,
+        challenge: &str,
+        message_encoding: MessageEncoding,
+    ) -> anyhow::Result<(H256, Option<MessageEncoding>)> {
+        match self.signing_scheme {
+            EvmSigningScheme::PersonalSign => {
+                let (message, message_encoding) = message_encoding.decode(challenge)?;
+                Ok((hash_message(message), Some(message_encoding)))
+            }
+            EvmSigningScheme::TypedData => {
+                Ok((H256(hash_typed_data(&parse_typed_data(challenge)?)?), None))
+            }
+        }
+    }
+}
+
+impl WalletProvider for EvmProvider {
+    fn name(&self) -> &str {
+        "WalletConnect"
+    }
+
+    fn description(&self) -> &str {
+        "WalletConnect - EVM-compatible wallet signature verification (Ethereum-style signatures)"
+    }
+
     fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
         // Validate that we have a challenge
         if input.challenge.is_none() {
@@ -55,12 +208,25 @@ impl WalletProvider for WalletConnectProvider {
         }
 
         // Validate that signature_data looks like an Ethereum signature
-        // Should be 0x + 130 hex chars (65 bytes) or just 130 hex chars
+        // Should be 64 bytes (EIP-2098) or 65+ bytes (r + s + v, where an EIP-155 v
+        // can take several bytes); contract wallet signatures can have any length
         let sig = input.signature_data.trim_start_matches("0x");
 
-        if sig.len() != 130 {
+        if is_erc6492_signature(sig) {
+            if self.erc6492_simulator.is_none() {
+                return Err(anyhow::anyhow!(
+                    "WalletConnect: EIP-6492 signature requires an EVM simulator"
+                ));
+            }
+        } else if self.erc1271_checker.is_some() {
+            if sig.is_empty() || sig.len() % 2 != 0 {
+                return Err(anyhow::anyhow!(
+                    "WalletConnect: signature_data must be a non-empty byte string"
+                ));
+            }
+        } else if !(128..=144).contains(&sig.len()) || sig.len() % 2 != 0 {
             return Err(anyhow::anyhow!(
-                "WalletConnect: signature_data must be 65 bytes (130 hex chars), got {} chars",
+                "WalletConnect: signature_data must be 64 to 72 bytes (128 to 144 hex chars: r || s plus a v of up to 8 bytes), got {} chars",
                 sig.len()
             ));
         }
@@ -72,19 +238,9 @@ impl WalletProvider for WalletConnectProvider {
             ));
         }
 
-        // Validate that address looks like an Ethereum address
-        let addr = input.expected_address.trim_start_matches("0x");
-        if addr.len() != 40 {
-            return Err(anyhow::anyhow!(
-                "WalletConnect: expected_address must be an Ethereum address (0x + 40 hex chars)"
-            ));
-        }
-
-        if !addr.chars().all(|c| c.is_ascii_hexdigit()) {
-            return Err(anyhow::anyhow!(
-                "WalletConnect: expected_address must be valid hexadecimal"
-            ));
-        }
+        // Validate that address is an Ethereum address (with a valid EIP-55 checksum)
+        EvmAddress::parse(&input.expected_address, self.checksum_policy)
+            .map_err(|e| anyhow::anyhow!("WalletConnect: {}", e))?;
 
         Ok(())
     }
@@ -97,6 +253,88 @@ impl WalletProvider for WalletConnectProvider {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("WalletConnect requires challenge"))?;
 
-        verify_evm_signature(&input.signature_data, challenge, &input.expected_address)
+        // Normalize to the checksummed form so the core checks see a canonical address
+        let expected_address =
+            EvmAddress::parse(&input.expected_address, self.checksum_policy)?.to_checksum();
+
+        if let Some(simulator) = &self.erc6492_simulator {
+            if is_erc6492_signature(&input.signature_data) {
+                let (message_hash, message_encoding) =
+                    self.message_hash(challenge, input.message_encoding)?;
+                let mut result = verify_erc6492_signature(
+                    simulator.as_ref(),
+                    &input.signature_data,
+                    message_hash,
+                    challenge,
+                    &expected_address,
+                )?;
+                result.message_encoding = message_encoding;
+                return Ok(result);
+            }
+        }
+
+        if let Some(public_key) = &input.public_key {
+            let public_key = parse_evm_public_key(public_key)
+                .map_err(|e| anyhow::anyhow!("WalletConnect: {}", e))?;
+            let (signature, encoding) = parse_evm_signature(&input.signature_data)?;
+            let (message_hash, message_encoding) =
+                self.message_hash(challenge, input.message_encoding)?;
+
+            let mut result = verify_evm_digest_with_public_key(
+                &signature,
+                encoding,
+                message_hash,
+                &public_key,
+                challenge,
+                &expected_address,
+            )?;
+            result.message_encoding = message_encoding;
+            return Ok(result);
+        }
+
+        let result = match self.signing_scheme {
+            EvmSigningScheme::PersonalSign => verify_evm_signature_with_encoding(
+                &input.signature_data,
+                challenge,
+                &expected_address,
+                input.message_encoding,
+            ),
+            EvmSigningScheme::TypedData => {
+                verify_eip712_signature(&input.signature_data, challenge, &expected_address)
+            }
+        };
+
+        let Some(checker) = &self.erc1271_checker else {
+            return result;
+        };
+
+        match result {
+            Ok(result) if result.is_valid() => Ok(result),
+            ecdsa_result => {
+                match &ecdsa_result {
+                    Ok(_) => log::info!("Recovered signer does not match, trying EIP-1271"),
+                    Err(e) => log::info!("ECDSA recovery failed ({}), trying EIP-1271", e),
+                }
+                let (message_hash, message_encoding) =
+                    self.message_hash(challenge, input.message_encoding)?;
+                match verify_erc1271_signature(
+                    checker.as_ref(),
+                    &input.signature_data,
+                    message_hash,
+                    challenge,
+                    &expected_address,
+                ) {
+                    Ok(mut result) => {
+                        result.message_encoding = message_encoding;
+                        Ok(result)
+                    }
+                    // A failing checker (e.g. RPC down) must not hide the ECDSA outcome
+                    Err(e) => {
+                        log::warn!("EIP-1271 check failed: {}", e);
+                        ecdsa_result
+                    }
+                }
+            }
+        }
     }
 }
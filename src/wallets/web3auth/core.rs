@@ -1,8 +1,12 @@
-use super::recover::recover_pubkey_from_signature;
-use crate::crypto::{account_id_from_pubkey, sha512half};
-use crate::types::VerificationResult;
+use super::recover::{recover_pubkey_from_signature, recover_pubkey_with_recovery_id};
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::address::xrpl_address;
+use crate::crypto::{derive_addresses, sha512half};
+use crate::types::{MessageHashScheme, SignatureEncoding, VerificationResult};
+use ethers_core::utils::{hash_message, keccak256};
 use hex::FromHex;
 use secp256k1::{ecdsa::Signature as EcdsaSignature, Message, PublicKey, Secp256k1};
+use sha2::{Digest, Sha256};
 
 /// Converts DER signature to compact format (r, s)
 fn der_to_compact(der_sig: &[u8]) -> Option<[u8; 64]> {
@@ -12,62 +16,166 @@ fn der_to_compact(der_sig: &[u8]) -> Option<[u8; 64]> {
     }
 }
 
-/// Creates the message hash in the format Web3Auth expects
-fn hash_challenge_for_web3auth(challenge: &str) -> [u8; 32] {
-    // Web3Auth typically uses the challenge directly or with a prefix
-    // Let's try SHA-512Half (XRPL standard)
-    sha512half(challenge.as_bytes())
+/// ECDSA signature in one of the formats Web3Auth SDKs emit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Web3AuthSignature {
+    /// `r || s`
+    pub compact: [u8; 64],
+    /// Recovery ID (0-3), when the signature carries one
+    pub recovery_id: Option<u8>,
+    /// Format the signature was given in
+    pub encoding: SignatureEncoding,
 }
 
-/// Verifies Web3Auth signature
+/// Detects the signature format and normalizes it to compact `r || s`
+///
+/// Accepted formats:
+/// - DER (`30 len 02 r 02 s`)
+/// - 64 bytes compact `r || s`
+/// - 65 bytes recoverable `r || s || v` with `v` = 0-3 or 27-30
+pub fn parse_web3auth_signature(signature: &[u8]) -> anyhow::Result<Web3AuthSignature> {
+    if let Some(compact) = der_to_compact(signature) {
+        return Ok(Web3AuthSignature {
+            compact,
+            recovery_id: None,
+            encoding: SignatureEncoding::Der,
+        });
+    }
+
+    let mut compact = [0u8; 64];
+    match signature.len() {
+        64 => {
+            compact.copy_from_slice(signature);
+            Ok(Web3AuthSignature {
+                compact,
+                recovery_id: None,
+                encoding: SignatureEncoding::Compact,
+            })
+        }
+        65 => {
+            compact.copy_from_slice(&signature[..64]);
+            let (recovery_id, encoding) = match signature[64] {
+                v @ 0..=3 => (v, SignatureEncoding::RecoverableRaw),
+                v @ 27..=30 => (v - 27, SignatureEncoding::RecoverableLegacy),
+                v => return Err(anyhow::anyhow!("Invalid recovery ID (v = {})", v)),
+            };
+            Ok(Web3AuthSignature {
+                compact,
+                recovery_id: Some(recovery_id),
+                encoding,
+            })
+        }
+        len => Err(anyhow::anyhow!(
+            "Failed to parse signature: not DER, and {} bytes is neither compact (64) nor recoverable (65)",
+            len
+        )),
+    }
+}
+
+/// Creates the message hash for a concrete scheme
+///
+/// Web3Auth's XRPL adapter signs SHA-512Half, its EVM adapter the EIP-191 hash, and
+/// custom adapters typically SHA-256 or Keccak-256.
+/// [`MessageHashScheme::Auto`] is not a hash: resolve it with
+/// [`MessageHashScheme::candidates`] first, passing it here is an error.
+pub fn hash_challenge_for_web3auth(
+    challenge: &str,
+    scheme: MessageHashScheme,
+) -> anyhow::Result<[u8; 32]> {
+    match scheme {
+        MessageHashScheme::Sha256 => Ok(Sha256::digest(challenge.as_bytes()).into()),
+        MessageHashScheme::Keccak256 => Ok(keccak256(challenge.as_bytes())),
+        MessageHashScheme::Eip191 => Ok(hash_message(challenge.as_bytes()).0),
+        MessageHashScheme::Sha512Half => Ok(sha512half(challenge.as_bytes())),
+        MessageHashScheme::Auto => Err(anyhow::anyhow!(
+            "Auto is not a concrete hash scheme; hash with each of its candidates"
+        )),
+    }
+}
+
+/// Verifies Web3Auth signature over the SHA-512Half of the challenge
 pub fn verify_web3auth_signature(
     signature_hex: &str,
     challenge: &str,
     expected_address: &str,
+) -> anyhow::Result<VerificationResult> {
+    verify_web3auth_signature_with_scheme(
+        signature_hex,
+        challenge,
+        expected_address,
+        MessageHashScheme::default(),
+    )
+}
+
+/// Verifies Web3Auth signature, hashing the challenge with `hash_scheme`
+///
+/// With [`MessageHashScheme::Auto`], each known scheme is tried until one recovers the
+/// expected address; the matching scheme is reported in the result.
+pub fn verify_web3auth_signature_with_scheme(
+    signature_hex: &str,
+    challenge: &str,
+    expected_address: &str,
+    hash_scheme: MessageHashScheme,
 ) -> anyhow::Result<VerificationResult> {
     log::debug!("Web3Auth verification starting");
-    log::debug!("Signature (DER): {}", signature_hex);
+    log::debug!("Signature: {}", signature_hex);
     log::debug!("Challenge: {}", challenge);
     log::debug!("Expected Address: {}", expected_address);
 
-    // Parse signature DER
-    let signature_der = Vec::from_hex(signature_hex)?;
-
-    // Convert DER to compact format
-    let signature_compact = der_to_compact(&signature_der)
-        .ok_or_else(|| anyhow::anyhow!("Failed to parse DER signature"))?;
+    // Parse signature (DER, compact or recoverable) into compact format
+    let signature_bytes = Vec::from_hex(signature_hex)?;
+    let signature = parse_web3auth_signature(&signature_bytes)?;
 
-    log::debug!("DER signature converted to compact format");
+    log::debug!(
+        "Signature format: {:?} (recovery ID: {:?})",
+        signature.encoding,
+        signature.recovery_id
+    );
 
-    // Hash the challenge
-    let message_hash = hash_challenge_for_web3auth(challenge);
-    log::debug!("Message hash: {}", hex::encode(message_hash));
+    for scheme in hash_scheme.candidates() {
+        // Hash the challenge
+        let message_hash = hash_challenge_for_web3auth(challenge, scheme)?;
+        log::debug!("Message hash ({:?}): {}", scheme, hex::encode(message_hash));
 
-    // Recover possible public keys
-    let pubkey_candidates = recover_pubkey_from_signature(&message_hash, &signature_compact);
-    log::debug!("Found {} pubkey candidates", pubkey_candidates.len());
+        // Recover the public key directly when the recovery ID is known,
+        // otherwise every possible candidate
+        let pubkey_candidates = match signature.recovery_id {
+            Some(recovery_id) => {
+                recover_pubkey_with_recovery_id(&message_hash, &signature.compact, recovery_id)
+                    .into_iter()
+                    .collect()
+            }
+            None => recover_pubkey_from_signature(&message_hash, &signature.compact),
+        };
+        log::debug!("Found {} pubkey candidates", pubkey_candidates.len());
 
-    // Try each candidate and see which one matches the address
-    for (i, pubkey) in pubkey_candidates.iter().enumerate() {
-        let account_id = account_id_from_pubkey(pubkey);
-        let derived_address = ripple_address_codec::encode_account_id(&account_id);
+        // Try each candidate and see which one matches the address
+        for (i, pubkey) in pubkey_candidates.iter().enumerate() {
+            let derived_address = xrpl_address(pubkey);
 
-        log::debug!("Candidate {}: {}", i, derived_address);
+            log::debug!("Candidate {}: {}", i, derived_address);
 
-        if derived_address == expected_address {
-            // Found the right pubkey! Now verify the signature
-            let signature_valid = verify_with_pubkey(pubkey, &signature_der, &message_hash);
+            if derived_address == expected_address {
+                // Found the right pubkey! Now verify the signature
+                let signature_valid = verify_with_pubkey(pubkey, &signature.compact, &message_hash);
 
-            log::info!("Address match found! Signature valid: {}", signature_valid);
-            log::debug!("Matched address: {}", derived_address);
+                log::info!("Address match found! Signature valid: {}", signature_valid);
+                log::debug!("Matched address: {} ({:?})", derived_address, scheme);
 
-            return Ok(VerificationResult {
-                address_valid: true,
-                challenge_valid: true, // Challenge is what we signed
-                signature_valid,
-                derived_address,
-                found_challenge: Some(challenge.to_string()),
-            });
+                return Ok(VerificationResult {
+                    address_valid: true,
+                    challenge_valid: true, // Challenge is what we signed
+                    signature_valid,
+                    derived_address,
+                    found_challenge: Some(challenge.to_string()),
+                    sign_in: parse_sign_in_challenge(challenge),
+                    signature_encoding: Some(signature.encoding),
+                    hash_scheme: Some(scheme),
+                    derived_addresses: Some(derive_addresses(pubkey)),
+                    ..Default::default()
+                }
+                .check_sign_in_account());
+            }
         }
     }
 
@@ -80,20 +188,102 @@ pub fn verify_web3auth_signature(
         signature_valid: false,
         derived_address: String::new(),
         found_challenge: Some(challenge.to_string()),
-    })
+        sign_in: parse_sign_in_challenge(challenge),
+        signature_encoding: Some(signature.encoding),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+/// Verifies a Web3Auth secp256k1 signature directly against a supplied public key
+///
+/// Skips public key recovery: the key (33-byte compressed or 65-byte uncompressed) must
+/// derive to the expected XRPL address and verify the signature over the challenge
+/// hashed with `hash_scheme` (trying each known scheme for `Auto`).
+pub fn verify_web3auth_signature_with_public_key(
+    signature_hex: &str,
+    public_key_hex: &str,
+    challenge: &str,
+    expected_address: &str,
+    hash_scheme: MessageHashScheme,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Web3Auth verification with supplied public key");
+    log::debug!("Signature: {}", signature_hex);
+    log::debug!("Public key: {}", public_key_hex);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let public_key = Vec::from_hex(public_key_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode public key hex: {}", e))
+        .and_then(|bytes| {
+            PublicKey::from_slice(&bytes)
+                .map_err(|e| anyhow::anyhow!("Invalid secp256k1 public key: {}", e))
+        })?
+        .serialize();
+
+    let signature_bytes = Vec::from_hex(signature_hex.trim_start_matches("0x"))?;
+    let signature = parse_web3auth_signature(&signature_bytes)?;
+    log::debug!("Signature format: {:?}", signature.encoding);
+
+    // XRPL addresses are derived from the compressed key
+    let derived_addresses = derive_addresses(&public_key);
+    let derived_address = derived_addresses.xrpl.clone();
+    let address_valid = derived_address == expected_address;
+
+    log::debug!("Derived address: {}", derived_address);
+    if !address_valid {
+        log::warn!("Public key does not derive to the expected address");
+    }
+
+    let mut matched_scheme = None;
+    for scheme in hash_scheme.candidates() {
+        let message_hash = hash_challenge_for_web3auth(challenge, scheme)?;
+        log::debug!("Message hash ({:?}): {}", scheme, hex::encode(message_hash));
+        if verify_with_pubkey(&public_key, &signature.compact, &message_hash) {
+            matched_scheme = Some(scheme);
+            break;
+        }
+    }
+    let signature_valid = matched_scheme.is_some();
+
+    log::info!(
+        "Signature verification against supplied key: {}",
+        if signature_valid { "VALID" } else { "INVALID" }
+    );
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address,
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        signature_encoding: Some(signature.encoding),
+        hash_scheme: matched_scheme,
+        derived_addresses: Some(derived_addresses),
+        ..Default::default()
+    }
+    .check_sign_in_account())
 }
 
 /// Verifies the signature using the recovered public key
-fn verify_with_pubkey(pubkey: &[u8], signature_der: &[u8], message_hash: &[u8; 32]) -> bool {
+fn verify_with_pubkey(
+    pubkey: &[u8],
+    signature_compact: &[u8; 64],
+    message_hash: &[u8; 32],
+) -> bool {
     let secp = Secp256k1::new();
 
-    let sig = match EcdsaSignature::from_der(signature_der) {
+    let sig = match EcdsaSignature::from_compact(signature_compact) {
         Ok(s) => {
-            log::debug!("DER signature parsed for verification");
+            log::debug!("Compact signature parsed for verification");
             s
         }
         Err(e) => {
-            log::error!("Failed to parse DER signature for verification: {:?}", e);
+            log::error!(
+                "Failed to parse compact signature for verification: {:?}",
+                e
+            );
             return false;
         }
     };
@@ -0,0 +1,125 @@
+use super::core::hash_challenge_for_web3auth;
+use crate::caip::parse_sign_in_challenge;
+use crate::crypto::derive_addresses;
+use crate::types::{MessageEncoding, MessageHashScheme, VerificationResult};
+use ed25519_dalek::{Signature, VerifyingKey};
+use hex::FromHex;
+
+/// XRPL prefix byte for ed25519 public keys
+pub const ED25519_KEY_PREFIX: u8 = 0xED;
+
+/// Parses an ed25519 public key into its XRPL form (`0xED` + 32 bytes)
+///
+/// Accepts the raw 32-byte key or the 33-byte XRPL form, with or without `0x` prefix.
+pub fn parse_ed25519_public_key(public_key_hex: &str) -> anyhow::Result<[u8; 33]> {
+    let bytes = Vec::from_hex(public_key_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode public key hex: {}", e))?;
+
+    let mut key = [0u8; 33];
+    key[0] = ED25519_KEY_PREFIX;
+    match bytes.len() {
+        32 => key[1..].copy_from_slice(&bytes),
+        33 if bytes[0] == ED25519_KEY_PREFIX => key.copy_from_slice(&bytes),
+        len => {
+            return Err(anyhow::anyhow!(
+            "Invalid ed25519 public key: expected 32 bytes or 33 bytes with 0xED prefix, got {}",
+            len
+        ))
+        }
+    }
+    Ok(key)
+}
+
+/// Returns true if the hex public key looks like an ed25519 key (32 bytes, or 33 with `0xED`)
+pub fn is_ed25519_public_key(public_key_hex: &str) -> bool {
+    let key = public_key_hex.trim_start_matches("0x");
+    key.len() == 64 || (key.len() == 66 && key[..2].eq_ignore_ascii_case("ed"))
+}
+
+/// Verifies a Web3Auth ed25519 signature against the supplied public key
+///
+/// Ed25519 has no public key recovery, so the key must be supplied by the caller. It is
+/// checked to derive to the expected XRPL address and the signature is verified over the
+/// raw challenge bytes (what ed25519 signers normally sign), then over the challenge hashed
+/// with `hash_scheme` (trying each known scheme for `Auto`). A raw-message match is
+/// reported with `hash_scheme: None` and `message_encoding: Some(Utf8)`.
+pub fn verify_web3auth_ed25519_signature(
+    signature_hex: &str,
+    public_key_hex: &str,
+    challenge: &str,
+    expected_address: &str,
+    hash_scheme: MessageHashScheme,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("Web3Auth ed25519 verification starting");
+    log::debug!("Signature: {}", signature_hex);
+    log::debug!("Public key: {}", public_key_hex);
+    log::debug!("Challenge: {}", challenge);
+    log::debug!("Expected Address: {}", expected_address);
+
+    let signature = Vec::from_hex(signature_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode signature hex: {}", e))?;
+    if signature.len() != 64 {
+        return Err(anyhow::anyhow!(
+            "Invalid ed25519 signature length: expected 64 bytes, got {}",
+            signature.len()
+        ));
+    }
+
+    let public_key = parse_ed25519_public_key(public_key_hex)?;
+    let derived_addresses = derive_addresses(&public_key);
+    let derived_address = derived_addresses.xrpl.clone();
+    let address_valid = derived_address == expected_address;
+
+    log::debug!("Derived address: {}", derived_address);
+    if !address_valid {
+        log::warn!("Public key does not derive to the expected address");
+    }
+
+    let raw_valid = verify_ed25519(&public_key, &signature, challenge.as_bytes());
+    let mut matched_scheme = None;
+    if !raw_valid {
+        for scheme in hash_scheme.candidates() {
+            let digest = hash_challenge_for_web3auth(challenge, scheme)?;
+            log::debug!("Message hash ({:?}): {}", scheme, hex::encode(digest));
+            if verify_ed25519(&public_key, &signature, &digest) {
+                matched_scheme = Some(scheme);
+                break;
+            }
+        }
+    }
+    let signature_valid = raw_valid || matched_scheme.is_some();
+
+    if raw_valid {
+        log::info!("Ed25519 signature valid (raw message)");
+    } else if signature_valid {
+        log::info!("Ed25519 signature valid ({:?})", matched_scheme);
+    } else {
+        log::warn!("Ed25519 signature verification failed");
+    }
+
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid: true, // Challenge is what we signed
+        signature_valid,
+        derived_address,
+        found_challenge: Some(challenge.to_string()),
+        sign_in: parse_sign_in_challenge(challenge),
+        message_encoding: raw_valid.then_some(MessageEncoding::Utf8),
+        hash_scheme: matched_scheme,
+        derived_addresses: Some(derived_addresses),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+/// Verifies an ed25519 signature with `verify_strict`, which rejects small-order keys and
+/// non-canonical signatures
+fn verify_ed25519(public_key: &[u8; 33], signature: &[u8], message: &[u8]) -> bool {
+    let Ok(key) = VerifyingKey::try_from(&public_key[1..]) else {
+        return false;
+    };
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return false;
+    };
+    key.verify_strict(message, &signature).is_ok()
+}
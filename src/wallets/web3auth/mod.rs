@@ -1,4 +1,5 @@
 pub mod core;
+pub mod ed25519;
 pub mod provider;
 pub mod recover;
 
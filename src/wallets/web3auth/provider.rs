@@ -1,8 +1,20 @@
 use super::super::provider::{VerificationInput, WalletProvider};
-use super::core::verify_web3auth_signature;
+use super::core::{
+    verify_web3auth_signature_with_public_key, verify_web3auth_signature_with_scheme,
+};
+use super::ed25519::{is_ed25519_public_key, verify_web3auth_ed25519_signature};
 use crate::types::VerificationResult;
 
 /// Provider for Web3Auth (secp256k1 raw signatures)
+///
+/// Signatures may be DER, 64-byte compact (`r || s`) or 65-byte recoverable
+/// (`r || s || v`); with a recovery ID the public key is recovered directly instead of
+/// trying all four candidates. The detected format is reported in the result.
+///
+/// The challenge is hashed with [`VerificationInput::hash_scheme`] before recovery:
+/// SHA-512Half by default (XRPL adapter), or SHA-256, Keccak-256 and EIP-191 for
+/// other adapters. [`MessageHashScheme::Auto`](crate::types::MessageHashScheme::Auto)
+/// tries each of them and the result reports the scheme that matched.
 pub struct Web3AuthProvider;
 
 impl WalletProvider for Web3AuthProvider {
@@ -22,10 +34,11 @@ impl WalletProvider for Web3AuthProvider {
             ));
         }
 
-        // Validate that signature_data looks like a DER signature
+        // Validate that signature_data looks like a DER, compact (64-byte) or
+        // recoverable (65-byte) signature
         if input.signature_data.len() < 64 {
             return Err(anyhow::anyhow!(
-                "Web3Auth: signature_data too short (expected: DER hex signature)"
+                "Web3Auth: signature_data too short (expected: DER, compact or recoverable hex signature)"
             ));
         }
 
@@ -47,6 +60,31 @@ impl WalletProvider for Web3AuthProvider {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Web3Auth requires challenge"))?;
 
-        verify_web3auth_signature(&input.signature_data, challenge, &input.expected_address)
+        if let Some(public_key) = &input.public_key {
+            if is_ed25519_public_key(public_key) {
+                return verify_web3auth_ed25519_signature(
+                    &input.signature_data,
+                    public_key,
+                    challenge,
+                    &input.expected_address,
+                    input.hash_scheme,
+                );
+            }
+
+            return verify_web3auth_signature_with_public_key(
+                &input.signature_data,
+                public_key,
+                challenge,
+                &input.expected_address,
+                input.hash_scheme,
+            );
+        }
+
+        verify_web3auth_signature_with_scheme(
+            &input.signature_data,
+            challenge,
+            &input.expected_address,
+            input.hash_scheme,
+        )
     }
 }
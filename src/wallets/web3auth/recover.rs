@@ -51,3 +51,25 @@ pub fn recover_pubkey_from_signature(
     );
     candidates
 }
+
+/// Recovers the public key from an ECDSA signature with a known recovery ID (0-3)
+pub fn recover_pubkey_with_recovery_id(
+    message_hash: &[u8; 32],
+    signature_compact: &[u8; 64],
+    recovery_id: u8,
+) -> Option<Vec<u8>> {
+    log::debug!("Recovering public key with recovery ID {}", recovery_id);
+
+    let secp = Secp256k1::new();
+    let message = Message::from_digest_slice(message_hash).ok()?;
+    let rec_id = secp256k1::ecdsa::RecoveryId::from_i32(recovery_id as i32).ok()?;
+    let recoverable_sig = RecoverableSignature::from_compact(signature_compact, rec_id).ok()?;
+
+    match secp.recover_ecdsa(&message, &recoverable_sig) {
+        Ok(pubkey) => Some(pubkey.serialize().to_vec()),
+        Err(e) => {
+            log::warn!("Public key recovery failed: {:?}", e);
+            None
+        }
+    }
+}
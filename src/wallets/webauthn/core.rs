@@ -0,0 +1,484 @@
+use crate::caip::parse_sign_in_challenge;
+use crate::cbor::CborValue;
+use crate::types::VerificationResult;
+use base64::{
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL},
+    Engine as _,
+};
+use p256::ecdsa::{signature::Verifier as _, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+/// `clientDataJSON.type` of assertions
+pub const ASSERTION_TYPE: &str = "webauthn.get";
+
+/// Authenticator data flag: user present (UP)
+pub const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// Authenticator data flag: user verified (UV)
+pub const FLAG_USER_VERIFIED: u8 = 0x04;
+
+/// COSE key parameters (RFC 9052 / RFC 9053)
+const COSE_KTY: i128 = 1;
+const COSE_ALG: i128 = 3;
+const COSE_EC2_CRV: i128 = -1;
+const COSE_EC2_X: i128 = -2;
+const COSE_EC2_Y: i128 = -3;
+const COSE_KTY_EC2: i128 = 2;
+const COSE_ALG_ES256: i128 = -7;
+const COSE_CRV_P256: i128 = 1;
+
+/// Fixed-size head of the authenticator data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticatorData {
+    /// SHA-256 of the relying party ID
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+}
+
+impl AuthenticatorData {
+    /// Parses `rpIdHash (32) || flags (1) || signCount (4, BE)`; extensions that may
+    /// follow are covered by the signature but not interpreted
+    pub fn parse(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 37 {
+            return Err(anyhow::anyhow!(
+                "Authenticator data too short: {} bytes",
+                data.len()
+            ));
+        }
+
+        Ok(AuthenticatorData {
+            rp_id_hash: data[..32].try_into()?,
+            flags: data[32],
+            sign_count: u32::from_be_bytes(data[33..37].try_into()?),
+        })
+    }
+
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+}
+
+/// Fields of `clientDataJSON` the relying party checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientData {
+    pub type_: String,
+    /// base64url of the challenge bytes
+    pub challenge: String,
+    pub origin: String,
+    pub cross_origin: bool,
+}
+
+impl ClientData {
+    pub fn parse(client_data_json: &[u8]) -> anyhow::Result<Self> {
+        let json: serde_json::Value = serde_json::from_slice(client_data_json)
+            .map_err(|e| anyhow::anyhow!("Failed to parse clientDataJSON: {}", e))?;
+        let field = |name: &str| {
+            json[name]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("clientDataJSON is missing '{}'", name))
+        };
+
+        Ok(ClientData {
+            type_: field("type")?,
+            challenge: field("challenge")?,
+            origin: field("origin")?,
+            cross_origin: json["crossOrigin"].as_bool().unwrap_or(false),
+        })
+    }
+
+    /// Whether the challenge is `expected`, given either as the challenge string the
+    /// app passed (its UTF-8 bytes) or as its base64url encoding
+    pub fn challenge_matches(&self, expected: &str) -> bool {
+        self.challenge.trim_end_matches('=') == BASE64_URL.encode(expected)
+            || self.challenge == expected
+    }
+
+    /// The challenge as text, if its bytes are UTF-8
+    pub fn decoded_challenge(&self) -> String {
+        BASE64_URL
+            .decode(self.challenge.trim_end_matches('='))
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .unwrap_or_else(|| self.challenge.clone())
+    }
+}
+
+/// WebAuthn assertion returned by `navigator.credentials.get()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebAuthnAssertion {
+    /// Credential ID (base64url), when the whole credential was sent
+    pub credential_id: Option<String>,
+    pub authenticator_data: Vec<u8>,
+    pub client_data_json: Vec<u8>,
+    /// DER-encoded ECDSA signature
+    pub signature: Vec<u8>,
+}
+
+impl WebAuthnAssertion {
+    /// Parses the `PublicKeyCredential` JSON (`{"id","response":{...}}`, as produced by
+    /// `toJSON()`) or the bare `response` object; binary fields are base64url (padded
+    /// or standard base64 also accepted)
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        let json: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| anyhow::anyhow!("Failed to parse WebAuthn assertion JSON: {}", e))?;
+        let response = if json["response"].is_object() {
+            &json["response"]
+        } else {
+            &json
+        };
+        let field = |name: &str| {
+            response[name]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("WebAuthn assertion is missing '{}'", name))
+                .and_then(decode_base64)
+        };
+
+        Ok(WebAuthnAssertion {
+            credential_id: json["id"].as_str().map(str::to_string),
+            authenticator_data: field("authenticatorData")?,
+            client_data_json: field("clientDataJSON")?,
+            signature: field("signature")?,
+        })
+    }
+
+    /// Bytes that are signed: `authenticatorData || SHA-256(clientDataJSON)`
+    pub fn signed_data(&self) -> Vec<u8> {
+        let mut data = self.authenticator_data.clone();
+        data.extend_from_slice(&Sha256::digest(&self.client_data_json));
+        data
+    }
+}
+
+/// Decodes base64url (with or without padding) or standard base64
+fn decode_base64(value: &str) -> anyhow::Result<Vec<u8>> {
+    BASE64_URL
+        .decode(value.trim_end_matches('='))
+        .or_else(|_| BASE64.decode(value))
+        .map_err(|e| anyhow::anyhow!("Invalid base64url value: {}", e))
+}
+
+/// Parses a registered COSE_Key (EC2, ES256, P-256), given as hex, base64url or base64
+pub fn parse_cose_public_key(public_key: &str) -> anyhow::Result<VerifyingKey> {
+    let public_key = public_key.trim();
+    let bytes = match hex::decode(public_key.trim_start_matches("0x")) {
+        Ok(bytes) => bytes,
+        Err(_) => decode_base64(public_key)?,
+    };
+    let key = CborValue::decode(&bytes)?;
+    let param = |label: i128| key.get(&CborValue::Int(label));
+
+    if param(COSE_KTY).and_then(CborValue::as_int) != Some(COSE_KTY_EC2) {
+        return Err(anyhow::anyhow!("COSE key is not an EC2 key"));
+    }
+    if let Some(alg) = param(COSE_ALG) {
+        if alg.as_int() != Some(COSE_ALG_ES256) {
+            return Err(anyhow::anyhow!(
+                "Unsupported COSE algorithm {:?}: only ES256 is supported",
+                alg
+            ));
+        }
+    }
+    if param(COSE_EC2_CRV).and_then(CborValue::as_int) != Some(COSE_CRV_P256) {
+        return Err(anyhow::anyhow!("COSE key is not on the P-256 curve"));
+    }
+
+    let coordinate = |label: i128, name: &str| -> anyhow::Result<[u8; 32]> {
+        param(label)
+            .and_then(CborValue::as_bytes)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("COSE key has no 32-byte '{}' coordinate", name))
+    };
+    let sec1 = [
+        &[0x04][..],
+        &coordinate(COSE_EC2_X, "x")?,
+        &coordinate(COSE_EC2_Y, "y")?,
+    ]
+    .concat();
+    VerifyingKey::from_sec1_bytes(&sec1)
+        .map_err(|e| anyhow::anyhow!("Invalid P-256 public key: {}", e))
+}
+
+/// Relying party rules the assertion is checked against
+///
+/// The RP ID and at least one allowed origin are required; verifying with a policy
+/// that lacks them is an error.
+#[derive(Debug, Clone, Default)]
+pub struct WebAuthnPolicy {
+    /// Relying party ID whose hash the authenticator data must carry
+    pub rp_id: Option<String>,
+    /// Origins the assertion may come from
+    pub allowed_origins: Vec<String>,
+    /// Whether the user-verified flag is required, not only user presence
+    pub require_user_verification: bool,
+    /// Whether assertions made in a cross-origin iframe (`crossOrigin: true`) are accepted
+    pub allow_cross_origin: bool,
+}
+
+impl WebAuthnPolicy {
+    /// Checks that the RP ID and at least one allowed origin are set
+    pub fn ensure_configured(&self) -> anyhow::Result<&str> {
+        let rp_id = self
+            .rp_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("WebAuthn: RP ID is required for verification"))?;
+        if self.allowed_origins.is_empty() {
+            return Err(anyhow::anyhow!(
+                "WebAuthn: at least one allowed origin is required for verification"
+            ));
+        }
+        Ok(rp_id)
+    }
+
+    /// Validates the ceremony type, origin, RP ID hash and flags
+    pub fn validate(
+        &self,
+        client_data: &ClientData,
+        authenticator_data: &AuthenticatorData,
+    ) -> anyhow::Result<()> {
+        if client_data.type_ != ASSERTION_TYPE {
+            return Err(anyhow::anyhow!(
+                "clientDataJSON type is '{}', not '{}'",
+                client_data.type_,
+                ASSERTION_TYPE
+            ));
+        }
+        let rp_id = self.ensure_configured()?;
+        if !self.allowed_origins.contains(&client_data.origin) {
+            return Err(anyhow::anyhow!(
+                "Origin '{}' is not allowed",
+                client_data.origin
+            ));
+        }
+        if client_data.cross_origin && !self.allow_cross_origin {
+            return Err(anyhow::anyhow!("Cross-origin assertions are not allowed"));
+        }
+
+        let rp_id_hash: [u8; 32] = Sha256::digest(rp_id.as_bytes()).into();
+        if authenticator_data.rp_id_hash != rp_id_hash {
+            return Err(anyhow::anyhow!(
+                "Authenticator data is not for relying party '{}'",
+                rp_id
+            ));
+        }
+
+        if !authenticator_data.user_present() {
+            return Err(anyhow::anyhow!("User presence flag is not set"));
+        }
+        if self.require_user_verification && !authenticator_data.user_verified() {
+            return Err(anyhow::anyhow!("User verification flag is not set"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Verifies a WebAuthn assertion with a registered ES256 COSE key
+///
+/// The expected address is the credential ID the key was registered under.
+///
+/// - `signature_valid`: the ES256 signature over `authenticatorData ||
+///   SHA-256(clientDataJSON)` is valid
+/// - `challenge_valid`: `clientDataJSON` carries the expected challenge, and the type,
+///   origin, RP ID hash and flags satisfy `policy`
+/// - `address_valid`: the assertion is for the expected credential (assertions sent
+///   without their credential ID are attributed to it)
+pub fn verify_webauthn_assertion(
+    assertion: &WebAuthnAssertion,
+    public_key: &str,
+    expected_challenge: &str,
+    expected_credential_id: &str,
+    policy: &WebAuthnPolicy,
+) -> anyhow::Result<VerificationResult> {
+    log::debug!("WebAuthn verification starting");
+    log::debug!("Credential ID: {:?}", assertion.credential_id);
+    log::debug!("Challenge: {}", expected_challenge);
+    log::debug!("Expected Credential ID: {}", expected_credential_id);
+
+    policy.ensure_configured()?;
+    let verifying_key = parse_cose_public_key(public_key)?;
+    let authenticator_data = AuthenticatorData::parse(&assertion.authenticator_data)?;
+    let client_data = ClientData::parse(&assertion.client_data_json)?;
+
+    // 1. Credential
+    let derived_address = assertion
+        .credential_id
+        .clone()
+        .unwrap_or_else(|| expected_credential_id.to_string());
+    let address_valid = derived_address == expected_credential_id;
+    if !address_valid {
+        log::warn!(
+            "Assertion is for credential {}, not {}",
+            derived_address,
+            expected_credential_id
+        );
+    }
+
+    // 2. Challenge and relying party rules
+    let mut challenge_valid = client_data.challenge_matches(expected_challenge);
+    if !challenge_valid {
+        log::warn!("clientDataJSON challenge does not match the expected challenge");
+    }
+    if let Err(e) = policy.validate(&client_data, &authenticator_data) {
+        log::warn!("WebAuthn assertion rejected: {}", e);
+        challenge_valid = false;
+    }
+
+    // 3. Signature
+    let signature_valid = match Signature::from_der(&assertion.signature)
+        .or_else(|_| Signature::from_slice(&assertion.signature))
+    {
+        Ok(signature) => match verifying_key.verify(&assertion.signed_data(), &signature) {
+            Ok(()) => {
+                log::info!("WebAuthn signature verification successful!");
+                true
+            }
+            Err(e) => {
+                log::warn!("WebAuthn signature verification failed: {}", e);
+                false
+            }
+        },
+        Err(e) => {
+            log::warn!("Invalid ES256 signature encoding: {}", e);
+            false
+        }
+    };
+
+    let found_challenge = client_data.decoded_challenge();
+    Ok(VerificationResult {
+        address_valid,
+        challenge_valid,
+        signature_valid,
+        derived_address,
+        sign_in: parse_sign_in_challenge(&found_challenge),
+        found_challenge: Some(found_challenge),
+        ..Default::default()
+    }
+    .check_sign_in_account())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_data(origin: &str) -> ClientData {
+        ClientData {
+            type_: ASSERTION_TYPE.to_string(),
+            challenge: BASE64_URL.encode("hello"),
+            origin: origin.to_string(),
+            cross_origin: false,
+        }
+    }
+
+    fn authenticator_data(rp_id: &str, flags: u8) -> AuthenticatorData {
+        let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+        data.push(flags);
+        data.extend_from_slice(&7u32.to_be_bytes());
+        AuthenticatorData::parse(&data).unwrap()
+    }
+
+    #[test]
+    fn test_client_data() {
+        let client_data = client_data("https://login.example.com:8443");
+        assert!(client_data.challenge_matches("hello"));
+        assert!(client_data.challenge_matches(&BASE64_URL.encode("hello")));
+        assert!(!client_data.challenge_matches("hell"));
+        assert_eq!(client_data.decoded_challenge(), "hello");
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = WebAuthnPolicy {
+            rp_id: Some("example.com".to_string()),
+            allowed_origins: vec!["https://example.com".to_string()],
+            require_user_verification: false,
+            allow_cross_origin: false,
+        };
+        let origin = client_data("https://example.com");
+        let data = authenticator_data("example.com", FLAG_USER_PRESENT);
+        assert_eq!(data.sign_count, 7);
+        assert!(policy.validate(&origin, &data).is_ok());
+
+        assert!(policy
+            .validate(&client_data("https://evil.com"), &data)
+            .is_err());
+        assert!(policy
+            .validate(&origin, &authenticator_data("evil.com", FLAG_USER_PRESENT))
+            .is_err());
+        assert!(policy
+            .validate(&origin, &authenticator_data("example.com", 0))
+            .is_err());
+
+        let uv = WebAuthnPolicy {
+            require_user_verification: true,
+            ..policy.clone()
+        };
+        assert!(uv.validate(&origin, &data).is_err());
+        assert!(uv
+            .validate(
+                &origin,
+                &authenticator_data("example.com", FLAG_USER_PRESENT | FLAG_USER_VERIFIED)
+            )
+            .is_ok());
+
+        // Cross-origin iframes need an explicit opt-in
+        let cross_origin = ClientData {
+            cross_origin: true,
+            ..origin.clone()
+        };
+        assert!(policy.validate(&cross_origin, &data).is_err());
+        let embedded = WebAuthnPolicy {
+            allow_cross_origin: true,
+            ..policy.clone()
+        };
+        assert!(embedded.validate(&cross_origin, &data).is_ok());
+
+        // The RP ID and allowed origins must be configured
+        assert!(WebAuthnPolicy::default().validate(&origin, &data).is_err());
+        let no_origins = WebAuthnPolicy {
+            allowed_origins: Vec::new(),
+            ..policy.clone()
+        };
+        assert!(no_origins.validate(&origin, &data).is_err());
+        let registration = ClientData {
+            type_: "webauthn.create".to_string(),
+            ..origin
+        };
+        assert!(policy.validate(&registration, &data).is_err());
+    }
+
+    #[test]
+    fn test_cose_key() {
+        let key = p256::ecdsa::SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let point = key.verifying_key().to_encoded_point(false);
+        let cose_key = |alg: i128| {
+            CborValue::Map(vec![
+                (CborValue::Int(COSE_KTY), CborValue::Int(COSE_KTY_EC2)),
+                (CborValue::Int(COSE_ALG), CborValue::Int(alg)),
+                (CborValue::Int(COSE_EC2_CRV), CborValue::Int(COSE_CRV_P256)),
+                (
+                    CborValue::Int(COSE_EC2_X),
+                    CborValue::Bytes(point.x().unwrap().to_vec()),
+                ),
+                (
+                    CborValue::Int(COSE_EC2_Y),
+                    CborValue::Bytes(point.y().unwrap().to_vec()),
+                ),
+            ])
+            .encode()
+        };
+
+        let parsed = parse_cose_public_key(&BASE64_URL.encode(cose_key(COSE_ALG_ES256))).unwrap();
+        assert_eq!(&parsed, key.verifying_key());
+        assert_eq!(
+            parse_cose_public_key(&hex::encode(cose_key(COSE_ALG_ES256))).unwrap(),
+            parsed
+        );
+        assert!(parse_cose_public_key(&hex::encode(cose_key(-8))).is_err());
+    }
+}
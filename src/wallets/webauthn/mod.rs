@@ -0,0 +1,48 @@
+//! WebAuthn Provider
+//!
+//! This module provides verification of WebAuthn assertions, so passkeys and security
+//! keys can sign in through the same pipeline as wallets.
+//!
+//! # Supported Authenticators
+//!
+//! - Platform passkeys (iCloud Keychain, Google Password Manager, Windows Hello)
+//! - FIDO2 security keys using ES256 (P-256)
+//!
+//! # Signature Format
+//!
+//! - Assertion: `authenticatorData`, `clientDataJSON` and `signature` from
+//!   `navigator.credentials.get()`, base64url
+//! - Signature: ES256 (ECDSA P-256, SHA-256) over
+//!   `authenticatorData || SHA-256(clientDataJSON)`, DER-encoded
+//! - Public key: COSE_Key (EC2, P-256) stored at registration
+//! - Address: the credential ID
+//!
+//! The challenge in `clientDataJSON` must be the expected challenge; the type, origin,
+//! RP ID hash, cross-origin flag and user-present flag are checked against a
+//! [`WebAuthnPolicy`], which must name the RP ID and the allowed origins.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wallet_signature_verify::wallets::{VerificationInput, WalletProvider, WebAuthnProvider};
+//!
+//! let input = VerificationInput {
+//!     signature_data: r#"{"id":"...","response":{"authenticatorData":"...","clientDataJSON":"...","signature":"..."}}"#.to_string(),
+//!     expected_address: "credential-id-base64url".to_string(),
+//!     challenge: Some("example.com:1760706960:afba42ef:login".to_string()),
+//!     public_key: Some("pQECAyYgASFYI...".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let provider = WebAuthnProvider::new()
+//!     .with_rp_id("example.com")
+//!     .with_allowed_origin("https://example.com");
+//! let result = provider.verify(&input)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod core;
+mod provider;
+
+pub use core::{WebAuthnAssertion, WebAuthnPolicy};
+pub use provider::WebAuthnProvider;
@@ -0,0 +1,107 @@
+use super::super::provider::{VerificationInput, WalletProvider};
+use super::core::{verify_webauthn_assertion, WebAuthnAssertion, WebAuthnPolicy};
+use crate::types::VerificationResult;
+
+/// Provider for passkeys and security keys (WebAuthn ES256 assertions)
+///
+/// The signature data is the `PublicKeyCredential` JSON returned by
+/// `navigator.credentials.get()` (or its `response` object), the expected address is
+/// the credential ID, and `public_key` is the COSE key registered for it. The
+/// challenge is what the app passed to `get()`. The relying party ID and at least one
+/// allowed origin must be set with [`WebAuthnProvider::with_rp_id`] and
+/// [`WebAuthnProvider::with_allowed_origin`], which is why this provider is not in the
+/// CLI registry.
+#[derive(Debug, Clone, Default)]
+pub struct WebAuthnProvider {
+    policy: WebAuthnPolicy,
+}
+
+impl WebAuthnProvider {
+    /// Creates a provider that requires user presence; set the RP ID and allowed
+    /// origins before verifying
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the authenticator data to be for this relying party ID
+    pub fn with_rp_id(mut self, rp_id: &str) -> Self {
+        self.policy.rp_id = Some(rp_id.to_string());
+        self
+    }
+
+    /// Allows assertions from this origin
+    pub fn with_allowed_origin(mut self, origin: &str) -> Self {
+        self.policy.allowed_origins.push(origin.to_string());
+        self
+    }
+
+    /// Requires the user-verified flag (PIN or biometrics), not only user presence
+    pub fn with_user_verification(mut self, required: bool) -> Self {
+        self.policy.require_user_verification = required;
+        self
+    }
+
+    /// Accepts assertions made inside a cross-origin iframe (`crossOrigin: true`)
+    pub fn with_cross_origin(mut self, allowed: bool) -> Self {
+        self.policy.allow_cross_origin = allowed;
+        self
+    }
+}
+
+impl WalletProvider for WebAuthnProvider {
+    fn name(&self) -> &str {
+        "WebAuthn"
+    }
+
+    fn description(&self) -> &str {
+        "WebAuthn - passkey and security key ES256 assertion verification"
+    }
+
+    fn validate_input(&self, input: &VerificationInput) -> anyhow::Result<()> {
+        // Validate that we have a challenge
+        if input.challenge.is_none() {
+            return Err(anyhow::anyhow!(
+                "WebAuthn: challenge is required for verification"
+            ));
+        }
+
+        // Validate that we have the registered key
+        if input.public_key.is_none() {
+            return Err(anyhow::anyhow!(
+                "WebAuthn: registered COSE public key is required for verification"
+            ));
+        }
+
+        // Validate that address is a credential ID
+        if input.expected_address.is_empty() {
+            return Err(anyhow::anyhow!("WebAuthn: credential ID cannot be empty"));
+        }
+
+        // Validate that the relying party is configured
+        self.policy.ensure_configured()?;
+
+        Ok(())
+    }
+
+    fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
+        self.validate_input(input)?;
+
+        let challenge = input
+            .challenge
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("WebAuthn requires challenge"))?;
+        let public_key = input
+            .public_key
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("WebAuthn requires public key"))?;
+
+        let assertion = WebAuthnAssertion::parse(&input.signature_data)?;
+        verify_webauthn_assertion(
+            &assertion,
+            public_key,
+            challenge,
+            &input.expected_address,
+            &self.policy,
+        )
+    }
+}
@@ -1,8 +1,12 @@
 use super::super::provider::{VerificationInput, WalletProvider};
+use crate::parser::extract_fields;
 use crate::types::VerificationResult;
 use crate::verify_xrpl_signin;
 
 /// Provider for Xaman Wallet (XRPL SignIn)
+///
+/// The signing key is carried in the blob's `SigningPubKey`; if
+/// [`VerificationInput::public_key`] is set it must match that key.
 pub struct XamanProvider;
 
 impl WalletProvider for XamanProvider {
@@ -35,6 +39,17 @@ impl WalletProvider for XamanProvider {
     fn verify(&self, input: &VerificationInput) -> anyhow::Result<VerificationResult> {
         self.validate_input(input)?;
 
+        if let Some(public_key) = &input.public_key {
+            let signing_pubkey = hex::encode(extract_fields(&input.signature_data)?.signing_pubkey);
+            if !signing_pubkey.eq_ignore_ascii_case(public_key.trim_start_matches("0x")) {
+                return Err(anyhow::anyhow!(
+                    "Xaman: SigningPubKey {} does not match the supplied public key {}",
+                    signing_pubkey.to_uppercase(),
+                    public_key
+                ));
+            }
+        }
+
         verify_xrpl_signin(
             &input.signature_data,
             &input.expected_address,
@@ -0,0 +1,418 @@
+//! Integration tests for Bitcoin signed-message verification
+//!
+//! Bitcoin wallets sign `SHA256d("Bitcoin Signed Message:\n" || message)` and encode the
+//! recovery ID and address type in the header byte of a base64 65-byte signature. The
+//! BIP-137 tests pair each header with the address types it may name, including
+//! Electrum's compressed P2PKH header on SegWit addresses, and pin Bitcoin Core's
+//! `signmessage` vector and `tests/vectors/bitcoin.py` signatures.
+//! The BIP-322 tests sign the `to_sign` virtual transaction with the P2WPKH, P2SH-P2WPKH
+//! or tweaked Taproot key and submit its witness (simple) or the whole transaction (full).
+
+mod common;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use common::{secp256k1_key, CHALLENGE};
+use secp256k1::{Keypair, Message, PublicKey, Scalar, SECP256K1};
+use wallet_signature_verify::crypto::{account_id_from_pubkey, tagged_hash};
+use wallet_signature_verify::wallets::bitcoin::bip322::{to_sign, to_spend};
+use wallet_signature_verify::wallets::bitcoin::core::{
+    bitcoin_message_hash, verify_bitcoin_signature,
+};
+use wallet_signature_verify::wallets::bitcoin::transaction::{encode_witness, Transaction};
+use wallet_signature_verify::wallets::bitcoin::{
+    BitcoinAddress, BitcoinAddressType, BitcoinNetwork, BitcoinProvider, BitcoinSigningScheme,
+};
+use wallet_signature_verify::wallets::{
+    get_wallet_provider, VerificationInput, WalletProvider, WalletType,
+};
+
+/// Signs `message` like "Sign Message" does, with the header base for the address type
+/// (27 uncompressed P2PKH, 31 compressed P2PKH / Electrum, 35 P2SH-P2WPKH, 39 P2WPKH)
+fn sign_message(message: &str, secret_byte: u8, header_base: u8) -> String {
+    let secret_key = secp256k1_key(secret_byte);
+    let digest = Message::from_digest(bitcoin_message_hash(message.as_bytes()));
+    let (recovery_id, compact) = SECP256K1
+        .sign_ecdsa_recoverable(&digest, &secret_key)
+        .serialize_compact();
+
+    let mut signature = vec![header_base + recovery_id.to_i32() as u8];
+    signature.extend_from_slice(&compact);
+    BASE64.encode(signature)
+}
+
+/// Address of the test key for `address_type` on `network`
+fn address_of(
+    secret_byte: u8,
+    address_type: BitcoinAddressType,
+    network: BitcoinNetwork,
+    compressed: bool,
+) -> String {
+    let public_key = PublicKey::from_secret_key(SECP256K1, &secp256k1_key(secret_byte));
+    let public_key = if compressed {
+        public_key.serialize().to_vec()
+    } else {
+        public_key.serialize_uncompressed().to_vec()
+    };
+
+    BitcoinAddress::from_public_key(address_type, network, &public_key)
+        .unwrap()
+        .to_string()
+}
+
+/// Test BIP-137 signatures for every supported address type
+#[test]
+fn test_bitcoin_bip137_address_types() {
+    use BitcoinAddressType::*;
+
+    for (address_type, header_base, compressed) in [
+        (P2pkh, 27, false),
+        (P2pkh, 31, true),
+        (P2shP2wpkh, 35, true),
+        (P2wpkh, 39, true),
+    ] {
+        let address = address_of(0x01, address_type, BitcoinNetwork::Mainnet, compressed);
+        let signature = sign_message(CHALLENGE, 0x01, header_base);
+
+        let result = verify_bitcoin_signature(&signature, CHALLENGE, &address).unwrap();
+
+        assert!(result.is_valid(), "{} should verify", address);
+        assert_eq!(result.derived_address, address);
+    }
+}
+
+/// Test Electrum-style signatures: SegWit addresses signed with the compressed P2PKH header
+#[test]
+fn test_bitcoin_electrum_segwit_signature() {
+    let signature = sign_message(CHALLENGE, 0x02, 31);
+
+    for address_type in [BitcoinAddressType::P2shP2wpkh, BitcoinAddressType::P2wpkh] {
+        let address = address_of(0x02, address_type, BitcoinNetwork::Mainnet, true);
+        let result = verify_bitcoin_signature(&signature, CHALLENGE, &address).unwrap();
+        assert!(result.is_valid(), "{} should verify", address);
+    }
+}
+
+/// Test that a header naming another address type is rejected
+#[test]
+fn test_bitcoin_header_address_type_mismatch() {
+    let address = address_of(
+        0x03,
+        BitcoinAddressType::P2pkh,
+        BitcoinNetwork::Mainnet,
+        true,
+    );
+    let signature = sign_message(CHALLENGE, 0x03, 39);
+
+    let result = verify_bitcoin_signature(&signature, CHALLENGE, &address).unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+}
+
+/// Test that the key compression flag in the header changes the P2PKH address
+#[test]
+fn test_bitcoin_compression_mismatch() {
+    let uncompressed = address_of(
+        0x04,
+        BitcoinAddressType::P2pkh,
+        BitcoinNetwork::Mainnet,
+        false,
+    );
+    let signature = sign_message(CHALLENGE, 0x04, 31);
+
+    let result = verify_bitcoin_signature(&signature, CHALLENGE, &uncompressed).unwrap();
+
+    assert!(!result.is_valid());
+}
+
+/// Test that a BIP-137 signature recovers another key, and fails, for a changed message
+#[test]
+fn test_bitcoin_wrong_challenge() {
+    let address = address_of(
+        0x05,
+        BitcoinAddressType::P2wpkh,
+        BitcoinNetwork::Mainnet,
+        true,
+    );
+    let signature = sign_message("original challenge", 0x05, 39);
+
+    let result = verify_bitcoin_signature(&signature, "tampered challenge", &address).unwrap();
+
+    assert!(!result.is_valid());
+}
+
+/// Test testnet addresses and a long (multi-byte varint) message through the registry
+#[test]
+fn test_bitcoin_provider_testnet_long_message() {
+    let challenge = "x".repeat(300);
+    let address = address_of(
+        0x06,
+        BitcoinAddressType::P2wpkh,
+        BitcoinNetwork::Testnet,
+        true,
+    );
+    assert!(address.starts_with("tb1q"));
+
+    let input = VerificationInput {
+        signature_data: sign_message(&challenge, 0x06, 39),
+        expected_address: address,
+        challenge: Some(challenge),
+        ..Default::default()
+    };
+
+    let wallet_type = WalletType::from_str("btc").unwrap();
+    let provider = get_wallet_provider(wallet_type);
+    let result = provider.verify(&input).unwrap();
+
+    assert_eq!(provider.name(), "Bitcoin");
+    assert!(result.is_valid());
+}
+
+/// Test fixed BIP-137 signatures produced outside this crate
+///
+/// The testnet vector is published in Bitcoin Core's `test/functional/rpc_signmessage.py`.
+/// The others were hashed and signed with an independent RFC 6979 secp256k1
+/// implementation by `tests/vectors/bitcoin.py`, one per header type plus Electrum's
+/// compressed P2PKH header on a SegWit address.
+#[test]
+fn test_bitcoin_independent_vectors() {
+    const CORE_ADDRESS: &str = "mpLQjfK79b7CCV4VMJWEWAj5Mpx8Up5zxB";
+    const CORE_SIGNATURE: &str =
+        "INbVnW4e6PeRmsv2Qgu8NuopvrVjkcxob+sX8OcZG0SALhWybUjzMLPdAsXI46YZGb0KQTRii+wWIQzRpG/U+S0=";
+    const VECTORS: [(&str, &str); 5] = [
+        (
+            "12xbsaRT5NrkQaPL9wGu6vD6b8HszJhUzt",
+            "G/vT1kB3d26lgWOcU341yILf4ptD5yeY/MmY5Vm64ZGOLpcVkHJNYQgyx8sXdNW66q3ag/RLSsZ1kva28XGhQKw=",
+        ),
+        (
+            "15gkRzu92kiMqVHrfKF6bpsVBvpHyzzQcS",
+            "H/vT1kB3d26lgWOcU341yILf4ptD5yeY/MmY5Vm64ZGOLpcVkHJNYQgyx8sXdNW66q3ag/RLSsZ1kva28XGhQKw=",
+        ),
+        (
+            "3K3SXnMjobtbo1HJ2sQ6Hw7a8LBP25faqp",
+            "I/vT1kB3d26lgWOcU341yILf4ptD5yeY/MmY5Vm64ZGOLpcVkHJNYQgyx8sXdNW66q3ag/RLSsZ1kva28XGhQKw=",
+        ),
+        (
+            "bc1qxdj04c4jc0f3cq09sdfgx7fyfhqdjjvf9u2rts",
+            "J/vT1kB3d26lgWOcU341yILf4ptD5yeY/MmY5Vm64ZGOLpcVkHJNYQgyx8sXdNW66q3ag/RLSsZ1kva28XGhQKw=",
+        ),
+        (
+            "bc1qxdj04c4jc0f3cq09sdfgx7fyfhqdjjvf9u2rts",
+            "H/vT1kB3d26lgWOcU341yILf4ptD5yeY/MmY5Vm64ZGOLpcVkHJNYQgyx8sXdNW66q3ag/RLSsZ1kva28XGhQKw=",
+        ),
+    ];
+
+    let core =
+        verify_bitcoin_signature(CORE_SIGNATURE, "This is just a test message", CORE_ADDRESS)
+            .unwrap();
+    assert!(core.is_valid(), "Bitcoin Core vector should verify");
+
+    for (address, signature) in VECTORS {
+        let result = verify_bitcoin_signature(signature, CHALLENGE, address).unwrap();
+        assert!(
+            result.is_valid(),
+            "Independent vector for {} should verify",
+            address
+        );
+        assert_eq!(result.derived_address, address);
+    }
+}
+
+/// Test input validation in the provider
+#[test]
+fn test_bitcoin_provider_validation() {
+    let provider = get_wallet_provider(WalletType::Bitcoin);
+    let address = address_of(
+        0x07,
+        BitcoinAddressType::P2pkh,
+        BitcoinNetwork::Mainnet,
+        true,
+    );
+
+    let missing_challenge = VerificationInput {
+        signature_data: sign_message(CHALLENGE, 0x07, 31),
+        expected_address: address.clone(),
+        ..Default::default()
+    };
+    assert!(provider.verify(&missing_challenge).is_err());
+
+    let bad_address = VerificationInput {
+        signature_data: sign_message(CHALLENGE, 0x07, 31),
+        expected_address: "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651".to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(provider.verify(&bad_address).is_err());
+
+    let bad_signature = VerificationInput {
+        signature_data: hex::encode([0x1f; 65]),
+        expected_address: address,
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(provider.verify(&bad_signature).is_err());
+}
+
+/// Signs the BIP-322 `to_sign` transaction for `message` and a P2WPKH, P2SH-P2WPKH or
+/// P2TR (BIP-86 key path) address of the key, returning (address, signed `to_sign`)
+fn sign_bip322(
+    message: &str,
+    secret_byte: u8,
+    address_type: BitcoinAddressType,
+) -> (String, Transaction) {
+    let secret_key = secp256k1_key(secret_byte);
+    let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key).serialize();
+    let address =
+        BitcoinAddress::from_public_key(address_type, BitcoinNetwork::Mainnet, &public_key)
+            .unwrap();
+
+    let to_spend = to_spend(&address.script_pubkey(), message.as_bytes());
+    let mut to_sign = to_sign(&to_spend);
+
+    if address_type == BitcoinAddressType::P2tr {
+        let (internal_key, _) =
+            Keypair::from_secret_key(SECP256K1, &secret_key).x_only_public_key();
+        let tweak =
+            Scalar::from_be_bytes(tagged_hash("TapTweak", &internal_key.serialize())).unwrap();
+        let keypair = Keypair::from_secret_key(SECP256K1, &secret_key)
+            .add_xonly_tweak(SECP256K1, &tweak)
+            .unwrap();
+
+        let sighash = to_sign
+            .taproot_key_spend_sighash(0, &to_spend.outputs, 0x00)
+            .unwrap();
+        let signature =
+            SECP256K1.sign_schnorr_no_aux_rand(&Message::from_digest(sighash), &keypair);
+        to_sign.inputs[0].witness = vec![signature.serialize().to_vec()];
+    } else {
+        let pubkey_hash = account_id_from_pubkey(&public_key);
+        let script_code = [&[0x76, 0xa9, 0x14], &pubkey_hash[..], &[0x88, 0xac]].concat();
+        let sighash = to_sign.segwit_v0_sighash(0, &script_code, 0);
+        let signature = SECP256K1.sign_ecdsa(&Message::from_digest(sighash), &secret_key);
+
+        let mut der = signature.serialize_der().to_vec();
+        der.push(0x01); // SIGHASH_ALL
+        to_sign.inputs[0].witness = vec![der, public_key.to_vec()];
+        if address_type == BitcoinAddressType::P2shP2wpkh {
+            to_sign.inputs[0].script_sig = [&[0x16, 0x00, 0x14], &pubkey_hash[..]].concat();
+        }
+    }
+
+    (address.to_string(), to_sign)
+}
+
+/// BIP-322 simple-format signature: the `to_sign` witness stack
+fn simple_signature(to_sign: &Transaction) -> String {
+    BASE64.encode(encode_witness(&to_sign.inputs[0].witness))
+}
+
+/// Provider configured for BIP-322 signatures
+fn bip322_provider() -> BitcoinProvider {
+    BitcoinProvider::new().with_signing_scheme(BitcoinSigningScheme::Bip322)
+}
+
+/// Test BIP-322 simple and full signatures for every SegWit address type
+#[test]
+fn test_bip322_simple_and_full_formats() {
+    use BitcoinAddressType::*;
+
+    for address_type in [P2wpkh, P2shP2wpkh, P2tr] {
+        let (address, to_sign) = sign_bip322(CHALLENGE, 0x11, address_type);
+
+        for signature in [
+            simple_signature(&to_sign),
+            BASE64.encode(to_sign.serialize()),
+        ] {
+            let input = VerificationInput {
+                signature_data: signature,
+                expected_address: address.clone(),
+                challenge: Some(CHALLENGE.to_string()),
+                ..Default::default()
+            };
+            let result = bip322_provider().verify(&input).unwrap();
+
+            assert!(result.is_valid(), "{} should verify", address);
+            assert_eq!(result.derived_address, address);
+        }
+    }
+}
+
+/// Test that a BIP-322 signature over a different challenge is rejected
+#[test]
+fn test_bip322_wrong_challenge() {
+    for address_type in [BitcoinAddressType::P2wpkh, BitcoinAddressType::P2tr] {
+        let (address, to_sign) = sign_bip322("original challenge", 0x12, address_type);
+
+        let input = VerificationInput {
+            signature_data: simple_signature(&to_sign),
+            expected_address: address,
+            challenge: Some("tampered challenge".to_string()),
+            ..Default::default()
+        };
+        let result = bip322_provider().verify(&input).unwrap();
+
+        assert!(!result.is_valid());
+        assert!(!result.signature_valid);
+    }
+}
+
+/// Test that a witness from another key does not verify for the expected address
+#[test]
+fn test_bip322_wrong_key() {
+    let (address, _) = sign_bip322(CHALLENGE, 0x13, BitcoinAddressType::P2wpkh);
+    let (_, other) = sign_bip322(CHALLENGE, 0x14, BitcoinAddressType::P2wpkh);
+
+    let input = VerificationInput {
+        signature_data: simple_signature(&other),
+        expected_address: address,
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    let result = bip322_provider().verify(&input).unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+}
+
+/// Test that a full-format transaction for another message is rejected
+#[test]
+fn test_bip322_full_format_wrong_to_spend() {
+    let (address, to_sign) = sign_bip322("another message", 0x15, BitcoinAddressType::P2tr);
+
+    let input = VerificationInput {
+        signature_data: BASE64.encode(to_sign.serialize()),
+        expected_address: address,
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+
+    assert!(bip322_provider().verify(&input).is_err());
+}
+
+/// Test that Taproot requires BIP-322 and that BIP-322 still accepts legacy signatures
+#[test]
+fn test_bip322_scheme_selection() {
+    let (taproot_address, to_sign) = sign_bip322(CHALLENGE, 0x16, BitcoinAddressType::P2tr);
+    let taproot = VerificationInput {
+        signature_data: simple_signature(&to_sign),
+        expected_address: taproot_address,
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(get_wallet_provider(WalletType::Bitcoin)
+        .verify(&taproot)
+        .is_err());
+
+    let legacy = VerificationInput {
+        signature_data: sign_message(CHALLENGE, 0x16, 31),
+        expected_address: address_of(
+            0x16,
+            BitcoinAddressType::P2pkh,
+            BitcoinNetwork::Mainnet,
+            true,
+        ),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(bip322_provider().verify(&legacy).unwrap().is_valid());
+}
@@ -0,0 +1,260 @@
+//! Integration tests for Cardano CIP-30 signData verification
+//!
+//! Wallets sign the CBOR `Sig_structure` of a `COSE_Sign1` whose protected header
+//! carries the signing address, and return the ed25519 key as a `COSE_Key`.
+//! The tests build that structure by hand for payment and stake keys, cover hashed
+//! payloads, and check the key and the signed address against the expected one; the
+//! `tests/vectors/cardano.py` DataSignature comes from an independent CBOR encoder.
+
+mod common;
+
+use common::{ed25519_key, CHALLENGE};
+use ed25519_dalek::{Signer as _, SigningKey};
+use wallet_signature_verify::wallets::cardano::address::blake2b_224;
+use wallet_signature_verify::wallets::cardano::cbor::CborValue;
+use wallet_signature_verify::wallets::cardano::core::CoseSign1;
+use wallet_signature_verify::wallets::{
+    get_wallet_provider, CardanoAddress, CardanoPayloadHashing, CardanoProvider, VerificationInput,
+    WalletProvider, WalletType,
+};
+
+/// Mainnet enterprise (`addr1v...`) and reward (`stake1u...`) addresses of a key
+fn addresses(signing_key: &SigningKey) -> (CardanoAddress, CardanoAddress) {
+    let key_hash = blake2b_224(signing_key.verifying_key().as_bytes());
+    let enterprise = CardanoAddress::from_bytes([&[0x61], &key_hash[..]].concat()).unwrap();
+    let reward = CardanoAddress::from_bytes([&[0xe1], &key_hash[..]].concat()).unwrap();
+    (enterprise, reward)
+}
+
+/// Hex `COSE_Key` of an ed25519 verifying key
+fn cose_key(signing_key: &SigningKey) -> String {
+    let key = CborValue::Map(vec![
+        (CborValue::Int(1), CborValue::Int(1)),
+        (CborValue::Int(3), CborValue::Int(-8)),
+        (CborValue::Int(-1), CborValue::Int(6)),
+        (
+            CborValue::Int(-2),
+            CborValue::Bytes(signing_key.verifying_key().as_bytes().to_vec()),
+        ),
+    ]);
+    hex::encode(key.encode())
+}
+
+/// Signs `message` like CIP-30 `signData` for `address`, returning the hex `COSE_Sign1`
+fn sign_data(
+    signing_key: &SigningKey,
+    address: &CardanoAddress,
+    message: &str,
+    hashed: bool,
+) -> String {
+    let protected = CborValue::Map(vec![
+        (CborValue::Int(1), CborValue::Int(-8)),
+        (
+            CborValue::Text("address".to_string()),
+            CborValue::Bytes(address.bytes.clone()),
+        ),
+    ])
+    .encode();
+    let payload = if hashed {
+        blake2b_224(message.as_bytes()).to_vec()
+    } else {
+        message.as_bytes().to_vec()
+    };
+
+    let unsigned = CborValue::Array(vec![
+        CborValue::Bytes(protected.clone()),
+        CborValue::Map(vec![(
+            CborValue::Text("hashed".to_string()),
+            CborValue::Bool(hashed),
+        )]),
+        CborValue::Bytes(payload.clone()),
+        CborValue::Bytes(vec![0; 64]),
+    ]);
+    let sig_structure = CoseSign1::from_cbor(&unsigned.encode())
+        .unwrap()
+        .sig_structure(&payload);
+    let signature = signing_key.sign(&sig_structure);
+
+    let CborValue::Array(mut items) = unsigned else {
+        unreachable!()
+    };
+    items[3] = CborValue::Bytes(signature.to_bytes().to_vec());
+    hex::encode(CborValue::Array(items).encode())
+}
+
+/// Test a valid signData signature with the COSE_Key passed as public key
+#[test]
+fn test_cardano_valid_signature() {
+    let signing_key = ed25519_key(0x01);
+    let (address, _) = addresses(&signing_key);
+
+    let input = VerificationInput {
+        signature_data: sign_data(&signing_key, &address, CHALLENGE, false),
+        expected_address: address.to_bech32().unwrap(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(&signing_key)),
+        ..Default::default()
+    };
+    let result = CardanoProvider::new().verify(&input).unwrap();
+
+    assert!(result.is_valid(), "Valid CIP-30 signature should verify");
+    assert_eq!(result.derived_address, address.to_bech32().unwrap());
+    assert_eq!(result.found_challenge.as_deref(), Some(CHALLENGE));
+}
+
+/// Test the DataSignature JSON signed by a stake key, through the registry
+#[test]
+fn test_cardano_data_signature_json_stake_address() {
+    let signing_key = ed25519_key(0x02);
+    let (_, reward) = addresses(&signing_key);
+    let data_signature = format!(
+        r#"{{"signature":"{}","key":"{}"}}"#,
+        sign_data(&signing_key, &reward, CHALLENGE, false),
+        cose_key(&signing_key)
+    );
+
+    let input = VerificationInput {
+        signature_data: data_signature,
+        expected_address: reward.to_bech32().unwrap(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    let provider = get_wallet_provider(WalletType::from_str("eternl").unwrap());
+    let result = provider.verify(&input).unwrap();
+
+    assert_eq!(provider.name(), "Cardano");
+    assert!(result.derived_address.starts_with("stake1"));
+    assert!(result.is_valid());
+}
+
+/// Test hashed payloads and the payload hashing options
+#[test]
+fn test_cardano_hashed_payload() {
+    let signing_key = ed25519_key(0x03);
+    let (address, _) = addresses(&signing_key);
+
+    let input = VerificationInput {
+        signature_data: sign_data(&signing_key, &address, CHALLENGE, true),
+        expected_address: address.to_bech32().unwrap(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(&signing_key)),
+        ..Default::default()
+    };
+
+    assert!(CardanoProvider::new().verify(&input).unwrap().is_valid());
+    assert!(CardanoProvider::new()
+        .with_payload_hashing(CardanoPayloadHashing::Hashed)
+        .verify(&input)
+        .unwrap()
+        .is_valid());
+    assert!(CardanoProvider::new()
+        .with_payload_hashing(CardanoPayloadHashing::Unhashed)
+        .verify(&input)
+        .is_err());
+}
+
+/// Test that a COSE_Sign1 carrying another payload fails the challenge check
+#[test]
+fn test_cardano_wrong_challenge() {
+    let signing_key = ed25519_key(0x04);
+    let (address, _) = addresses(&signing_key);
+
+    let input = VerificationInput {
+        signature_data: sign_data(&signing_key, &address, "original challenge", false),
+        expected_address: address.to_bech32().unwrap(),
+        challenge: Some("tampered challenge".to_string()),
+        public_key: Some(cose_key(&signing_key)),
+        ..Default::default()
+    };
+    let result = CardanoProvider::new().verify(&input).unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.challenge_valid);
+}
+
+/// Test that a key or signed address for another account is rejected
+#[test]
+fn test_cardano_address_mismatch() {
+    let signing_key = ed25519_key(0x05);
+    let other_key = ed25519_key(0x06);
+    let (address, _) = addresses(&signing_key);
+    let (other_address, _) = addresses(&other_key);
+
+    // Signed for its own address, presented as another account's
+    let wrong_expected = VerificationInput {
+        signature_data: sign_data(&signing_key, &address, CHALLENGE, false),
+        expected_address: other_address.to_bech32().unwrap(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(&signing_key)),
+        ..Default::default()
+    };
+    let result = CardanoProvider::new().verify(&wrong_expected).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+
+    // Claims another account's address in the protected header
+    let forged_header = VerificationInput {
+        signature_data: sign_data(&signing_key, &other_address, CHALLENGE, false),
+        expected_address: other_address.to_bech32().unwrap(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(&signing_key)),
+        ..Default::default()
+    };
+    let result = CardanoProvider::new().verify(&forged_header).unwrap();
+    assert!(result.signature_valid);
+    assert!(!result.address_valid);
+}
+
+/// Test that a missing COSE_Key and non-Cardano addresses are rejected
+#[test]
+fn test_cardano_provider_validation() {
+    let signing_key = ed25519_key(0x07);
+    let (address, _) = addresses(&signing_key);
+    let signature = sign_data(&signing_key, &address, CHALLENGE, false);
+
+    let missing_key = VerificationInput {
+        signature_data: signature.clone(),
+        expected_address: address.to_bech32().unwrap(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(CardanoProvider::new().verify(&missing_key).is_err());
+
+    let bitcoin_address = VerificationInput {
+        signature_data: signature,
+        expected_address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(&signing_key)),
+        ..Default::default()
+    };
+    assert!(CardanoProvider::new().verify(&bitcoin_address).is_err());
+}
+
+/// Test a fixed DataSignature produced outside this crate
+///
+/// The `COSE_Sign1`, `Sig_structure`, `COSE_Key` and address were encoded with an
+/// independent CBOR, BLAKE2b and bech32 implementation and signed with ed25519 from
+/// pyca/cryptography by `tests/vectors/cardano.py`.
+#[test]
+fn test_cardano_independent_vector() {
+    const ADDRESS: &str = "addr1vy848a5350qs2y2cxeh7e2rn9pjlrhed9nr9kad383fqwysfc2r23";
+    const COSE_SIGN1: &str = "84582aa201276761646472657373581d610f53f691a3c1051158366feca8732865f1df2d2cc65b75b13c520712a166686173686564f458256578616d706c652e636f6d3a313736303730363936303a61666261343265663a6c6f67696e5840be02106147cc639b4f0dcaaee864437b3eaadbc76f785f5e7c0a92e5af6f9b1134f07c37a7fe05e7295e2c04a2d2d900063fbbe73525fb54fd739c344e0a2308";
+    const COSE_KEY: &str =
+        "a4010103272006215820982930635c20e6f2f311bc36f13723bf131238d5217bd02bb6dc07b7efebfa64";
+
+    let input = VerificationInput {
+        signature_data: format!(r#"{{"signature":"{}","key":"{}"}}"#, COSE_SIGN1, COSE_KEY),
+        expected_address: ADDRESS.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    let result = CardanoProvider::new().verify(&input).unwrap();
+    assert!(result.is_valid(), "Independent CIP-30 vector should verify");
+    assert_eq!(result.derived_address, ADDRESS);
+
+    let tampered = VerificationInput {
+        challenge: Some(format!("{}x", CHALLENGE)),
+        ..input
+    };
+    assert!(!CardanoProvider::new().verify(&tampered).unwrap().is_valid());
+}
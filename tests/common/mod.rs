@@ -0,0 +1,43 @@
+//! Keys and fixtures shared by the chain integration tests
+//!
+//! Test keys are fixed `[secret_byte; 32]` patterns, so signatures are reproducible and
+//! every test can pick keys that do not collide with the others in its suite. Each test
+//! crate uses only some of these helpers.
+#![allow(dead_code)]
+
+use ed25519_dalek::SigningKey;
+use secp256k1::SecretKey;
+use wallet_signature_verify::wallets::VerificationInput;
+
+/// Challenge most tests sign, in the `domain:timestamp:nonce:action` layout
+pub const CHALLENGE: &str = "example.com:1760706960:afba42ef:login";
+
+/// ed25519 key whose 32-byte seed repeats `secret_byte`
+pub fn ed25519_key(secret_byte: u8) -> SigningKey {
+    SigningKey::from_bytes(&[secret_byte; 32])
+}
+
+/// secp256k1 secret key whose 32 bytes repeat `secret_byte`
+pub fn secp256k1_key(secret_byte: u8) -> SecretKey {
+    SecretKey::from_slice(&[secret_byte; 32]).unwrap()
+}
+
+/// Verification input for a signature by `address` over `challenge`
+pub fn input(
+    signature: impl Into<String>,
+    address: impl Into<String>,
+    challenge: &str,
+) -> VerificationInput {
+    VerificationInput {
+        signature_data: signature.into(),
+        expected_address: address.into(),
+        challenge: Some(challenge.to_string()),
+        ..Default::default()
+    }
+}
+
+/// P-256 signing key whose 32 bytes repeat `secret_byte`
+#[cfg(any(feature = "move_chains", feature = "webauthn"))]
+pub fn p256_key(secret_byte: u8) -> p256::ecdsa::SigningKey {
+    p256::ecdsa::SigningKey::from_slice(&[secret_byte; 32]).unwrap()
+}
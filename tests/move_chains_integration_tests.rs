@@ -0,0 +1,365 @@
+//! Integration tests for Sui and Aptos message signature verification
+//!
+//! Sui wallets sign the BLAKE2b digest of the intent-prefixed personal message and
+//! return `flag || signature || public key`; Aptos wallets sign the `APTOS\n...` full
+//! message with ed25519. The Sui tests sign with each key scheme (ed25519, secp256k1,
+//! secp256r1) and the Aptos tests with and without the JSON wrapper, where the nonce the
+//! app requested has to match; `tests/vectors/move_chains.py` hashes, serializes and
+//! derives addresses for one of each on its own.
+
+mod common;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use common::{ed25519_key, p256_key, secp256k1_key, CHALLENGE};
+use ed25519_dalek::Signer as _;
+use p256::ecdsa::signature::hazmat::PrehashSigner as _;
+use secp256k1::{Message, PublicKey, SECP256K1};
+use sha2::{Digest, Sha256};
+use wallet_signature_verify::wallets::move_chains::aptos::{aptos_address, AptosFullMessage};
+use wallet_signature_verify::wallets::move_chains::sui::{personal_message_digest, sui_address};
+use wallet_signature_verify::wallets::{
+    get_wallet_provider, AptosProvider, SuiProvider, SuiSignatureScheme, VerificationInput,
+    WalletProvider, WalletType,
+};
+
+/// Signs `message` like `signPersonalMessage` does with a key of the given scheme,
+/// returning (base64 serialized signature, address)
+fn sui_sign(message: &str, scheme: SuiSignatureScheme, secret_byte: u8) -> (String, String) {
+    let digest = personal_message_digest(message.as_bytes());
+    let (signature, public_key): ([u8; 64], Vec<u8>) = match scheme {
+        SuiSignatureScheme::Ed25519 => {
+            let key = ed25519_key(secret_byte);
+            (
+                key.sign(&digest).to_bytes(),
+                key.verifying_key().to_bytes().to_vec(),
+            )
+        }
+        SuiSignatureScheme::Secp256k1 => {
+            let key = secp256k1_key(secret_byte);
+            let hash: [u8; 32] = Sha256::digest(digest).into();
+            let signature = SECP256K1.sign_ecdsa(&Message::from_digest(hash), &key);
+            (
+                signature.serialize_compact(),
+                PublicKey::from_secret_key(SECP256K1, &key)
+                    .serialize()
+                    .to_vec(),
+            )
+        }
+        SuiSignatureScheme::Secp256r1 => {
+            let key = p256_key(secret_byte);
+            let hash: [u8; 32] = Sha256::digest(digest).into();
+            let signature: p256::ecdsa::Signature = key.sign_prehash(&hash).unwrap();
+            let signature = signature.normalize_s().unwrap_or(signature);
+            (
+                signature.to_bytes().into(),
+                key.verifying_key()
+                    .to_encoded_point(true)
+                    .as_bytes()
+                    .to_vec(),
+            )
+        }
+    };
+
+    let serialized = [&[scheme.flag()][..], &signature, &public_key].concat();
+    (BASE64.encode(serialized), sui_address(scheme, &public_key))
+}
+
+/// Signs the Aptos full message for `message` and `nonce`, returning
+/// (hex signature, full message, hex public key, address)
+fn aptos_sign(message: &str, nonce: &str, secret_byte: u8) -> (String, String, String, String) {
+    let key = ed25519_key(secret_byte);
+    let public_key = key.verifying_key().to_bytes();
+    let full_message = AptosFullMessage {
+        message: message.to_string(),
+        nonce: nonce.to_string(),
+        ..Default::default()
+    }
+    .build();
+
+    (
+        format!(
+            "0x{}",
+            hex::encode(key.sign(full_message.as_bytes()).to_bytes())
+        ),
+        full_message,
+        format!("0x{}", hex::encode(public_key)),
+        aptos_address(&public_key),
+    )
+}
+
+/// Test valid Sui signatures for each supported key scheme
+#[test]
+fn test_sui_valid_signatures() {
+    for (scheme, secret_byte) in [
+        (SuiSignatureScheme::Ed25519, 0x01),
+        (SuiSignatureScheme::Secp256k1, 0x02),
+        (SuiSignatureScheme::Secp256r1, 0x03),
+    ] {
+        let (signature, address) = sui_sign(CHALLENGE, scheme, secret_byte);
+
+        let input = VerificationInput {
+            signature_data: signature,
+            expected_address: address.clone(),
+            challenge: Some(CHALLENGE.to_string()),
+            ..Default::default()
+        };
+        let result = SuiProvider.verify(&input).unwrap();
+
+        assert!(
+            result.is_valid(),
+            "Valid {:?} signature should verify",
+            scheme
+        );
+        assert_eq!(result.derived_address, address);
+    }
+}
+
+/// Test the signPersonalMessage JSON output through the registry, and that the signed
+/// bytes must be the challenge
+#[test]
+fn test_sui_personal_message_json() {
+    let (signature, address) = sui_sign(CHALLENGE, SuiSignatureScheme::Ed25519, 0x04);
+    let json = |bytes: &str| {
+        format!(
+            r#"{{"bytes":"{}","signature":"{}"}}"#,
+            BASE64.encode(bytes),
+            signature
+        )
+    };
+
+    let provider = get_wallet_provider(WalletType::from_str("suiet").unwrap());
+    assert_eq!(provider.name(), "Sui");
+
+    let input = VerificationInput {
+        signature_data: json(CHALLENGE),
+        expected_address: address.clone(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(provider.verify(&input).unwrap().is_valid());
+
+    let other_bytes = VerificationInput {
+        signature_data: json("other message"),
+        expected_address: address,
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    let result = provider.verify(&other_bytes).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.challenge_valid);
+}
+
+/// Test that Sui signatures over another challenge or by another key are rejected
+#[test]
+fn test_sui_wrong_challenge_and_address() {
+    let (signature, address) = sui_sign("original challenge", SuiSignatureScheme::Ed25519, 0x05);
+    let (_, other_address) = sui_sign(CHALLENGE, SuiSignatureScheme::Secp256k1, 0x06);
+
+    let tampered = VerificationInput {
+        signature_data: signature.clone(),
+        expected_address: address,
+        challenge: Some("tampered challenge".to_string()),
+        ..Default::default()
+    };
+    let result = SuiProvider.verify(&tampered).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.signature_valid);
+
+    let wrong_address = VerificationInput {
+        signature_data: signature,
+        expected_address: other_address,
+        challenge: Some("original challenge".to_string()),
+        ..Default::default()
+    };
+    let result = SuiProvider.verify(&wrong_address).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+    assert!(result.signature_valid);
+}
+
+/// Test a valid Aptos signMessage JSON output, which needs the requested nonce
+#[test]
+fn test_aptos_valid_signature() {
+    let (signature, full_message, public_key, address) = aptos_sign(CHALLENGE, "8b1a2c", 0x07);
+    let json = serde_json::json!({
+        "fullMessage": full_message,
+        "signature": signature,
+        "publicKey": public_key,
+    });
+
+    let input = VerificationInput {
+        signature_data: json.to_string(),
+        expected_address: address.clone(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    let result = AptosProvider::new()
+        .with_nonce("8b1a2c")
+        .verify(&input)
+        .unwrap();
+
+    assert!(result.is_valid(), "Valid Aptos signature should verify");
+    assert_eq!(result.derived_address, address);
+
+    // Without the nonce any old signMessage output would be accepted
+    assert!(AptosProvider::new().verify(&input).is_err());
+    assert!(WalletType::from_str("petra").is_err());
+}
+
+/// Test a bare Aptos signature, which needs the nonce and public key, including a
+/// full message with the `address` line
+#[test]
+fn test_aptos_bare_signature() {
+    let key = ed25519_key(0x08);
+    let public_key = key.verifying_key().to_bytes();
+    let address = aptos_address(&public_key);
+    let full_message = AptosFullMessage {
+        address: Some(address.clone()),
+        message: CHALLENGE.to_string(),
+        nonce: "42".to_string(),
+        ..Default::default()
+    }
+    .build();
+
+    let input = VerificationInput {
+        signature_data: hex::encode(key.sign(full_message.as_bytes()).to_bytes()),
+        expected_address: address,
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(hex::encode(public_key)),
+        ..Default::default()
+    };
+
+    assert!(AptosProvider::new()
+        .with_nonce("42")
+        .verify(&input)
+        .unwrap()
+        .is_valid());
+    assert!(!AptosProvider::new()
+        .with_nonce("43")
+        .verify(&input)
+        .unwrap()
+        .is_valid());
+    assert!(AptosProvider::new().verify(&input).is_err());
+}
+
+/// Test that Aptos signatures over another challenge, nonce or key are rejected
+#[test]
+fn test_aptos_wrong_challenge_nonce_and_address() {
+    let (signature, full_message, public_key, address) = aptos_sign(CHALLENGE, "42", 0x09);
+    let (_, _, _, other_address) = aptos_sign(CHALLENGE, "42", 0x0a);
+    let input = |expected_address: &str, challenge: &str| VerificationInput {
+        signature_data: serde_json::json!({
+            "fullMessage": full_message,
+            "signature": signature,
+        })
+        .to_string(),
+        expected_address: expected_address.to_string(),
+        challenge: Some(challenge.to_string()),
+        public_key: Some(public_key.clone()),
+        ..Default::default()
+    };
+
+    let result = AptosProvider::new()
+        .with_nonce("42")
+        .verify(&input(&address, "tampered challenge"))
+        .unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.challenge_valid);
+
+    let result = AptosProvider::new()
+        .with_nonce("43")
+        .verify(&input(&address, CHALLENGE))
+        .unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.challenge_valid);
+
+    let result = AptosProvider::new()
+        .with_nonce("42")
+        .verify(&input(&other_address, CHALLENGE))
+        .unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+}
+
+/// Test that malformed addresses and missing challenges are rejected
+#[test]
+fn test_move_chains_provider_validation() {
+    let (signature, address) = sui_sign(CHALLENGE, SuiSignatureScheme::Ed25519, 0x0b);
+
+    let no_prefix = VerificationInput {
+        signature_data: signature.clone(),
+        expected_address: address.trim_start_matches("0x").to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(SuiProvider.verify(&no_prefix).is_err());
+    assert!(AptosProvider::new()
+        .with_nonce("42")
+        .verify(&no_prefix)
+        .is_err());
+
+    let missing_challenge = VerificationInput {
+        signature_data: signature,
+        expected_address: address,
+        ..Default::default()
+    };
+    assert!(SuiProvider.verify(&missing_challenge).is_err());
+    assert!(AptosProvider::new()
+        .with_nonce("42")
+        .verify(&missing_challenge)
+        .is_err());
+}
+
+/// Test an ed25519 signPersonalMessage signature produced outside this crate by
+/// `tests/vectors/move_chains.py`
+#[test]
+fn test_sui_independent_vector() {
+    const ADDRESS: &str = "0x49e538f9edc1080f3b4a3b651c57c4b422ee3e359186cc4191248eaf33489871";
+    const SIGNATURE: &str = "ALnZi+MlfoSk4KU6LUV9etF2rpo+wj28Sh7jmvR5NNdodrQlyl0IV0y+OwHVioZNo46lh8NeKVKCsSzP8wiM8AV6saNqgbxIUVeL7RMH7mRorJnzDdZEjAGuTbuIuucTJg==";
+
+    let input = VerificationInput {
+        signature_data: SIGNATURE.to_string(),
+        expected_address: ADDRESS.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    let result = SuiProvider.verify(&input).unwrap();
+    assert!(result.is_valid(), "Independent Sui vector should verify");
+    assert_eq!(result.derived_address, ADDRESS);
+
+    let tampered = VerificationInput {
+        challenge: Some(format!("{}x", CHALLENGE)),
+        ..input
+    };
+    assert!(!SuiProvider.verify(&tampered).unwrap().is_valid());
+}
+
+/// Test an Aptos signMessage output with application and chain ID produced outside
+/// this crate by `tests/vectors/move_chains.py`
+#[test]
+fn test_aptos_independent_vector() {
+    const ADDRESS: &str = "0xd8819475e8d722edc9c11366698e638f96e8b8286aeb408af5e6c1359360456f";
+    const FULL_MESSAGE: &str = "APTOS\napplication: https://example.com\nchainId: 1\nmessage: example.com:1760706960:afba42ef:login\nnonce: 8b1a2c";
+    const SIGNATURE: &str = "0x7100dad08c1e7f98352a1014df348214202953e51868974cff4b73d91027b2fa12527582b8b11163a2866e30703f45934b66c95c37c6134467c0008814933905";
+    const PUBLIC_KEY: &str = "0x5021c07ae83b341dd39f9db442b52471ebca270e59b51b853102a7fa526fb0bb";
+
+    let input = |full_message: &str| VerificationInput {
+        signature_data: serde_json::json!({
+            "fullMessage": full_message,
+            "signature": SIGNATURE,
+            "publicKey": PUBLIC_KEY,
+        })
+        .to_string(),
+        expected_address: ADDRESS.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+
+    let provider = || AptosProvider::new().with_nonce("8b1a2c");
+    let result = provider().verify(&input(FULL_MESSAGE)).unwrap();
+    assert!(result.is_valid(), "Independent Aptos vector should verify");
+    assert_eq!(result.derived_address, ADDRESS);
+
+    let other_chain = FULL_MESSAGE.replace("chainId: 1", "chainId: 2");
+    assert!(!provider().verify(&input(&other_chain)).unwrap().is_valid());
+}
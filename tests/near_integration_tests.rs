@@ -0,0 +1,240 @@
+//! Integration tests for NEAR NEP-413 signMessage verification
+//!
+//! Wallets sign `SHA-256(borsh(2^31 + 413) || borsh(payload))` with an ed25519 access
+//! key of the account. Implicit accounts are checked offline against their hex key;
+//! named accounts go through an in-memory access-key lookup standing in for RPC. The
+//! tests also hold the nonce and recipient to the requested ones, and pin a payload
+//! Borsh-encoded by `tests/vectors/near.py`.
+
+mod common;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use common::{ed25519_key, input, CHALLENGE};
+use ed25519_dalek::Signer as _;
+use std::sync::Arc;
+use wallet_signature_verify::wallets::near::core::format_near_public_key;
+use wallet_signature_verify::wallets::near::Nep413Payload;
+use wallet_signature_verify::wallets::{
+    get_wallet_provider, AccessKeyPermission, InMemoryAccessKeys, NearProvider, WalletProvider,
+    WalletType,
+};
+
+const RECIPIENT: &str = "example.com";
+const NONCE: [u8; 32] = [0x5a; 32];
+
+/// Signs the NEP-413 payload for `message`, returning the `signMessage` JSON output
+/// for `account_id` and the `ed25519:` public key
+fn sign_message(message: &str, secret_byte: u8, account_id: Option<&str>) -> (String, String) {
+    let signing_key = ed25519_key(secret_byte);
+    let public_key = format_near_public_key(signing_key.verifying_key().as_bytes());
+    let account_id = account_id
+        .map(str::to_string)
+        .unwrap_or_else(|| hex::encode(signing_key.verifying_key().as_bytes()));
+
+    let payload = Nep413Payload {
+        message: message.to_string(),
+        nonce: NONCE,
+        recipient: RECIPIENT.to_string(),
+        callback_url: None,
+    };
+    let signature = signing_key.sign(&payload.hash());
+
+    let output = format!(
+        r#"{{"accountId":"{}","publicKey":"{}","signature":"{}"}}"#,
+        account_id,
+        public_key,
+        BASE64.encode(signature.to_bytes())
+    );
+    (output, public_key)
+}
+
+/// Provider configured with the nonce and recipient the app requested
+fn provider() -> NearProvider {
+    NearProvider::new()
+        .with_recipient(RECIPIENT)
+        .with_nonce(NONCE)
+}
+
+/// Test a valid signature by an implicit account, checked offline
+#[test]
+fn test_near_implicit_account() {
+    let signing_key = ed25519_key(0x01);
+    let account_id = hex::encode(signing_key.verifying_key().as_bytes());
+    let (output, _) = sign_message(CHALLENGE, 0x01, None);
+
+    let result = provider()
+        .verify(&input(output, &account_id, CHALLENGE))
+        .unwrap();
+
+    assert!(result.is_valid(), "Valid NEP-413 signature should verify");
+    assert_eq!(result.derived_address, account_id);
+}
+
+/// Test a named account through the pluggable access-key lookup
+#[test]
+fn test_near_named_account_access_keys() {
+    let (output, public_key) = sign_message(CHALLENGE, 0x02, Some("alice.near"));
+    let access_keys = Arc::new(InMemoryAccessKeys::new());
+
+    // Unknown key: the signature is valid but the account is not proven
+    let with_lookup = provider().with_access_key_lookup(access_keys.clone());
+    let result = with_lookup
+        .verify(&input(output.clone(), "alice.near", CHALLENGE))
+        .unwrap();
+    assert!(result.signature_valid);
+    assert!(!result.address_valid);
+
+    // Function-call keys cannot sign in
+    access_keys
+        .add_key("alice.near", &public_key, AccessKeyPermission::FunctionCall)
+        .unwrap();
+    let result = with_lookup
+        .verify(&input(output.clone(), "alice.near", CHALLENGE))
+        .unwrap();
+    assert!(!result.is_valid());
+
+    access_keys
+        .add_key("alice.near", &public_key, AccessKeyPermission::FullAccess)
+        .unwrap();
+    let result = with_lookup
+        .verify(&input(output, "alice.near", CHALLENGE))
+        .unwrap();
+    assert!(result.is_valid());
+}
+
+/// Test that a signature over a different challenge, nonce or recipient is rejected
+#[test]
+fn test_near_wrong_payload() {
+    let signing_key = ed25519_key(0x03);
+    let account_id = hex::encode(signing_key.verifying_key().as_bytes());
+    let (output, _) = sign_message("original challenge", 0x03, None);
+
+    let result = provider()
+        .verify(&input(output.clone(), &account_id, "tampered challenge"))
+        .unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.signature_valid);
+
+    let other_nonce = NearProvider::new()
+        .with_recipient(RECIPIENT)
+        .with_nonce([0x00; 32]);
+    assert!(
+        !other_nonce
+            .verify(&input(output.clone(), &account_id, "original challenge"))
+            .unwrap()
+            .signature_valid
+    );
+
+    let other_recipient = NearProvider::new()
+        .with_recipient("evil.example")
+        .with_nonce(NONCE);
+    assert!(
+        !other_recipient
+            .verify(&input(output, &account_id, "original challenge"))
+            .unwrap()
+            .signature_valid
+    );
+}
+
+/// Test that nonce and recipient sent back with the signature must match the requested ones
+#[test]
+fn test_near_echoed_payload_mismatch() {
+    let signing_key = ed25519_key(0x07);
+    let account_id = hex::encode(signing_key.verifying_key().as_bytes());
+    let (output, _) = sign_message(CHALLENGE, 0x07, None);
+
+    let mut echoed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    echoed["nonce"] = hex::encode(NONCE).into();
+    echoed["recipient"] = RECIPIENT.into();
+    let result = provider()
+        .verify(&input(echoed.to_string(), &account_id, CHALLENGE))
+        .unwrap();
+    assert!(result.is_valid());
+    assert_eq!(result.recipient.as_deref(), Some(RECIPIENT));
+
+    echoed["recipient"] = "evil.example".into();
+    let result = provider()
+        .verify(&input(echoed.to_string(), &account_id, CHALLENGE))
+        .unwrap();
+    assert!(result.signature_valid);
+    assert!(!result.challenge_valid);
+
+    echoed["recipient"] = RECIPIENT.into();
+    echoed["nonce"] = hex::encode([0x00; 32]).into();
+    let result = provider()
+        .verify(&input(echoed.to_string(), &account_id, CHALLENGE))
+        .unwrap();
+    assert!(!result.challenge_valid);
+}
+
+/// Test that a key belonging to another implicit account is rejected, through the registry
+#[test]
+fn test_near_wrong_account() {
+    let (output, _) = sign_message(CHALLENGE, 0x04, None);
+    let other_account = hex::encode(ed25519_key(0x05).verifying_key().as_bytes());
+    // Apps may send the requested nonce and recipient back with the wallet output
+    let mut output: serde_json::Value = serde_json::from_str(&output).unwrap();
+    output["nonce"] = hex::encode(NONCE).into();
+    output["recipient"] = RECIPIENT.into();
+    let output = output.to_string();
+
+    let provider = get_wallet_provider(WalletType::from_str("near").unwrap());
+    let result = provider
+        .verify(&input(output, &other_account, CHALLENGE))
+        .unwrap();
+
+    assert_eq!(provider.name(), "NEAR");
+    assert!(result.signature_valid);
+    assert!(!result.address_valid);
+    // Without a configured recipient the signed one is reported for the caller to check
+    assert_eq!(result.recipient.as_deref(), Some(RECIPIENT));
+}
+
+/// Test that missing payload fields and invalid account IDs are rejected
+#[test]
+fn test_near_provider_validation() {
+    let (output, _) = sign_message(CHALLENGE, 0x06, Some("bob.near"));
+
+    let missing_nonce = NearProvider::new().with_recipient(RECIPIENT);
+    assert!(missing_nonce
+        .verify(&input(output.clone(), "bob.near", CHALLENGE))
+        .is_err());
+
+    assert!(provider()
+        .verify(&input(output.clone(), "Bob.NEAR", CHALLENGE))
+        .is_err());
+
+    // A bare signature needs the public key alongside
+    let bare_signature = serde_json::from_str::<serde_json::Value>(&output).unwrap()["signature"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(provider()
+        .verify(&input(bare_signature, "bob.near", CHALLENGE))
+        .is_err());
+}
+
+/// Test a signMessage output for an implicit account, produced outside this crate by
+/// `tests/vectors/near.py`
+#[test]
+fn test_near_independent_vector() {
+    const ACCOUNT_ID: &str = "ea5139a82824729a35bff9c60a268c03b2abedd8556e126b5a46bda400ad531e";
+    const OUTPUT: &str = r#"{"accountId":"ea5139a82824729a35bff9c60a268c03b2abedd8556e126b5a46bda400ad531e","publicKey":"ed25519:GmgEmC55CpzWz46xQB73pWhSgJrsdUkpQKjX7JzXieff","signature":"qDpeX3HmCU2q2zXdDLnigOnCz6KH244H73QbWfUVc2mnZowzr4wYAMUd7sIDUNlGrofbdFNrNfJ9xO/ETY3DDQ=="}"#;
+
+    let result = provider()
+        .verify(&input(OUTPUT.to_string(), ACCOUNT_ID, CHALLENGE))
+        .unwrap();
+    assert!(
+        result.is_valid(),
+        "Independent NEP-413 vector should verify"
+    );
+    assert_eq!(result.derived_address, ACCOUNT_ID);
+    assert_eq!(result.recipient.as_deref(), Some(RECIPIENT));
+
+    let other_recipient = NearProvider::new()
+        .with_recipient("other.example")
+        .with_nonce(NONCE)
+        .verify(&input(OUTPUT.to_string(), ACCOUNT_ID, CHALLENGE))
+        .unwrap();
+    assert!(!other_recipient.is_valid());
+}
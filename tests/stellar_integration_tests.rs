@@ -0,0 +1,364 @@
+//! Integration tests for Stellar SEP-10 challenge verification
+//!
+//! The server builds a ManageData challenge transaction and signs it, the client adds
+//! its signature, and the verifier checks both along with the SEP-10 rules. The tests
+//! assemble those envelopes with the crate's XDR types and break one rule at a time
+//! (server key, home and web auth domains, time bounds, `client_domain`, extra
+//! operations and signers); `tests/vectors/stellar.py` serializes its envelopes itself.
+
+mod common;
+
+use common::ed25519_key;
+use ed25519_dalek::{Signer as _, SigningKey};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wallet_signature_verify::wallets::stellar::sep10::TESTNET_NETWORK_PASSPHRASE;
+use wallet_signature_verify::wallets::stellar::strkey::encode_account_id;
+use wallet_signature_verify::wallets::stellar::xdr::{
+    DecoratedSignature, Memo, Operation, OperationBody, TimeBounds, Transaction,
+    TransactionEnvelope,
+};
+use wallet_signature_verify::wallets::{
+    StellarProvider, VerificationInput, WalletProvider, WalletType,
+};
+
+const HOME_DOMAIN: &str = "example.com";
+const WEB_AUTH_DOMAIN: &str = "auth.example.com";
+const ISSUED_AT: u64 = 1_760_706_960;
+
+/// 64-byte nonce of the first operation (base64 of 48 random bytes)
+const NONCE: &str = "b5pJ0i0ouXjnuFGcZsjYRQaPNp4wjIbu3XEvQxAWZaO0S2SqvjTyWbmJLWnjD+v2";
+
+fn server_key() -> SigningKey {
+    ed25519_key(0x01)
+}
+
+/// `G...` address of a key
+fn address(key: &SigningKey) -> String {
+    encode_account_id(key.verifying_key().as_bytes())
+}
+
+/// Adds `key`'s signature to the envelope
+fn sign(envelope: &mut TransactionEnvelope, key: &SigningKey, network_passphrase: &str) {
+    let hash = envelope.signature_base_hash(network_passphrase);
+    envelope.signatures.push(DecoratedSignature {
+        hint: key.verifying_key().as_bytes()[28..].try_into().unwrap(),
+        signature: key.sign(&hash).to_bytes().to_vec(),
+    });
+}
+
+/// Builds the server-signed challenge for `client`, as a SEP-10 server would
+fn challenge(client: &SigningKey, home_domain: &str) -> TransactionEnvelope {
+    challenge_with_operations(client, home_domain, Vec::new())
+}
+
+/// Builds the server-signed challenge for `client` with extra operations at the end
+fn challenge_with_operations(
+    client: &SigningKey,
+    home_domain: &str,
+    extra_operations: Vec<Operation>,
+) -> TransactionEnvelope {
+    let server = server_key();
+    let transaction = Transaction {
+        source_account: server.verifying_key().to_bytes(),
+        fee: 200,
+        seq_num: 0,
+        time_bounds: Some(TimeBounds {
+            min_time: ISSUED_AT,
+            max_time: ISSUED_AT + 900,
+        }),
+        memo: Memo::None,
+        operations: vec![
+            Operation {
+                source_account: Some(client.verifying_key().to_bytes()),
+                body: OperationBody::ManageData {
+                    name: format!("{} auth", home_domain),
+                    value: Some(NONCE.as_bytes().to_vec()),
+                },
+            },
+            Operation {
+                source_account: Some(server.verifying_key().to_bytes()),
+                body: OperationBody::ManageData {
+                    name: "web_auth_domain".to_string(),
+                    value: Some(WEB_AUTH_DOMAIN.as_bytes().to_vec()),
+                },
+            },
+        ]
+        .into_iter()
+        .chain(extra_operations)
+        .collect(),
+    };
+
+    let mut envelope = TransactionEnvelope::new(transaction);
+    sign(&mut envelope, &server, TESTNET_NETWORK_PASSPHRASE);
+    envelope
+}
+
+/// Provider with the server's rules, one minute after the challenge was issued
+fn provider() -> StellarProvider {
+    StellarProvider::new()
+        .with_server_account(&address(&server_key()))
+        .with_home_domain(HOME_DOMAIN)
+        .with_web_auth_domain(WEB_AUTH_DOMAIN)
+        .with_network_passphrase(TESTNET_NETWORK_PASSPHRASE)
+        .with_time(UNIX_EPOCH + Duration::from_secs(ISSUED_AT + 60))
+}
+
+/// Builds the verification input for a signed challenge
+fn input(envelope: &TransactionEnvelope, address: String) -> VerificationInput {
+    common::input(envelope.to_base64(), address, NONCE)
+}
+
+/// Test a challenge signed by the server and the client
+#[test]
+fn test_stellar_valid_challenge() {
+    let client = ed25519_key(0x02);
+    let mut envelope = challenge(&client, HOME_DOMAIN);
+    sign(&mut envelope, &client, TESTNET_NETWORK_PASSPHRASE);
+
+    let result = provider()
+        .verify(&input(&envelope, address(&client)))
+        .unwrap();
+
+    assert!(result.is_valid(), "Valid SEP-10 challenge should verify");
+    assert_eq!(result.derived_address, address(&client));
+    assert_eq!(result.found_challenge.as_deref(), Some(NONCE));
+}
+
+/// Test that a challenge the client did not sign, or signed for another network, is rejected
+#[test]
+fn test_stellar_missing_client_signature() {
+    let client = ed25519_key(0x03);
+    let unsigned = challenge(&client, HOME_DOMAIN);
+
+    let result = provider()
+        .verify(&input(&unsigned, address(&client)))
+        .unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.signature_valid);
+
+    let mut wrong_network = challenge(&client, HOME_DOMAIN);
+    sign(
+        &mut wrong_network,
+        &client,
+        "Public Global Stellar Network ; September 2015",
+    );
+    let result = provider()
+        .verify(&input(&wrong_network, address(&client)))
+        .unwrap();
+    assert!(!result.signature_valid);
+}
+
+/// Test that the SEP-10 rules are enforced: server account, time bounds, home domain
+#[test]
+fn test_stellar_rules() {
+    let client = ed25519_key(0x04);
+    let mut envelope = challenge(&client, HOME_DOMAIN);
+    sign(&mut envelope, &client, TESTNET_NETWORK_PASSPHRASE);
+    let input = input(&envelope, address(&client));
+
+    let other_server = provider().with_server_account(&address(&ed25519_key(0x09)));
+    let result = other_server.verify(&input).unwrap();
+    assert!(!result.challenge_valid);
+    assert!(result.signature_valid);
+
+    let expired = provider().with_time(UNIX_EPOCH + Duration::from_secs(ISSUED_AT + 901));
+    assert!(!expired.verify(&input).unwrap().challenge_valid);
+
+    let other_domain = provider().with_home_domain("evil.example");
+    assert!(!other_domain.verify(&input).unwrap().challenge_valid);
+
+    let other_web_auth_domain = provider().with_web_auth_domain("evil.example");
+    assert!(
+        !other_web_auth_domain
+            .verify(&input)
+            .unwrap()
+            .challenge_valid
+    );
+
+    // The current time is far outside the challenge window
+    let now = provider().with_time(SystemTime::now());
+    assert!(!now.verify(&input).unwrap().challenge_valid);
+
+    // Without a server account or home domain the source account can't be trusted
+    let unconfigured = StellarProvider::new()
+        .with_network_passphrase(TESTNET_NETWORK_PASSPHRASE)
+        .with_time(UNIX_EPOCH + Duration::from_secs(ISSUED_AT + 60));
+    let result = unconfigured.verify(&input).unwrap();
+    assert!(!result.challenge_valid);
+    assert!(result.signature_valid);
+    let result = unconfigured
+        .with_server_account(&address(&server_key()))
+        .verify(&input)
+        .unwrap();
+    assert!(!result.challenge_valid);
+}
+
+/// Test a challenge with a `client_domain` operation, which the client domain's signing
+/// key must sign as well
+#[test]
+fn test_stellar_client_domain() {
+    let client = ed25519_key(0x0a);
+    let client_domain = ed25519_key(0x0b);
+    let client_domain_operation = Operation {
+        source_account: Some(client_domain.verifying_key().to_bytes()),
+        body: OperationBody::ManageData {
+            name: "client_domain".to_string(),
+            value: Some(b"wallet.example".to_vec()),
+        },
+    };
+    let mut envelope =
+        challenge_with_operations(&client, HOME_DOMAIN, vec![client_domain_operation]);
+    sign(&mut envelope, &client, TESTNET_NETWORK_PASSPHRASE);
+
+    // The client domain account has not signed yet
+    let result = provider()
+        .verify(&input(&envelope, address(&client)))
+        .unwrap();
+    assert!(!result.challenge_valid);
+    assert!(result.signature_valid);
+
+    sign(&mut envelope, &client_domain, TESTNET_NETWORK_PASSPHRASE);
+    let result = provider()
+        .verify(&input(&envelope, address(&client)))
+        .unwrap();
+    assert!(
+        result.is_valid(),
+        "Challenge signed by all three should verify"
+    );
+
+    // Any other signer invalidates the challenge
+    sign(
+        &mut envelope,
+        &ed25519_key(0x0c),
+        TESTNET_NETWORK_PASSPHRASE,
+    );
+    let result = provider()
+        .verify(&input(&envelope, address(&client)))
+        .unwrap();
+    assert!(!result.challenge_valid);
+    assert!(result.signature_valid);
+}
+
+/// Test that an operation by another account is only allowed for `client_domain`, and
+/// that unrecognized or invalid signatures are rejected
+#[test]
+fn test_stellar_extra_operations_and_signatures() {
+    let client = ed25519_key(0x0d);
+    let other = ed25519_key(0x0e);
+    let other_operation = Operation {
+        source_account: Some(other.verifying_key().to_bytes()),
+        body: OperationBody::ManageData {
+            name: "other".to_string(),
+            value: None,
+        },
+    };
+    let mut envelope = challenge_with_operations(&client, HOME_DOMAIN, vec![other_operation]);
+    sign(&mut envelope, &client, TESTNET_NETWORK_PASSPHRASE);
+    sign(&mut envelope, &other, TESTNET_NETWORK_PASSPHRASE);
+    let result = provider()
+        .verify(&input(&envelope, address(&client)))
+        .unwrap();
+    assert!(!result.challenge_valid);
+
+    // A corrupted copy of the client signature is not by any known signer
+    let mut envelope = challenge(&client, HOME_DOMAIN);
+    sign(&mut envelope, &client, TESTNET_NETWORK_PASSPHRASE);
+    let mut corrupted = envelope.signatures[1].clone();
+    corrupted.signature[0] ^= 1;
+    envelope.signatures.push(corrupted);
+    let result = provider()
+        .verify(&input(&envelope, address(&client)))
+        .unwrap();
+    assert!(!result.challenge_valid);
+    assert!(result.signature_valid);
+}
+
+/// Test that a challenge without a valid server signature is rejected
+#[test]
+fn test_stellar_forged_server_signature() {
+    let client = ed25519_key(0x05);
+    let mut envelope = challenge(&client, HOME_DOMAIN);
+    // Replace the server signature with one by the client
+    envelope.signatures.clear();
+    sign(&mut envelope, &client, TESTNET_NETWORK_PASSPHRASE);
+
+    let result = provider()
+        .verify(&input(&envelope, address(&client)))
+        .unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.challenge_valid);
+}
+
+/// Test that another account, a different nonce and malformed envelopes are rejected
+#[test]
+fn test_stellar_wrong_account_and_nonce() {
+    let client = ed25519_key(0x06);
+    let other = ed25519_key(0x07);
+    let mut envelope = challenge(&client, HOME_DOMAIN);
+    sign(&mut envelope, &client, TESTNET_NETWORK_PASSPHRASE);
+
+    let result = provider()
+        .verify(&input(&envelope, address(&other)))
+        .unwrap();
+    assert!(!result.address_valid);
+
+    let mut other_nonce = input(&envelope, address(&client));
+    other_nonce.challenge = Some("another nonce".to_string());
+    assert!(!provider().verify(&other_nonce).unwrap().challenge_valid);
+
+    let mut malformed = input(&envelope, address(&client));
+    malformed.signature_data = "AAAAAgAAAAA=".to_string();
+    assert!(provider().verify(&malformed).is_err());
+
+    // Stellar needs the server's rules, so it is library-only
+    assert!(WalletType::from_str("freighter").is_err());
+}
+
+/// Test testnet challenges produced and signed outside this crate by
+/// `tests/vectors/stellar.py`, one of them with a `client_domain` operation
+#[test]
+fn test_stellar_independent_vector() {
+    const SERVER_ACCOUNT: &str = "GCUKI64HNBMSLW3JKY7P2RYKY476QC2Z3R5ZGTLLW26GGBORU7QRQZEN";
+    const CLIENT_ACCOUNT: &str = "GCOUA6QL5LTXSB2HSOEYPZNDGDC3QEAA7S2GZSFBZNNHN3HGIEL36DPW";
+    const ENVELOPE: &str = "AAAAAgAAAACopHuHaFkl22lWPv1HCsc/6AtZ3HuTTWu2vGMF0afhGAAAAMgAAAAAAAAAAAAAAAEAAAAAaPJBkAAAAABo8kUUAAAAAAAAAAIAAAABAAAAAJ1Aegvq53kHR5OJh+WjMMW4EAD8tGzIoctaduzmQRe/AAAACgAAABBleGFtcGxlLmNvbSBhdXRoAAAAAQAAAEBiNXBKMGkwb3VYam51RkdjWnNqWVJRYVBOcDR3aklidTNYRXZReEFXWmFPMFMyU3F2alR5V2JtSkxXbmpEK3YyAAAAAQAAAACopHuHaFkl22lWPv1HCsc/6AtZ3HuTTWu2vGMF0afhGAAAAAoAAAAPd2ViX2F1dGhfZG9tYWluAAAAAAEAAAAQYXV0aC5leGFtcGxlLmNvbQAAAAAAAAAC0afhGAAAAECdejI1j8oab29UhweeE9h3uwLO8c0av0pN5B3NU2Mn2kQPEgo3zlXBMyY5wEYvPN7wlKozEZVTYurWQ0eZCX0L5kEXvwAAAEDh7GlPkDIJX1aWlJXV1K2yZEvu63urLeNIJlwypfrnIgteXArbewcU93Lw14RBENtcl1JKPQWtFlr2n4s3jewH";
+
+    let provider = || {
+        StellarProvider::new()
+            .with_server_account(SERVER_ACCOUNT)
+            .with_home_domain(HOME_DOMAIN)
+            .with_web_auth_domain(WEB_AUTH_DOMAIN)
+            .with_network_passphrase(TESTNET_NETWORK_PASSPHRASE)
+            .with_time(UNIX_EPOCH + Duration::from_secs(ISSUED_AT + 60))
+    };
+    let input = VerificationInput {
+        signature_data: ENVELOPE.to_string(),
+        expected_address: CLIENT_ACCOUNT.to_string(),
+        challenge: Some(NONCE.to_string()),
+        ..Default::default()
+    };
+
+    let result = provider().verify(&input).unwrap();
+    assert!(result.is_valid(), "Independent SEP-10 vector should verify");
+    assert_eq!(result.derived_address, CLIENT_ACCOUNT);
+
+    // Signed for testnet, so the public network passphrase must not verify it
+    let result = provider()
+        .with_network_passphrase("Public Global Stellar Network ; September 2015")
+        .verify(&input)
+        .unwrap();
+    assert!(!result.is_valid());
+
+    // Signed by the server, the client domain account and the client
+    const CLIENT_DOMAIN_ENVELOPE: &str = "AAAAAgAAAACopHuHaFkl22lWPv1HCsc/6AtZ3HuTTWu2vGMF0afhGAAAASwAAAAAAAAAAAAAAAEAAAAAaPJBkAAAAABo8kUUAAAAAAAAAAMAAAABAAAAAJ1Aegvq53kHR5OJh+WjMMW4EAD8tGzIoctaduzmQRe/AAAACgAAABBleGFtcGxlLmNvbSBhdXRoAAAAAQAAAEBiNXBKMGkwb3VYam51RkdjWnNqWVJRYVBOcDR3aklidTNYRXZReEFXWmFPMFMyU3F2alR5V2JtSkxXbmpEK3YyAAAAAQAAAACopHuHaFkl22lWPv1HCsc/6AtZ3HuTTWu2vGMF0afhGAAAAAoAAAAPd2ViX2F1dGhfZG9tYWluAAAAAAEAAAAQYXV0aC5leGFtcGxlLmNvbQAAAAEAAAAAurjdveBW4x3ZuIDESTHh0ADEbG35UdWkTx7bKBXq6cIAAAAKAAAADWNsaWVudF9kb21haW4AAAAAAAABAAAADndhbGxldC5leGFtcGxlAAAAAAAAAAAAA9Gn4RgAAABApK4KlhfLLVBNeTVIIgiVIu81dkm+ZyjCfWkpUf4kAXn7Tk31VXc2JjaoeIYbyOBrD644c61laQktpvbVNqr4CxXq6cIAAABAKYk/hwC8+A/yulr8EWja0HtQesXsiWZXt3L07df7NklOpnXsW3mK4Tzuta/YFyPnsDdJCF8GneJhaipOArj6AOZBF78AAABAtwW7P/1ujirCikojOT1mLczNXb3gsBsbA7IBOg0K4pMSCGGpa01Z9PeccFg9UZSGC3eUD+oPQBE95nVroDkCAQ==";
+    let result = provider()
+        .verify(&VerificationInput {
+            signature_data: CLIENT_DOMAIN_ENVELOPE.to_string(),
+            ..input
+        })
+        .unwrap();
+    assert!(
+        result.is_valid(),
+        "Independent client_domain vector should verify"
+    );
+}
@@ -0,0 +1,233 @@
+//! Integration tests for Polkadot/Substrate signRaw verification
+//!
+//! polkadot.js and Talisman sign `<Bytes>message</Bytes>` with sr25519 by default;
+//! ed25519 and ecdsa accounts are supported as well. The tests sign with all three key
+//! types, with and without the `<Bytes>` wrapper and MultiSignature type byte, and
+//! check SS58 prefixes; sr25519 signing is randomized, so only the ed25519 and ecdsa
+//! vectors from `tests/vectors/substrate.py` are fixed.
+
+mod common;
+
+use common::{ed25519_key, input, secp256k1_key, CHALLENGE};
+use ed25519_dalek::Signer as _;
+use schnorrkel::{ExpansionMode, MiniSecretKey};
+use secp256k1::{Message, PublicKey, SECP256K1};
+use wallet_signature_verify::wallets::substrate::core::{blake2b_256, wrap_bytes};
+use wallet_signature_verify::wallets::substrate::ss58_prefix;
+use wallet_signature_verify::wallets::{
+    get_wallet_provider, Ss58Address, SubstrateKeyType, SubstrateProvider, VerificationInput,
+    WalletProvider, WalletType,
+};
+
+/// Signs `message` with an sr25519 key, returning (hex signature, SS58 address)
+fn sign_sr25519(message: &[u8], secret_byte: u8, prefix: u16) -> (String, String) {
+    let keypair = MiniSecretKey::from_bytes(&[secret_byte; 32])
+        .unwrap()
+        .expand_to_keypair(ExpansionMode::Ed25519);
+    let signature = keypair.sign_simple(b"substrate", message);
+    let address = Ss58Address::new(prefix, keypair.public.to_bytes());
+
+    (
+        format!("0x{}", hex::encode(signature.to_bytes())),
+        address.to_string(),
+    )
+}
+
+/// Signs `message` with an ed25519 key, returning (hex signature, SS58 address)
+fn sign_ed25519(message: &[u8], secret_byte: u8) -> (String, String) {
+    let signing_key = ed25519_key(secret_byte);
+    let signature = signing_key.sign(message);
+    let address = Ss58Address::new(
+        ss58_prefix::SUBSTRATE,
+        signing_key.verifying_key().to_bytes(),
+    );
+
+    (
+        format!("0x{}", hex::encode(signature.to_bytes())),
+        address.to_string(),
+    )
+}
+
+/// Signs `BLAKE2b-256(message)` with a secp256k1 key, returning
+/// (hex `r || s || v`, SS58 address of BLAKE2b-256(compressed key))
+fn sign_ecdsa(message: &[u8], secret_byte: u8) -> (String, String) {
+    let secret_key = secp256k1_key(secret_byte);
+    let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key);
+    let signature =
+        SECP256K1.sign_ecdsa_recoverable(&Message::from_digest(blake2b_256(message)), &secret_key);
+    let (recovery_id, compact) = signature.serialize_compact();
+    let address = Ss58Address::new(ss58_prefix::POLKADOT, blake2b_256(&public_key.serialize()));
+
+    (
+        format!(
+            "0x{}{:02x}",
+            hex::encode(compact),
+            recovery_id.to_i32() as u8
+        ),
+        address.to_string(),
+    )
+}
+
+/// Test an sr25519 signature over the `<Bytes>`-wrapped challenge
+#[test]
+fn test_substrate_sr25519_wrapped() {
+    let (signature, address) = sign_sr25519(
+        &wrap_bytes(CHALLENGE.as_bytes()),
+        0x01,
+        ss58_prefix::POLKADOT,
+    );
+
+    let result = SubstrateProvider::new()
+        .with_ss58_prefix(ss58_prefix::POLKADOT)
+        .verify(&input(signature, address.clone(), CHALLENGE))
+        .unwrap();
+
+    assert!(result.is_valid(), "Valid sr25519 signature should verify");
+    assert_eq!(result.derived_address, address);
+}
+
+/// Test an sr25519 signature over the raw challenge, with a MultiSignature type byte,
+/// through the registry
+#[test]
+fn test_substrate_sr25519_unwrapped_multisignature() {
+    let (signature, address) = sign_sr25519(CHALLENGE.as_bytes(), 0x02, ss58_prefix::KUSAMA);
+    let multi_signature = format!("0x01{}", &signature[2..]);
+
+    let provider = get_wallet_provider(WalletType::from_str("talisman").unwrap());
+    let result = provider
+        .verify(&input(multi_signature, address, CHALLENGE))
+        .unwrap();
+
+    assert_eq!(provider.name(), "Substrate");
+    assert!(result.is_valid());
+}
+
+/// Test ed25519 and ecdsa accounts
+#[test]
+fn test_substrate_ed25519_and_ecdsa() {
+    let (signature, address) = sign_ed25519(&wrap_bytes(CHALLENGE.as_bytes()), 0x03);
+    let result = SubstrateProvider::new()
+        .verify(&input(signature.clone(), address.clone(), CHALLENGE))
+        .unwrap();
+    assert!(result.is_valid(), "Valid ed25519 signature should verify");
+
+    // Pinning another key type rejects it
+    let sr25519_only = SubstrateProvider::new().with_key_type(SubstrateKeyType::Sr25519);
+    assert!(!sr25519_only
+        .verify(&input(signature, address, CHALLENGE))
+        .unwrap()
+        .is_valid());
+
+    let (signature, address) = sign_ecdsa(CHALLENGE.as_bytes(), 0x04);
+    let result = SubstrateProvider::new()
+        .verify(&input(signature, address.clone(), CHALLENGE))
+        .unwrap();
+    assert!(result.is_valid(), "Valid ecdsa signature should verify");
+    assert_eq!(result.derived_address, address);
+}
+
+/// Test that an sr25519 signature matches neither the wrapped nor the raw form of
+/// another challenge
+#[test]
+fn test_substrate_wrong_challenge() {
+    let (signature, address) = sign_sr25519(
+        &wrap_bytes(b"original challenge"),
+        0x05,
+        ss58_prefix::SUBSTRATE,
+    );
+
+    let result = SubstrateProvider::new()
+        .verify(&input(signature, address, "tampered challenge"))
+        .unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.signature_valid);
+}
+
+/// Test that signatures from another account are rejected
+#[test]
+fn test_substrate_wrong_address() {
+    let message = wrap_bytes(CHALLENGE.as_bytes());
+    let (signature, _) = sign_sr25519(&message, 0x06, ss58_prefix::SUBSTRATE);
+    let (_, other_address) = sign_sr25519(&message, 0x07, ss58_prefix::SUBSTRATE);
+
+    let result = SubstrateProvider::new()
+        .verify(&input(signature, other_address, CHALLENGE))
+        .unwrap();
+    assert!(!result.is_valid());
+
+    let (signature, _) = sign_ecdsa(&message, 0x08);
+    let (_, other_address) = sign_ecdsa(&message, 0x09);
+    let result = SubstrateProvider::new()
+        .verify(&input(signature, other_address, CHALLENGE))
+        .unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+}
+
+/// Test that the configured network prefix and malformed inputs are rejected
+#[test]
+fn test_substrate_provider_validation() {
+    let (signature, address) = sign_sr25519(
+        &wrap_bytes(CHALLENGE.as_bytes()),
+        0x0a,
+        ss58_prefix::SUBSTRATE,
+    );
+
+    let polkadot_only = SubstrateProvider::new().with_ss58_prefix(ss58_prefix::POLKADOT);
+    assert!(polkadot_only
+        .verify(&input(signature.clone(), address.clone(), CHALLENGE))
+        .is_err());
+
+    let not_hex = input("not a signature".to_string(), address.clone(), CHALLENGE);
+    assert!(SubstrateProvider::new().verify(&not_hex).is_err());
+
+    let missing_challenge = VerificationInput {
+        signature_data: signature,
+        expected_address: address,
+        ..Default::default()
+    };
+    assert!(SubstrateProvider::new().verify(&missing_challenge).is_err());
+}
+
+/// Test ed25519 and ecdsa signRaw signatures produced outside this crate by
+/// `tests/vectors/substrate.py`
+///
+/// sr25519 signing is randomized and has no independent implementation here,
+/// so only the deterministic key types are pinned.
+#[test]
+fn test_substrate_independent_vectors() {
+    const ED25519_ADDRESS: &str = "5EEPcdCRgtSg6UQkiE1mokqBirMderP2s5yHKCgmHXUKQWvh";
+    const ED25519_SIGNATURE: &str = "0x737afedf7af04a95a38eddcd1c8d9e5ceb043e982a5f0506afb857fa221edb64f72adfed531618e7ade7dfa3bceed6a04bbe97c48b5bb95d68f1dbf633528406";
+    const ECDSA_ADDRESS: &str = "12V2naB4tYgTWcJW4Z35VWE9A5uBQTYtHeJ42pD1SfULKNh6";
+    const ECDSA_SIGNATURE: &str = "0x89475e178765e834ed4e01bd89532110a0790266850dc841f38cf5bf127d0bc03c0543cd1d813cb2c5e5654edcaf328874b3919db702d10b601572998f726e4e00";
+
+    for (signature, address) in [
+        (ED25519_SIGNATURE, ED25519_ADDRESS),
+        (ECDSA_SIGNATURE, ECDSA_ADDRESS),
+    ] {
+        let result = SubstrateProvider::new()
+            .verify(&input(
+                signature.to_string(),
+                address.to_string(),
+                CHALLENGE,
+            ))
+            .unwrap();
+        assert!(
+            result.is_valid(),
+            "Independent vector for {} should verify",
+            address
+        );
+        assert_eq!(result.derived_address, address);
+
+        let tampered = input(
+            signature.to_string(),
+            address.to_string(),
+            &format!("{}x", CHALLENGE),
+        );
+        assert!(!SubstrateProvider::new()
+            .verify(&tampered)
+            .unwrap()
+            .is_valid());
+    }
+}
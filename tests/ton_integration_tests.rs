@@ -0,0 +1,293 @@
+//! Integration tests for TON Connect `ton_proof` verification
+//!
+//! Wallets sign a structured message binding the address, app domain, timestamp and
+//! payload; the verifier reads the key from the wallet state init, whose hash is the
+//! address. The tests build wallet state inits with the crate's cell builder and check
+//! the payload, domain allowlist and proof age, the state init against the address,
+//! and a caller-supplied key; `tests/vectors/ton.py` hashes cells and writes the BoC
+//! with its own implementation.
+
+mod common;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use common::ed25519_key;
+use ed25519_dalek::{Signer as _, SigningKey};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, UNIX_EPOCH};
+use wallet_signature_verify::wallets::ton::boc::{Cell, CellBuilder};
+use wallet_signature_verify::wallets::{
+    TonAddress, TonProvider, VerificationInput, WalletProvider, WalletType,
+};
+
+const DOMAIN: &str = "example.com";
+const PAYLOAD: &str = "afba42ef0b9c4d31";
+const TIMESTAMP: u64 = 1_760_706_960;
+
+/// Wallet v4 style state init for the key of `secret_byte`, returning
+/// (signing key, base64 state init, raw address)
+fn wallet(secret_byte: u8) -> (SigningKey, String, TonAddress) {
+    let key = ed25519_key(secret_byte);
+    let code = CellBuilder::new()
+        .store_bytes(b"wallet v4 code")
+        .build()
+        .unwrap();
+    let data = CellBuilder::new()
+        .store_uint(0, 32) // seqno
+        .store_uint(698983191, 32) // subwallet
+        .store_bytes(key.verifying_key().as_bytes())
+        .store_bit(false) // no plugins
+        .build()
+        .unwrap();
+    let state_init: Cell = CellBuilder::new()
+        .store_uint(0b00110, 5)
+        .store_ref(code)
+        .store_ref(data)
+        .build()
+        .unwrap();
+
+    let address = TonAddress {
+        workchain: 0,
+        hash: state_init.hash(),
+    };
+    (key, BASE64.encode(state_init.to_boc()), address)
+}
+
+/// Signs a `ton_proof` for `address` like a TON Connect wallet does, returning the
+/// base64 signature
+fn sign_proof(key: &SigningKey, address: &TonAddress, domain: &str, payload: &str) -> String {
+    let mut message = b"ton-proof-item-v2/".to_vec();
+    message.extend_from_slice(&address.workchain.to_be_bytes());
+    message.extend_from_slice(&address.hash);
+    message.extend_from_slice(&(domain.len() as u32).to_le_bytes());
+    message.extend_from_slice(domain.as_bytes());
+    message.extend_from_slice(&TIMESTAMP.to_le_bytes());
+    message.extend_from_slice(payload.as_bytes());
+
+    let mut signed = vec![0xff, 0xff];
+    signed.extend_from_slice(b"ton-connect");
+    signed.extend_from_slice(&Sha256::digest(&message));
+    let hash = Sha256::digest(&signed);
+    BASE64.encode(key.sign(&hash).to_bytes())
+}
+
+/// The JSON a TON Connect app posts to its backend
+fn ton_proof_json(address: &TonAddress, signature: &str, state_init: Option<&str>) -> String {
+    let mut proof = serde_json::json!({
+        "timestamp": TIMESTAMP,
+        "domain": { "lengthBytes": DOMAIN.len(), "value": DOMAIN },
+        "payload": PAYLOAD,
+        "signature": signature,
+    });
+    if let Some(state_init) = state_init {
+        proof["state_init"] = state_init.into();
+    }
+    serde_json::json!({
+        "address": address.to_string(),
+        "network": "-239",
+        "proof": proof,
+    })
+    .to_string()
+}
+
+/// Provider with the app domain allowed and the clock set shortly after the proof
+fn provider() -> TonProvider {
+    TonProvider::new()
+        .with_allowed_domain(DOMAIN)
+        .with_time(UNIX_EPOCH + Duration::from_secs(TIMESTAMP + 60))
+}
+
+/// Test a valid proof whose key is read from the state init, with a user-friendly
+/// address
+#[test]
+fn test_ton_valid_proof_with_state_init() {
+    let (key, state_init, address) = wallet(0x01);
+    let signature = sign_proof(&key, &address, DOMAIN, PAYLOAD);
+
+    let input = VerificationInput {
+        signature_data: ton_proof_json(&address, &signature, Some(&state_init)),
+        expected_address: address.to_friendly(false, false),
+        challenge: Some(PAYLOAD.to_string()),
+        ..Default::default()
+    };
+    let result = provider().verify(&input).unwrap();
+
+    assert!(result.is_valid(), "Valid ton_proof should verify");
+    assert_eq!(result.derived_address, address.to_string());
+    assert_eq!(result.found_challenge.as_deref(), Some(PAYLOAD));
+}
+
+/// Test that the payload and an allowed domain are required
+#[test]
+fn test_ton_requires_challenge_and_domain() {
+    let (key, state_init, address) = wallet(0x01);
+    let signature = sign_proof(&key, &address, DOMAIN, PAYLOAD);
+    let input = VerificationInput {
+        signature_data: ton_proof_json(&address, &signature, Some(&state_init)),
+        expected_address: address.to_string(),
+        challenge: Some(PAYLOAD.to_string()),
+        ..Default::default()
+    };
+
+    let without_challenge = VerificationInput {
+        challenge: None,
+        ..input.clone()
+    };
+    assert!(provider().verify(&without_challenge).is_err());
+
+    let without_domain = TonProvider::new().with_time(UNIX_EPOCH + Duration::from_secs(TIMESTAMP));
+    assert!(without_domain.verify(&input).is_err());
+    assert!(WalletType::from_str("ton").is_err(), "TON is library-only");
+}
+
+/// Test a bare proof object verified against a public key supplied by the caller
+#[test]
+fn test_ton_valid_proof_with_public_key() {
+    let (key, _, address) = wallet(0x02);
+    let signature = sign_proof(&key, &address, DOMAIN, PAYLOAD);
+    let proof = serde_json::json!({
+        "timestamp": TIMESTAMP,
+        "domain": { "lengthBytes": DOMAIN.len(), "value": DOMAIN },
+        "payload": PAYLOAD,
+        "signature": signature,
+    });
+
+    let input = VerificationInput {
+        signature_data: proof.to_string(),
+        expected_address: address.to_string(),
+        challenge: Some(PAYLOAD.to_string()),
+        public_key: Some(hex::encode(key.verifying_key().as_bytes())),
+        ..Default::default()
+    };
+    assert!(provider().verify(&input).unwrap().is_valid());
+
+    // Without a state init, the key must come from the caller
+    let no_key = VerificationInput {
+        public_key: None,
+        ..input
+    };
+    assert!(provider().verify(&no_key).is_err());
+}
+
+/// Test that a different payload, domain or expired proof is rejected
+#[test]
+fn test_ton_wrong_challenge_domain_and_time() {
+    let (key, state_init, address) = wallet(0x03);
+    let signature = sign_proof(&key, &address, DOMAIN, PAYLOAD);
+    let input = VerificationInput {
+        signature_data: ton_proof_json(&address, &signature, Some(&state_init)),
+        expected_address: address.to_string(),
+        challenge: Some("another payload".to_string()),
+        ..Default::default()
+    };
+
+    let result = provider().verify(&input).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.challenge_valid);
+    assert!(result.signature_valid);
+
+    let input = VerificationInput {
+        challenge: Some(PAYLOAD.to_string()),
+        ..input
+    };
+    let other_domain = TonProvider::new()
+        .with_allowed_domain("other.com")
+        .with_time(UNIX_EPOCH + Duration::from_secs(TIMESTAMP));
+    assert!(!other_domain.verify(&input).unwrap().challenge_valid);
+
+    let expired = provider()
+        .with_max_age(Duration::from_secs(30))
+        .with_time(UNIX_EPOCH + Duration::from_secs(TIMESTAMP + 60));
+    assert!(!expired.verify(&input).unwrap().challenge_valid);
+}
+
+/// Test that a state init of another wallet, or a key not in the state init, is
+/// rejected
+#[test]
+fn test_ton_state_init_address_mismatch() {
+    let (key, _, address) = wallet(0x04);
+    let (_, other_state_init, _) = wallet(0x05);
+    let signature = sign_proof(&key, &address, DOMAIN, PAYLOAD);
+
+    let input = VerificationInput {
+        signature_data: ton_proof_json(&address, &signature, Some(&other_state_init)),
+        expected_address: address.to_string(),
+        challenge: Some(PAYLOAD.to_string()),
+        ..Default::default()
+    };
+    let result = provider().verify(&input).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+
+    let (_, state_init, _) = wallet(0x04);
+    let foreign_key = VerificationInput {
+        signature_data: ton_proof_json(&address, &signature, Some(&state_init)),
+        public_key: Some(hex::encode([0x06; 32])),
+        ..input
+    };
+    let result = provider().verify(&foreign_key).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+}
+
+/// Test that a proof signed for another address does not verify, and that malformed
+/// input is rejected
+#[test]
+fn test_ton_provider_validation() {
+    let (key, state_init, address) = wallet(0x07);
+    let (_, _, other_address) = wallet(0x08);
+    let signature = sign_proof(&key, &other_address, DOMAIN, PAYLOAD);
+
+    let input = VerificationInput {
+        signature_data: ton_proof_json(&address, &signature, Some(&state_init)),
+        expected_address: address.to_string(),
+        challenge: Some(PAYLOAD.to_string()),
+        ..Default::default()
+    };
+    let result = provider().verify(&input).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.signature_valid);
+
+    let not_json = VerificationInput {
+        signature_data: signature,
+        ..input.clone()
+    };
+    assert!(provider().verify(&not_json).is_err());
+
+    let bad_address = VerificationInput {
+        expected_address: "0:1234".to_string(),
+        ..input
+    };
+    assert!(provider().verify(&bad_address).is_err());
+}
+
+/// Test a `ton_proof` with a checksummed state init produced outside this crate by
+/// `tests/vectors/ton.py`
+#[test]
+fn test_ton_independent_vector() {
+    const RAW_ADDRESS: &str = "0:487fc2550961b8a7f37faa076f114f830cb57b60c1f1e35222abd36f128f2d4e";
+    const FRIENDLY_ADDRESS: &str = "EQBIf8JVCWG4p_N_qgdvEU-DDLV7YMHx41Iiq9NvEo8tTq0T";
+    const STATE_INIT: &str = "te6cckEBAwEAQAACATQBAgAcd2FsbGV0IHY0IGNvZGUAUQAAAAApqaMXHbpxTVogJJL7oYiz3Mn2yXH7bUU1nd70SvIo8NbdXylA5TeKNg==";
+    const SIGNATURE: &str =
+        "BOt6+5VmxdSCDJ2wtAjLOnAIHfgLMJWbXNV6uBeDoj77p8R8sl/vyuDGoeyL219NVGmEKH3SvlGDd8vCQRgACg==";
+
+    let address: TonAddress = RAW_ADDRESS.parse().unwrap();
+    assert_eq!(address.to_friendly(true, false), FRIENDLY_ADDRESS);
+
+    let input = VerificationInput {
+        signature_data: ton_proof_json(&address, SIGNATURE, Some(STATE_INIT)),
+        expected_address: FRIENDLY_ADDRESS.to_string(),
+        challenge: Some(PAYLOAD.to_string()),
+        ..Default::default()
+    };
+    let result = provider().verify(&input).unwrap();
+    assert!(
+        result.is_valid(),
+        "Independent ton_proof vector should verify"
+    );
+    assert_eq!(result.derived_address, RAW_ADDRESS);
+
+    let other_domain = TonProvider::new()
+        .with_allowed_domain("other.example")
+        .with_time(UNIX_EPOCH + Duration::from_secs(TIMESTAMP + 60));
+    assert!(!other_domain.verify(&input).unwrap().is_valid());
+}
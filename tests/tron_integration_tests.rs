@@ -0,0 +1,168 @@
+//! Integration tests for Tron TIP-191 signature verification
+//!
+//! TronLink signs `keccak256("\x19TRON Signed Message:\n" || len || message)` with
+//! `signMessageV2`, and hex messages with a fixed `32` length in the legacy v1 format.
+//! The tests sign both formats and check that the provider's signing scheme picks the
+//! right prefix; `tests/vectors/tron.py` hashes its vectors with its own Keccak-256.
+
+mod common;
+
+use common::{input, secp256k1_key, CHALLENGE};
+use ethers_core::utils::keccak256;
+use secp256k1::{Message, PublicKey, SECP256K1};
+use wallet_signature_verify::wallets::tron::core::{
+    tron_address, tron_message_hash_v1, tron_message_hash_v2,
+};
+use wallet_signature_verify::wallets::{
+    get_wallet_provider, TronProvider, TronSigningScheme, VerificationInput, WalletProvider,
+    WalletType,
+};
+
+/// Signs `hash` with the key, returning (hex r || s || v with v = 27/28, T-address)
+fn sign_hash(hash: [u8; 32], secret_byte: u8) -> (String, String) {
+    let secret_key = secp256k1_key(secret_byte);
+    let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key);
+    let address = tron_address(&public_key.serialize_uncompressed()).unwrap();
+
+    let signature = SECP256K1.sign_ecdsa_recoverable(&Message::from_digest(hash), &secret_key);
+    let (recovery_id, compact) = signature.serialize_compact();
+    let mut bytes = compact.to_vec();
+    bytes.push(27 + recovery_id.to_i32() as u8);
+
+    (format!("0x{}", hex::encode(bytes)), address)
+}
+
+/// Test a valid signMessageV2 signature
+#[test]
+fn test_tron_sign_message_v2() {
+    let (signature, address) = sign_hash(tron_message_hash_v2(CHALLENGE.as_bytes()), 0x01);
+
+    let result = TronProvider::new()
+        .verify(&input(signature, address.clone(), CHALLENGE))
+        .unwrap();
+
+    assert!(
+        result.is_valid(),
+        "Valid signMessageV2 signature should verify"
+    );
+    assert!(address.starts_with('T'));
+    assert_eq!(result.derived_address, address);
+}
+
+/// Test a legacy v1 signature over a hex challenge, through the registry
+#[test]
+fn test_tron_sign_message_v1() {
+    let challenge = hex::encode(keccak256(CHALLENGE));
+    let (signature, address) = sign_hash(tron_message_hash_v1(&challenge).unwrap(), 0x02);
+
+    let provider = get_wallet_provider(WalletType::from_str("tronlink").unwrap());
+    let result = provider
+        .verify(&input(signature.clone(), address.clone(), &challenge))
+        .unwrap();
+
+    assert_eq!(provider.name(), "Tron");
+    assert!(result.is_valid());
+
+    // Pinning the v2 scheme no longer accepts it
+    let v2_only = TronProvider::new().with_signing_scheme(TronSigningScheme::SignMessageV2);
+    assert!(!v2_only
+        .verify(&input(signature, address, &challenge))
+        .unwrap()
+        .is_valid());
+}
+
+/// Test that a signMessageV2 signature recovers another address for a changed message
+#[test]
+fn test_tron_wrong_challenge() {
+    let (signature, address) = sign_hash(tron_message_hash_v2(b"original challenge"), 0x03);
+
+    let result = TronProvider::new()
+        .verify(&input(signature, address, "tampered challenge"))
+        .unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.signature_valid);
+}
+
+/// Test that a signature from another account is rejected
+#[test]
+fn test_tron_wrong_address() {
+    let (signature, _) = sign_hash(tron_message_hash_v2(CHALLENGE.as_bytes()), 0x04);
+    let (_, other_address) = sign_hash(tron_message_hash_v2(CHALLENGE.as_bytes()), 0x05);
+
+    let result = TronProvider::new()
+        .verify(&input(signature, other_address, CHALLENGE))
+        .unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+}
+
+/// Test that malformed signatures, non-Tron addresses and missing challenges are rejected
+#[test]
+fn test_tron_provider_validation() {
+    let (signature, address) = sign_hash(tron_message_hash_v2(CHALLENGE.as_bytes()), 0x06);
+    let provider = TronProvider::new();
+
+    let evm_address = input(
+        signature.clone(),
+        "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string(),
+        CHALLENGE,
+    );
+    assert!(provider.verify(&evm_address).is_err());
+
+    let short_signature = input(signature[..100].to_string(), address.clone(), CHALLENGE);
+    assert!(provider.verify(&short_signature).is_err());
+
+    let missing_challenge = VerificationInput {
+        signature_data: signature,
+        expected_address: address,
+        ..Default::default()
+    };
+    assert!(provider.verify(&missing_challenge).is_err());
+}
+
+/// Test fixed signMessageV2 and v1 signatures produced outside this crate
+///
+/// Hashed with an independent Keccak-256 and signed with an independent RFC 6979
+/// secp256k1 implementation by `tests/vectors/tron.py`.
+#[test]
+fn test_tron_independent_vectors() {
+    const ADDRESS: &str = "TC4XonTGvV9nSTwQyA1Cq76tg9bqxm3uD6";
+    const V2_SIGNATURE: &str = "0xb51594b017b341c6b3a213096f5653cc5ed45ea0f9c9d7c8a7fc8ef332c79b26197176e235455955b6b45435cee93bc4a13e8b31b47ecac8ff8c1164819f363b1c";
+    const V1_CHALLENGE: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const V1_SIGNATURE: &str = "0x1b35d5e562328a3110b0260894a795079789c57ae7b2685bc252eafd049212a87d37409568b341f07de3e3ab7b85abc43d20677c7f7d2604d5c1f797c388dfff1b";
+
+    let v2 = TronProvider::new()
+        .with_signing_scheme(TronSigningScheme::SignMessageV2)
+        .verify(&input(
+            V2_SIGNATURE.to_string(),
+            ADDRESS.to_string(),
+            CHALLENGE,
+        ))
+        .unwrap();
+    assert!(
+        v2.is_valid(),
+        "Independent signMessageV2 vector should verify"
+    );
+    assert_eq!(v2.derived_address, ADDRESS);
+
+    let v1 = TronProvider::new()
+        .with_signing_scheme(TronSigningScheme::SignMessage)
+        .verify(&input(
+            V1_SIGNATURE.to_string(),
+            ADDRESS.to_string(),
+            V1_CHALLENGE,
+        ))
+        .unwrap();
+    assert!(v1.is_valid(), "Independent v1 vector should verify");
+
+    let swapped = TronProvider::new()
+        .verify(&input(
+            V1_SIGNATURE.to_string(),
+            ADDRESS.to_string(),
+            CHALLENGE,
+        ))
+        .unwrap();
+    assert!(!swapped.is_valid());
+}
@@ -0,0 +1,85 @@
+"""Independent BIP-137 signed-message vectors for tests/bitcoin_integration_tests.rs."""
+
+import base64
+import hashlib
+
+from common import (
+    CHALLENGE,
+    P,
+    N,
+    base58check,
+    bech32_encode,
+    convert_bits,
+    hash160,
+    point_add,
+    point_mul,
+    secp256k1_key,
+    secp256k1_public_key,
+    secp256k1_sign,
+)
+
+
+def message_hash(message):
+    """SHA256d of the varint-prefixed magic and message (lengths below 0xfd here)."""
+    magic = b"Bitcoin Signed Message:\n"
+    data = bytes([len(magic)]) + magic + bytes([len(message)]) + message
+    return hashlib.sha256(hashlib.sha256(data).digest()).digest()
+
+
+def p2pkh(public_key, version=0x00):
+    return base58check(bytes([version]) + hash160(public_key))
+
+
+def p2sh_p2wpkh(public_key):
+    return base58check(b"\x05" + hash160(b"\x00\x14" + hash160(public_key)))
+
+
+def p2wpkh(public_key):
+    return bech32_encode("bc", [0] + convert_bits(hash160(public_key), 8, 5))
+
+
+def recover(digest, signature):
+    """Compressed public key recovered from a 65-byte compact signature."""
+    recovery_id = (signature[0] - 27) & 3
+    r = int.from_bytes(signature[1:33], "big")
+    s = int.from_bytes(signature[33:], "big")
+    y = pow((r**3 + 7) % P, (P + 1) // 4, P)
+    if y & 1 != recovery_id & 1:
+        y = P - y
+    r_inverse = pow(r, N - 2, N)
+    z = int.from_bytes(digest, "big")
+    x, y = point_add(point_mul(s * r_inverse % N, (r, y)), point_mul(-z * r_inverse % N))
+    return bytes([2 + (y & 1)]) + x.to_bytes(32, "big")
+
+
+# Well-known addresses of the generator key (secret 1)
+assert p2pkh(secp256k1_public_key(1)) == "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+assert p2pkh(secp256k1_public_key(1, compressed=False)) == "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm"
+
+# Published: Bitcoin Core's test/functional/rpc_signmessage.py signs with a testnet key
+CORE_ADDRESS = "mpLQjfK79b7CCV4VMJWEWAj5Mpx8Up5zxB"
+CORE_MESSAGE = b"This is just a test message"
+CORE_SIGNATURE = (
+    "INbVnW4e6PeRmsv2Qgu8NuopvrVjkcxob+sX8OcZG0SALhWybUjzMLPdAsXI46YZGb0KQTRii+wWIQzRpG/U+S0="
+)
+core_key = recover(message_hash(CORE_MESSAGE), base64.b64decode(CORE_SIGNATURE))
+assert p2pkh(core_key, version=0x6F) == CORE_ADDRESS
+
+secret = secp256k1_key("bitcoin")
+compressed = secp256k1_public_key(secret)
+uncompressed = secp256k1_public_key(secret, compressed=False)
+r, s, recovery_id = secp256k1_sign(secret, message_hash(CHALLENGE.encode()))
+
+
+def signature(header_base):
+    """Base64 header byte (address type base + recovery ID), r and s."""
+    header = bytes([header_base + recovery_id])
+    return base64.b64encode(header + r.to_bytes(32, "big") + s.to_bytes(32, "big")).decode()
+
+
+print("P2PKH_UNCOMPRESSED =", p2pkh(uncompressed), signature(27))
+print("P2PKH =", p2pkh(compressed), signature(31))
+print("P2SH_P2WPKH =", p2sh_p2wpkh(compressed), signature(35))
+print("P2WPKH =", p2wpkh(compressed), signature(39))
+# Electrum signs SegWit addresses with the compressed P2PKH header
+print("ELECTRUM_P2WPKH =", p2wpkh(compressed), signature(31))
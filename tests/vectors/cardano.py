@@ -0,0 +1,23 @@
+"""Independent CIP-30 `signData` vector for tests/cardano_integration_tests.rs."""
+
+import hashlib
+
+from common import CHALLENGE, bech32, cbor, ed25519_key
+
+private_key, public_key = ed25519_key("cardano")
+
+# Mainnet enterprise address (header 0x61): the key hash without a stake part
+address_bytes = b"\x61" + hashlib.blake2b(public_key, digest_size=28).digest()
+address = bech32("addr", address_bytes)
+
+# COSE_Sign1 with the address in the protected header, signed over its Sig_structure
+message = CHALLENGE.encode()
+protected = cbor({1: -8, "address": address_bytes})
+sig_structure = cbor(["Signature1", protected, b"", message])
+signature = private_key.sign(sig_structure)
+cose_sign1 = cbor([protected, {"hashed": False}, message, signature])
+cose_key = cbor({1: 1, 3: -8, -1: 6, -2: public_key})
+
+print("ADDRESS =", address)
+print("COSE_SIGN1 =", cose_sign1.hex())
+print("COSE_KEY =", cose_key.hex())
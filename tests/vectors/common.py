@@ -0,0 +1,278 @@
+"""Shared helpers for the independent test vector generators.
+
+Everything the vectors depend on is either implemented here from its specification
+(Keccak-256, secp256k1 with RFC 6979 nonces, base58, bech32, CBOR) or taken from
+pyca/cryptography (ed25519, P-256), so none of the crate's code or Rust dependencies
+are involved in producing them.
+"""
+
+import hashlib
+import hmac
+
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PrivateKey
+
+# Challenge signed by most vectors (`CHALLENGE` in the integration tests)
+CHALLENGE = "example.com:1760706960:afba42ef:login"
+
+
+def seed(name):
+    """Deterministic 32-byte secret for the key called `name`."""
+    return hashlib.sha256(b"wallet-signature-verify/" + name.encode()).digest()
+
+
+def ed25519_key(name):
+    """ed25519 key pair derived from `name`: (private key, raw 32-byte public key)."""
+    private_key = Ed25519PrivateKey.from_private_bytes(seed(name))
+    public_key = private_key.public_key().public_bytes(
+        serialization.Encoding.Raw, serialization.PublicFormat.Raw
+    )
+    return private_key, public_key
+
+
+def secp256k1_key(name):
+    """secp256k1 secret scalar derived from `name`."""
+    return int.from_bytes(seed(name), "big")
+
+
+# ---------------------------------------------------------------------------
+# Keccak-256 (the original Keccak padding used by Ethereum and Tron, not SHA3-256)
+# ---------------------------------------------------------------------------
+
+_ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+_ROTATIONS = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+]
+_MASK64 = (1 << 64) - 1
+
+
+def _rotl(x, n):
+    n %= 64
+    return ((x << n) | (x >> (64 - n))) & _MASK64
+
+
+def _keccak_f(state):
+    for round_constant in _ROUND_CONSTANTS:
+        c = [state[x][0] ^ state[x][1] ^ state[x][2] ^ state[x][3] ^ state[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ _rotl(c[(x + 1) % 5], 1) for x in range(5)]
+        state = [[state[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = _rotl(state[x][y], _ROTATIONS[x][y])
+        state = [
+            [b[x][y] ^ (~b[(x + 1) % 5][y] & b[(x + 2) % 5][y]) for y in range(5)]
+            for x in range(5)
+        ]
+        state[0][0] ^= round_constant
+    return state
+
+
+def keccak256(data):
+    rate = 136
+    data = bytearray(data)
+    data.append(0x01)
+    while len(data) % rate:
+        data.append(0)
+    data[-1] |= 0x80
+
+    state = [[0] * 5 for _ in range(5)]
+    for offset in range(0, len(data), rate):
+        block = data[offset:offset + rate]
+        for i in range(rate // 8):
+            state[i % 5][i // 5] ^= int.from_bytes(block[8 * i:8 * i + 8], "little")
+        state = _keccak_f(state)
+    return b"".join(state[i % 5][i // 5].to_bytes(8, "little") for i in range(4))
+
+
+assert keccak256(b"").hex() == "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+
+# ---------------------------------------------------------------------------
+# secp256k1
+# ---------------------------------------------------------------------------
+
+P = 2**256 - 2**32 - 977
+N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+G = (
+    0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798,
+    0x483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8,
+)
+
+
+def point_add(p, q):
+    if p is None:
+        return q
+    if q is None:
+        return p
+    if p[0] == q[0] and (p[1] + q[1]) % P == 0:
+        return None
+    if p == q:
+        slope = 3 * p[0] * p[0] * pow(2 * p[1], P - 2, P) % P
+    else:
+        slope = (q[1] - p[1]) * pow(q[0] - p[0], P - 2, P) % P
+    x = (slope * slope - p[0] - q[0]) % P
+    return x, (slope * (p[0] - x) - p[1]) % P
+
+
+def point_mul(k, point=G):
+    result = None
+    while k:
+        if k & 1:
+            result = point_add(result, point)
+        point = point_add(point, point)
+        k >>= 1
+    return result
+
+
+def secp256k1_public_key(secret, compressed=True):
+    x, y = point_mul(secret)
+    if compressed:
+        return bytes([2 + (y & 1)]) + x.to_bytes(32, "big")
+    return b"\x04" + x.to_bytes(32, "big") + y.to_bytes(32, "big")
+
+
+def _rfc6979_nonce(secret, digest):
+    x = secret.to_bytes(32, "big")
+    v = b"\x01" * 32
+    k = b"\x00" * 32
+    k = hmac.new(k, v + b"\x00" + x + digest, hashlib.sha256).digest()
+    v = hmac.new(k, v, hashlib.sha256).digest()
+    k = hmac.new(k, v + b"\x01" + x + digest, hashlib.sha256).digest()
+    v = hmac.new(k, v, hashlib.sha256).digest()
+    while True:
+        v = hmac.new(k, v, hashlib.sha256).digest()
+        nonce = int.from_bytes(v, "big")
+        if 1 <= nonce < N:
+            return nonce
+        k = hmac.new(k, v + b"\x00", hashlib.sha256).digest()
+        v = hmac.new(k, v, hashlib.sha256).digest()
+
+
+def secp256k1_sign(secret, digest):
+    """Deterministic low-S ECDSA over a 32-byte digest: (r, s, recovery id)."""
+    z = int.from_bytes(digest, "big")
+    nonce = _rfc6979_nonce(secret, digest)
+    point = point_mul(nonce)
+    r = point[0] % N
+    s = pow(nonce, N - 2, N) * (z + r * secret) % N
+    recovery_id = point[1] & 1
+    if s > N // 2:
+        s = N - s
+        recovery_id ^= 1
+    return r, s, recovery_id
+
+
+# Published RFC 6979 vector: key 1 signing SHA-256("Satoshi Nakamoto")
+assert secp256k1_sign(1, hashlib.sha256(b"Satoshi Nakamoto").digest())[:2] == (
+    0x934B1EA10A4B3C1757E2B0C017D0B6143CE3C9A7E6A4A49860D7A6AB210EE3D8,
+    0x2442CE9D2B916064108014783E923EC36B49743E2FFA1C4496F01A512AAFD9E5,
+)
+
+# ---------------------------------------------------------------------------
+# Encodings
+# ---------------------------------------------------------------------------
+
+_BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def base58(data):
+    n = int.from_bytes(data, "big")
+    encoded = ""
+    while n:
+        n, remainder = divmod(n, 58)
+        encoded = _BASE58_ALPHABET[remainder] + encoded
+    return "1" * (len(data) - len(data.lstrip(b"\x00"))) + encoded
+
+
+def base58check(data):
+    return base58(data + hashlib.sha256(hashlib.sha256(data).digest()).digest()[:4])
+
+
+_BECH32_CHARSET = "qpzry9x8gf2tvdw0s3jn54khce6mua7l"
+
+
+def _bech32_polymod(values):
+    generator = [0x3B6A57B2, 0x26508E6D, 0x1EA119FA, 0x3D4233DD, 0x2A1462B3]
+    checksum = 1
+    for value in values:
+        top = checksum >> 25
+        checksum = (checksum & 0x1FFFFFF) << 5 ^ value
+        for i in range(5):
+            checksum ^= generator[i] if (top >> i) & 1 else 0
+    return checksum
+
+
+def convert_bits(data, from_bits, to_bits):
+    """Regroups `data` into `to_bits`-wide values, zero-padding the last one."""
+    acc, bits, out = 0, 0, []
+    for value in data:
+        acc = (acc << from_bits) | value
+        bits += from_bits
+        while bits >= to_bits:
+            bits -= to_bits
+            out.append((acc >> bits) & ((1 << to_bits) - 1))
+    if bits:
+        out.append((acc << (to_bits - bits)) & ((1 << to_bits) - 1))
+    return out
+
+
+def bech32_encode(hrp, words, constant=1):
+    """Encodes 5-bit `words`; `constant` is 1 for bech32 and 0x2bc830a3 for bech32m."""
+    expanded = [ord(c) >> 5 for c in hrp] + [0] + [ord(c) & 31 for c in hrp]
+    polymod = _bech32_polymod(expanded + words + [0] * 6) ^ constant
+    checksum = [(polymod >> 5 * (5 - i)) & 31 for i in range(6)]
+    return hrp + "1" + "".join(_BECH32_CHARSET[d] for d in words + checksum)
+
+
+def bech32(hrp, data):
+    """bech32 of raw bytes, as used for Cosmos and Cardano addresses."""
+    return bech32_encode(hrp, convert_bits(data, 8, 5))
+
+
+def hash160(data):
+    return hashlib.new("ripemd160", hashlib.sha256(data).digest()).digest()
+
+
+# ---------------------------------------------------------------------------
+# CBOR (the canonical subset COSE structures need)
+# ---------------------------------------------------------------------------
+
+
+def _cbor_head(major, n):
+    if n < 24:
+        return bytes([major << 5 | n])
+    if n < 256:
+        return bytes([major << 5 | 24, n])
+    if n < 65536:
+        return bytes([major << 5 | 25]) + n.to_bytes(2, "big")
+    return bytes([major << 5 | 26]) + n.to_bytes(4, "big")
+
+
+def cbor(value):
+    if isinstance(value, bool):
+        return b"\xf5" if value else b"\xf4"
+    if isinstance(value, int):
+        return _cbor_head(0, value) if value >= 0 else _cbor_head(1, -1 - value)
+    if isinstance(value, (bytes, bytearray)):
+        return _cbor_head(2, len(value)) + bytes(value)
+    if isinstance(value, str):
+        encoded = value.encode()
+        return _cbor_head(3, len(encoded)) + encoded
+    if isinstance(value, list):
+        return _cbor_head(4, len(value)) + b"".join(cbor(item) for item in value)
+    if isinstance(value, dict):
+        return _cbor_head(5, len(value)) + b"".join(
+            cbor(key) + cbor(item) for key, item in value.items()
+        )
+    raise TypeError(f"cannot encode {value!r} as CBOR")
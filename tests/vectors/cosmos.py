@@ -0,0 +1,46 @@
+"""Independent ADR-036 `signArbitrary` vector for tests/cosmos_integration_tests.rs."""
+
+import base64
+import hashlib
+import json
+
+from common import CHALLENGE, bech32, hash160, secp256k1_key, secp256k1_public_key, secp256k1_sign
+
+secret = secp256k1_key("cosmos")
+public_key = secp256k1_public_key(secret)
+address = bech32("cosmos", hash160(public_key))
+
+# Amino JSON sign doc of the MsgSignData, keys sorted and without whitespace
+sign_doc = json.dumps(
+    {
+        "account_number": "0",
+        "chain_id": "",
+        "fee": {"amount": [], "gas": "0"},
+        "memo": "",
+        "msgs": [
+            {
+                "type": "sign/MsgSignData",
+                "value": {
+                    "data": base64.b64encode(CHALLENGE.encode()).decode(),
+                    "signer": address,
+                },
+            }
+        ],
+        "sequence": "0",
+    },
+    separators=(",", ":"),
+    sort_keys=True,
+)
+r, s, _ = secp256k1_sign(secret, hashlib.sha256(sign_doc.encode()).digest())
+std_signature = {
+    "pub_key": {
+        "type": "tendermint/PubKeySecp256k1",
+        "value": base64.b64encode(public_key).decode(),
+    },
+    "signature": base64.b64encode(r.to_bytes(32, "big") + s.to_bytes(32, "big")).decode(),
+}
+
+print("ADDRESS =", address)
+print("STD_SIGNATURE =", json.dumps(std_signature, separators=(",", ":")))
+# The generator point's key, the well-known 751e76e8...3bd6 account ID
+print("GENERATOR_ADDRESS =", bech32("cosmos", hash160(secp256k1_public_key(1))))
@@ -0,0 +1,39 @@
+"""Independent Sui and Aptos vectors for tests/move_chains_integration_tests.rs."""
+
+import base64
+import hashlib
+import json
+
+from common import CHALLENGE, ed25519_key
+
+
+def uleb128(n):
+    out = b""
+    while True:
+        byte = n & 0x7F
+        n >>= 7
+        if not n:
+            return out + bytes([byte])
+        out += bytes([byte | 0x80])
+
+
+# Sui signPersonalMessage: ed25519 over BLAKE2b-256(intent || BCS(vector<u8>)), with
+# the flag-prefixed address hash and serialized signature
+private_key, public_key = ed25519_key("sui")
+message = CHALLENGE.encode()
+digest = hashlib.blake2b(bytes([3, 0, 0]) + uleb128(len(message)) + message, digest_size=32).digest()
+print("Sui ADDRESS =", "0x" + hashlib.blake2b(b"\x00" + public_key, digest_size=32).hexdigest())
+print("Sui SIGNATURE =", base64.b64encode(b"\x00" + private_key.sign(digest) + public_key).decode())
+
+# Aptos signMessage: ed25519 over the full message; the address is SHA3-256 of the
+# public key and the single-key scheme byte
+private_key, public_key = ed25519_key("aptos")
+full_message = (
+    "APTOS\napplication: https://example.com\nchainId: 1\nmessage: "
+    + CHALLENGE
+    + "\nnonce: 8b1a2c"
+)
+print("Aptos ADDRESS =", "0x" + hashlib.sha3_256(public_key + b"\x00").hexdigest())
+print("Aptos FULL_MESSAGE =", json.dumps(full_message))
+print("Aptos SIGNATURE =", "0x" + private_key.sign(full_message.encode()).hex())
+print("Aptos PUBLIC_KEY =", "0x" + public_key.hex())
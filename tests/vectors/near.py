@@ -0,0 +1,38 @@
+"""Independent NEP-413 `signMessage` vector for tests/near_integration_tests.rs."""
+
+import base64
+import hashlib
+import json
+import struct
+
+from common import CHALLENGE, base58, ed25519_key
+
+NONCE = bytes([0x5A]) * 32
+RECIPIENT = "example.com"
+
+
+def borsh_string(value):
+    encoded = value.encode()
+    return struct.pack("<I", len(encoded)) + encoded
+
+
+# Borsh payload behind the 2^31 + 413 tag: message, nonce, recipient, no callback URL
+payload = (
+    struct.pack("<I", 2**31 + 413)
+    + borsh_string(CHALLENGE)
+    + NONCE
+    + borsh_string(RECIPIENT)
+    + b"\x00"
+)
+
+private_key, public_key = ed25519_key("near")
+# Implicit account: the hex public key is the account ID
+account_id = public_key.hex()
+output = {
+    "accountId": account_id,
+    "publicKey": "ed25519:" + base58(public_key),
+    "signature": base64.b64encode(private_key.sign(hashlib.sha256(payload).digest())).decode(),
+}
+
+print("ACCOUNT_ID =", account_id)
+print("OUTPUT =", json.dumps(output, separators=(",", ":")))
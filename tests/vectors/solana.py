@@ -0,0 +1,39 @@
+"""Independent Solana `signMessage` and `signIn` (SIWS) vectors for
+tests/solana_integration_tests.rs."""
+
+import json
+
+from common import CHALLENGE, base58, ed25519_key
+
+# RFC 8032 section 7.1, TEST 2: a published ed25519 signature over the message "r"
+RFC8032_PUBLIC_KEY = bytes.fromhex("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c")
+RFC8032_SIGNATURE = bytes.fromhex(
+    "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da"
+    "085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+)
+print("RFC8032_ADDRESS =", base58(RFC8032_PUBLIC_KEY))
+print("RFC8032_SIGNATURE =", base58(RFC8032_SIGNATURE))
+
+# signMessage: ed25519 over the raw UTF-8 bytes; the address is the base58 public key
+private_key, public_key = ed25519_key("solana")
+print("ADDRESS =", base58(public_key))
+print("SIGNATURE =", base58(private_key.sign(CHALLENGE.encode())))
+
+# signIn: the SIWS message text is written out from the Sign-In With Solana spec (the
+# EIP-4361 layout with "Solana account"), and the wallet signs its UTF-8 bytes
+private_key, public_key = ed25519_key("siws")
+address = base58(public_key)
+message = (
+    "example.com wants you to sign in with your Solana account:\n"
+    + address
+    + "\n\nSign in to Example"
+    + "\n\nURI: https://example.com/login"
+    + "\nVersion: 1"
+    + "\nChain ID: mainnet"
+    + "\nNonce: afba42ef"
+    + "\nIssued At: 2025-10-17T12:00:00Z"
+    + "\nExpiration Time: 2025-10-17T12:10:00Z"
+)
+print("SIWS ADDRESS =", address)
+print("SIWS MESSAGE =", json.dumps(message))
+print("SIWS SIGNATURE =", base58(private_key.sign(message.encode())))
@@ -0,0 +1,112 @@
+"""Independent SEP-10 challenge vectors for tests/stellar_integration_tests.rs.
+
+Prints a challenge signed by the server and the client, and one that also carries a
+`client_domain` operation signed by the client domain account.
+"""
+
+import base64
+import hashlib
+import struct
+
+from common import ed25519_key
+
+NETWORK_PASSPHRASE = b"Test SDF Network ; September 2015"
+HOME_DOMAIN = b"example.com"
+WEB_AUTH_DOMAIN = b"auth.example.com"
+CLIENT_DOMAIN = b"wallet.example"
+NONCE = b"b5pJ0i0ouXjnuFGcZsjYRQaPNp4wjIbu3XEvQxAWZaO0S2SqvjTyWbmJLWnjD+v2"
+ISSUED_AT = 1760706960
+
+ENVELOPE_TYPE_TX = 2
+KEY_TYPE_ED25519 = 0
+MEMO_NONE = 0
+PRECOND_TIME = 1
+MANAGE_DATA = 10
+
+
+def crc16_xmodem(data):
+    crc = 0
+    for byte in data:
+        crc ^= byte << 8
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x1021) & 0xFFFF if crc & 0x8000 else (crc << 1) & 0xFFFF
+    return crc
+
+
+def account_id(public_key):
+    """StrKey `G...` account ID (SEP-23)."""
+    data = bytes([6 << 3]) + public_key
+    return base64.b32encode(data + struct.pack("<H", crc16_xmodem(data))).decode()
+
+
+def opaque(data):
+    """XDR variable-length opaque: length, bytes, zero padding to 4 bytes."""
+    return struct.pack(">I", len(data)) + data + b"\x00" * (-len(data) % 4)
+
+
+def muxed_account(public_key):
+    return struct.pack(">i", KEY_TYPE_ED25519) + public_key
+
+
+def manage_data(source, name, value):
+    return (
+        struct.pack(">I", 1)  # source account present
+        + muxed_account(source)
+        + struct.pack(">i", MANAGE_DATA)
+        + opaque(name)
+        + struct.pack(">I", 1)  # value present
+        + opaque(value)
+    )
+
+
+def challenge(server, client, signers, client_domain_account=None):
+    """Base64 envelope of a challenge for `client`, signed by each of `signers`."""
+    operations = [
+        manage_data(client, HOME_DOMAIN + b" auth", NONCE),
+        manage_data(server, b"web_auth_domain", WEB_AUTH_DOMAIN),
+    ]
+    if client_domain_account is not None:
+        operations.append(manage_data(client_domain_account, b"client_domain", CLIENT_DOMAIN))
+
+    transaction = (
+        muxed_account(server)
+        + struct.pack(">I", 100 * len(operations))  # fee
+        + struct.pack(">q", 0)  # sequence number
+        + struct.pack(">i", PRECOND_TIME)
+        + struct.pack(">QQ", ISSUED_AT, ISSUED_AT + 900)
+        + struct.pack(">i", MEMO_NONE)
+        + struct.pack(">I", len(operations))
+        + b"".join(operations)
+        + struct.pack(">i", 0)  # no extension
+    )
+    signature_base = (
+        hashlib.sha256(NETWORK_PASSPHRASE).digest()
+        + struct.pack(">i", ENVELOPE_TYPE_TX)
+        + transaction
+    )
+    digest = hashlib.sha256(signature_base).digest()
+
+    signatures = b"".join(
+        public_key[28:] + opaque(private_key.sign(digest)) for private_key, public_key in signers
+    )
+    envelope = (
+        struct.pack(">i", ENVELOPE_TYPE_TX)
+        + transaction
+        + struct.pack(">I", len(signers))
+        + signatures
+    )
+    return base64.b64encode(envelope).decode()
+
+
+server = ed25519_key("stellar-server")
+client = ed25519_key("stellar-client")
+client_domain = ed25519_key("stellar-client-domain")
+
+print("SERVER_ACCOUNT =", account_id(server[1]))
+print("CLIENT_ACCOUNT =", account_id(client[1]))
+print("ENVELOPE =", challenge(server[1], client[1], [server, client]))
+print("CLIENT_DOMAIN_ACCOUNT =", account_id(client_domain[1]))
+print(
+    "CLIENT_DOMAIN_ENVELOPE =",
+    challenge(server[1], client[1], [server, client_domain, client], client_domain[1]),
+)
@@ -0,0 +1,39 @@
+"""Independent ed25519 and ecdsa `signRaw` vectors for tests/substrate_integration_tests.rs.
+
+sr25519 signatures are randomized and have no independent implementation here, so
+only the deterministic key types are produced.
+"""
+
+import hashlib
+
+from common import CHALLENGE, base58, ed25519_key, secp256k1_key, secp256k1_public_key, secp256k1_sign
+
+
+def ss58(prefix, account_id):
+    """SS58 address: base58 of prefix || account ID || first 2 bytes of the checksum."""
+    data = bytes([prefix]) + account_id
+    checksum = hashlib.blake2b(b"SS58PRE" + data, digest_size=64).digest()[:2]
+    return base58(data + checksum)
+
+
+# Alice's well-known address on the generic prefix
+assert (
+    ss58(42, bytes.fromhex("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"))
+    == "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+)
+
+# polkadot.js wraps the message in <Bytes> before signing
+message = b"<Bytes>" + CHALLENGE.encode() + b"</Bytes>"
+
+# ed25519 signs the message itself; the account ID is the public key
+private_key, public_key = ed25519_key("substrate-ed25519")
+print("ED25519_ADDRESS =", ss58(42, public_key))
+print("ED25519_SIGNATURE =", "0x" + private_key.sign(message).hex())
+
+# ecdsa signs BLAKE2b-256 of the message; the account ID is BLAKE2b-256 of the
+# compressed public key, and the signature ends with the bare recovery ID
+secret = secp256k1_key("substrate-ecdsa")
+r, s, recovery_id = secp256k1_sign(secret, hashlib.blake2b(message, digest_size=32).digest())
+account_id = hashlib.blake2b(secp256k1_public_key(secret), digest_size=32).digest()
+print("ECDSA_ADDRESS =", ss58(0, account_id))
+print("ECDSA_SIGNATURE =", "0x" + (r.to_bytes(32, "big") + s.to_bytes(32, "big") + bytes([recovery_id])).hex())
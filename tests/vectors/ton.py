@@ -0,0 +1,122 @@
+"""Independent TON Connect `ton_proof` vector for tests/ton_integration_tests.rs."""
+
+import base64
+import hashlib
+import struct
+
+from common import ed25519_key
+
+DOMAIN = b"example.com"
+TIMESTAMP = 1760706960
+PAYLOAD = b"afba42ef0b9c4d31"
+SUBWALLET_ID = 698983191
+
+
+class Cell:
+    """Ordinary cell holding up to 1023 bits (as a '0'/'1' string) and 4 references."""
+
+    def __init__(self, bits, refs=()):
+        self.bits = bits
+        self.refs = list(refs)
+
+    def descriptors(self):
+        return bytes([len(self.refs), len(self.bits) // 8 + (len(self.bits) + 7) // 8])
+
+    def data(self):
+        bits = self.bits
+        if len(bits) % 8:
+            # Completion tag: a 1 bit, then zeros up to the byte boundary
+            bits += "1" + "0" * (-(len(bits) + 1) % 8)
+        return bytes(int(bits[i:i + 8], 2) for i in range(0, len(bits), 8))
+
+    def depth(self):
+        return 1 + max(ref.depth() for ref in self.refs) if self.refs else 0
+
+    def hash(self):
+        return hashlib.sha256(
+            self.descriptors()
+            + self.data()
+            + b"".join(struct.pack(">H", ref.depth()) for ref in self.refs)
+            + b"".join(ref.hash() for ref in self.refs)
+        ).digest()
+
+
+def uint(value, bits):
+    return format(value, "0%db" % bits)
+
+
+def byte_bits(data):
+    return "".join(uint(byte, 8) for byte in data)
+
+
+def crc32c(data):
+    crc = 0xFFFFFFFF
+    for byte in data:
+        crc ^= byte
+        for _ in range(8):
+            crc = (crc >> 1) ^ 0x82F63B78 if crc & 1 else crc >> 1
+    return crc ^ 0xFFFFFFFF
+
+
+def crc16_xmodem(data):
+    crc = 0
+    for byte in data:
+        crc ^= byte << 8
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x1021) & 0xFFFF if crc & 0x8000 else (crc << 1) & 0xFFFF
+    return crc
+
+
+def bag_of_cells(root):
+    """Single-root BoC with 1-byte references and offsets, and a CRC32C checksum."""
+    cells = [root]
+    for cell in cells:
+        cells.extend(cell.refs)
+    index = {id(cell): i for i, cell in enumerate(cells)}
+    serialized = b"".join(
+        cell.descriptors() + cell.data() + bytes(index[id(ref)] for ref in cell.refs)
+        for cell in cells
+    )
+    header = bytes.fromhex("b5ee9c72") + bytes([0x41, 1, len(cells), 1, 0, len(serialized), 0])
+    boc = header + serialized
+    return boc + struct.pack("<I", crc32c(boc))
+
+
+def wallet_state_init(public_key):
+    """Wallet v4 style state init: code and data refs, the data holding the key."""
+    code = Cell(byte_bits(b"wallet v4 code"))
+    data = Cell(uint(0, 32) + uint(SUBWALLET_ID, 32) + byte_bits(public_key) + "0")
+    return Cell(uint(0b00110, 5), [code, data])
+
+
+# Same state init as the crate's boc unit test, whose hash is pinned there
+_test_data = Cell(uint(0, 32) + uint(SUBWALLET_ID, 32) + byte_bits(b"\x42" * 32) + "0")
+_test_state_init = Cell(uint(0b00110, 5), [Cell(uint(0xDEADBEEF, 32)), _test_data])
+assert (
+    _test_state_init.hash().hex()
+    == "2a044c4e06803de3a983ff2a9e88090d9bd379980fea7bd6e3dc07a5e8fb76a8"
+)
+
+private_key, public_key = ed25519_key("ton")
+state_init = wallet_state_init(public_key)
+account_hash = state_init.hash()
+
+# Bounceable mainnet user-friendly address: tag, workchain, hash, CRC16
+friendly = bytes([0x11, 0]) + account_hash
+friendly += struct.pack(">H", crc16_xmodem(friendly))
+
+message = (
+    b"ton-proof-item-v2/"
+    + struct.pack(">i", 0)
+    + account_hash
+    + struct.pack("<I", len(DOMAIN))
+    + DOMAIN
+    + struct.pack("<Q", TIMESTAMP)
+    + PAYLOAD
+)
+signed = hashlib.sha256(b"\xff\xff" + b"ton-connect" + hashlib.sha256(message).digest()).digest()
+
+print("RAW_ADDRESS =", "0:" + account_hash.hex())
+print("FRIENDLY_ADDRESS =", base64.urlsafe_b64encode(friendly).decode())
+print("STATE_INIT =", base64.b64encode(bag_of_cells(state_init)).decode())
+print("SIGNATURE =", base64.b64encode(private_key.sign(signed)).decode())
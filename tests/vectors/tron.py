@@ -0,0 +1,44 @@
+"""Independent TIP-191 `signMessageV2` and v1 vectors for tests/tron_integration_tests.rs."""
+
+from common import (
+    CHALLENGE,
+    base58check,
+    keccak256,
+    secp256k1_key,
+    secp256k1_public_key,
+    secp256k1_sign,
+)
+
+
+def tron_address(public_key):
+    """`T...` address: base58check of 0x41 || last 20 bytes of Keccak-256(x || y)."""
+    return base58check(b"\x41" + keccak256(public_key[1:])[-20:])
+
+
+def sign(secret, digest):
+    """65-byte `r || s || v` signature with v = 27 + recovery id, as TronWeb returns it."""
+    r, s, recovery_id = secp256k1_sign(secret, digest)
+    return "0x" + (r.to_bytes(32, "big") + s.to_bytes(32, "big") + bytes([27 + recovery_id])).hex()
+
+
+secret = secp256k1_key("tron")
+address = tron_address(secp256k1_public_key(secret, compressed=False))
+
+# signMessageV2: the message is prefixed with its decimal length
+message = CHALLENGE.encode()
+v2_signature = sign(
+    secret, keccak256(b"\x19TRON Signed Message:\n" + str(len(message)).encode() + message)
+)
+
+# Legacy signMessage: a hex string is signed as 32 raw bytes with a fixed length of 32
+v1_challenge = bytes(range(32)).hex()
+v1_signature = sign(
+    secret, keccak256(b"\x19TRON Signed Message:\n32" + bytes.fromhex(v1_challenge))
+)
+
+print("ADDRESS =", address)
+print("V2_SIGNATURE =", v2_signature)
+print("V1_CHALLENGE =", v1_challenge)
+print("V1_SIGNATURE =", v1_signature)
+# Address of the generator point's key (secret 1), for comparison with other tools
+print("GENERATOR_ADDRESS =", tron_address(secp256k1_public_key(1, compressed=False)))
@@ -0,0 +1,59 @@
+"""Independent WebAuthn ES256 assertion vector for tests/webauthn_integration_tests.rs."""
+
+import base64
+import hashlib
+import json
+import struct
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+
+from common import CHALLENGE, cbor, seed
+
+RP_ID = b"example.com"
+ORIGIN = "https://example.com"
+FLAGS = 0x05  # user present, user verified
+SIGN_COUNT = 7
+
+
+def base64url(data):
+    return base64.urlsafe_b64encode(data).rstrip(b"=").decode()
+
+
+private_key = ec.derive_private_key(int.from_bytes(seed("webauthn"), "big"), ec.SECP256R1())
+numbers = private_key.public_key().public_numbers()
+# COSE_Key: EC2 (1), ES256 (-7), P-256 (1), x, y
+cose_key = cbor({1: 2, 3: -7, -1: 1, -2: numbers.x.to_bytes(32, "big"), -3: numbers.y.to_bytes(32, "big")})
+
+authenticator_data = hashlib.sha256(RP_ID).digest() + bytes([FLAGS]) + struct.pack(">I", SIGN_COUNT)
+client_data_json = json.dumps(
+    {
+        "type": "webauthn.get",
+        "challenge": base64url(CHALLENGE.encode()),
+        "origin": ORIGIN,
+        "crossOrigin": False,
+    },
+    separators=(",", ":"),
+).encode()
+# DER ECDSA over authenticatorData || SHA-256(clientDataJSON), with RFC 6979 nonces
+signature = private_key.sign(
+    authenticator_data + hashlib.sha256(client_data_json).digest(),
+    ec.ECDSA(hashes.SHA256(), deterministic_signing=True),
+)
+
+credential_id = base64url(hashlib.sha256(b"credential").digest()[:16])
+credential = {
+    "id": credential_id,
+    "rawId": credential_id,
+    "type": "public-key",
+    "response": {
+        "authenticatorData": base64url(authenticator_data),
+        "clientDataJSON": base64url(client_data_json),
+        "signature": base64url(signature),
+        "userHandle": None,
+    },
+}
+
+print("CREDENTIAL_ID =", credential_id)
+print("COSE_KEY =", base64url(cose_key))
+print("CREDENTIAL =", json.dumps(credential, separators=(",", ":")))
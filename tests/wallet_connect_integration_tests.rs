@@ -102,6 +102,10 @@ fn test_wallet_connect_wrong_challenge() {
 /// Test WalletConnect with invalid signature format (too short)
 #[test]
 fn test_wallet_connect_invalid_signature_length() {
+    use wallet_signature_verify::wallets::{
+        VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
     let invalid_signature = "0xe5092134a1e3a91dafe709"; // Too short
     let challenge = "test:challenge";
     let address = "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651";
@@ -112,6 +116,16 @@ fn test_wallet_connect_invalid_signature_length() {
         result.is_err(),
         "Invalid signature length should return error"
     );
+
+    // The provider reports the accepted range: 64 to 72 bytes
+    let input = VerificationInput {
+        signature_data: format!("0x{}", "11".repeat(73)),
+        expected_address: address.to_string(),
+        challenge: Some(challenge.to_string()),
+        ..Default::default()
+    };
+    let error = WalletConnectProvider.validate_input(&input).unwrap_err();
+    assert!(error.to_string().contains("64 to 72 bytes"), "{}", error);
 }
 
 /// Test WalletConnect with malformed signature hex
@@ -232,3 +246,540 @@ fn test_wallet_connect_eip191_format() {
         "Valid EIP-191 signature should verify"
     );
 }
+
+/// Signs `message` with EIP-191 personal_sign using a throwaway test key
+///
+/// Returns the 65-byte signature (hex, v = 27/28) and the checksummed signer address.
+fn personal_sign(message: &[u8], secret_byte: u8) -> (String, String) {
+    use ethers_core::utils::{hash_message, keccak256, to_checksum};
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(&[secret_byte; 32]).unwrap();
+    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+
+    let digest = hash_message(message);
+    let msg = Message::from_digest_slice(digest.as_bytes()).unwrap();
+    let (rec_id, compact) = secp
+        .sign_ecdsa_recoverable(&msg, &secret_key)
+        .serialize_compact();
+
+    let mut signature = compact.to_vec();
+    signature.push(27 + rec_id.to_i32() as u8);
+
+    let hash = keccak256(&public_key.serialize_uncompressed()[1..]);
+    let address = to_checksum(&ethers_core::types::Address::from_slice(&hash[12..]), None);
+
+    (format!("0x{}", hex::encode(signature)), address)
+}
+
+/// Test that a SIWE (CAIP-122 for eip155) challenge is parsed into the result
+#[test]
+fn test_wallet_connect_siwe_challenge_exposes_caip10_account() {
+    use wallet_signature_verify::caip::{AccountId, ChainId, SignInMessage};
+
+    let (_, address) = personal_sign(b"", 0x11);
+    let message = SignInMessage {
+        domain: "example.com".to_string(),
+        account: AccountId::new(ChainId::eip155(137), &address).unwrap(),
+        statement: Some("Sign in to Example".to_string()),
+        uri: "https://example.com/login".to_string(),
+        version: "1".to_string(),
+        nonce: "a1b2c3d4e5".to_string(),
+        issued_at: "2025-10-17T12:00:00Z".to_string(),
+        expiration_time: None,
+        not_before: None,
+        request_id: None,
+        resources: Vec::new(),
+    };
+    let challenge = message.render();
+    let (signature, _) = personal_sign(challenge.as_bytes(), 0x11);
+
+    let result = verify_evm_signature(&signature, &challenge, &address).unwrap();
+
+    assert!(result.is_valid(), "SIWE signature should verify");
+    assert_eq!(result.sign_in, Some(message));
+    assert_eq!(result.chain_id(), Some(&ChainId::eip155(137)));
+    assert_eq!(
+        result.caip10_account().map(|a| a.to_string()),
+        Some(format!("eip155:137:{}", address))
+    );
+}
+
+/// Test that a SIWE challenge naming another account than the signer is rejected
+#[test]
+fn test_wallet_connect_siwe_challenge_for_other_account() {
+    use wallet_signature_verify::caip::{AccountId, ChainId, SignInMessage};
+
+    let (_, other_address) = personal_sign(b"", 0x22);
+    let challenge = SignInMessage {
+        domain: "example.com".to_string(),
+        account: AccountId::new(ChainId::eip155(1), &other_address).unwrap(),
+        statement: None,
+        uri: "https://example.com/login".to_string(),
+        version: "1".to_string(),
+        nonce: "a1b2c3d4e5".to_string(),
+        issued_at: "2025-10-17T12:00:00Z".to_string(),
+        expiration_time: None,
+        not_before: None,
+        request_id: None,
+        resources: Vec::new(),
+    }
+    .render();
+    let (signature, address) = personal_sign(challenge.as_bytes(), 0x11);
+
+    let result = verify_evm_signature(&signature, &challenge, &address).unwrap();
+    assert!(result.signature_valid);
+    assert!(result.address_valid);
+    assert!(
+        !result.challenge_valid,
+        "Sign-in account must be the signer"
+    );
+    assert!(!result.is_valid());
+
+    // The account is compared case-insensitively for eip155
+    let lowercase = challenge.replace(&other_address, &other_address.to_lowercase());
+    let (signature, _) = personal_sign(lowercase.as_bytes(), 0x22);
+    let result = verify_evm_signature(&signature, &lowercase, &other_address).unwrap();
+    assert!(result.is_valid());
+}
+
+/// Test that plain colon-separated challenges are not mistaken for sign-in messages
+#[test]
+fn test_wallet_connect_legacy_challenge_has_no_caip10_account() {
+    let signature = "0xe5092134a1e3a91dafe7095916466a00d93fa01c540914fc3a010c05220281eb1f8fbcb34ce784875cd4a01cabef782c3c0f7e33d508410e957fb01c1c5b10071b";
+    let challenge = "nuff.tech:1760706960:afba42ef-fbb7-4504-8915-583046d6eb26:login:0x33f9D9f0348c1a4Bace2ad839903bBD47F430651";
+    let address = "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651";
+
+    let result = verify_evm_signature(signature, challenge, address).unwrap();
+
+    assert!(result.is_valid());
+    assert!(result.sign_in.is_none());
+    assert!(result.caip10_account().is_none());
+}
+
+/// Test EIP-712 typed-data verification through the WalletConnect provider
+#[test]
+fn test_wallet_connect_provider_eip712_signing_scheme() {
+    use wallet_signature_verify::wallets::{
+        EvmProvider, EvmSigningScheme, VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    // "Mail" example and "Cow" signature from the EIP-712 specification
+    let typed_data = r#"{"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"},{"name":"chainId","type":"uint256"},{"name":"verifyingContract","type":"address"}],"Person":[{"name":"name","type":"string"},{"name":"wallet","type":"address"}],"Mail":[{"name":"from","type":"Person"},{"name":"to","type":"Person"},{"name":"contents","type":"string"}]},"primaryType":"Mail","domain":{"name":"Ether Mail","version":"1","chainId":1,"verifyingContract":"0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"},"message":{"from":{"name":"Cow","wallet":"0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},"to":{"name":"Bob","wallet":"0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},"contents":"Hello, Bob!"}}"#;
+    let input = VerificationInput {
+        signature_data: "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c".to_string(),
+        expected_address: "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826".to_string(),
+        challenge: Some(typed_data.to_string()),
+        ..Default::default()
+    };
+
+    let typed_provider = EvmProvider::new().with_signing_scheme(EvmSigningScheme::TypedData);
+    assert!(
+        typed_provider.verify(&input).unwrap().is_valid(),
+        "EIP-712 signature should verify with the typed-data scheme"
+    );
+
+    // The same signature is not a valid personal_sign over the JSON text
+    let personal_provider = WalletConnectProvider;
+    assert!(!personal_provider.verify(&input).unwrap().is_valid());
+}
+
+/// Test EIP-1271 fallback for a smart-contract wallet owned by an EOA
+#[test]
+fn test_wallet_connect_provider_erc1271_fallback() {
+    use ethers_core::types::Address;
+    use std::sync::Arc;
+    use wallet_signature_verify::wallets::wallet_connect::erc1271::{
+        ContractWallet, InMemoryEvmState,
+    };
+    use wallet_signature_verify::wallets::{
+        EvmProvider, VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    let challenge = "example.com:1760706960:afba42ef:login:safe";
+    let (signature, owner) = personal_sign(challenge.as_bytes(), 0x33);
+
+    let safe_address = "0x5afe00000000000000000000000000000000cafe";
+    let state = Arc::new(InMemoryEvmState::new());
+    state.deploy(
+        safe_address.parse::<Address>().unwrap(),
+        ContractWallet {
+            owners: vec![owner.parse().unwrap()],
+            ..Default::default()
+        },
+    );
+
+    let input = VerificationInput {
+        signature_data: signature,
+        expected_address: safe_address.to_string(),
+        challenge: Some(challenge.to_string()),
+        ..Default::default()
+    };
+
+    // Plain ECDSA recovery yields the owner, not the contract
+    assert!(!WalletConnectProvider.verify(&input).unwrap().is_valid());
+
+    let provider = EvmProvider::new().with_erc1271_checker(state.clone());
+    let result = provider.verify(&input).unwrap();
+    assert!(
+        result.is_valid(),
+        "Contract wallet should accept owner signature"
+    );
+
+    // A signature from a non-owner is rejected by the contract
+    let (stranger_signature, _) = personal_sign(challenge.as_bytes(), 0x44);
+    let stranger_input = VerificationInput {
+        signature_data: stranger_signature,
+        ..input
+    };
+    assert!(!provider.verify(&stranger_input).unwrap().is_valid());
+}
+
+/// Test that a failing EIP-1271 checker does not mask the ECDSA result
+#[test]
+fn test_wallet_connect_provider_erc1271_checker_error() {
+    use ethers_core::types::{Address, H256};
+    use std::sync::Arc;
+    use wallet_signature_verify::wallets::wallet_connect::erc1271::Erc1271Checker;
+    use wallet_signature_verify::wallets::{EvmProvider, VerificationInput, WalletProvider};
+
+    struct Unreachable;
+
+    impl Erc1271Checker for Unreachable {
+        fn is_valid_signature(&self, _: Address, _: H256, _: &[u8]) -> anyhow::Result<bool> {
+            Err(anyhow::anyhow!("RPC unreachable"))
+        }
+    }
+
+    let challenge = "example.com:1760706960:afba42ef:login:rpc-down";
+    let (signature, address) = personal_sign(challenge.as_bytes(), 0x55);
+    let (_, other_address) = personal_sign(b"", 0x66);
+    let provider = EvmProvider::new().with_erc1271_checker(Arc::new(Unreachable));
+
+    let input = VerificationInput {
+        signature_data: signature,
+        expected_address: other_address,
+        challenge: Some(challenge.to_string()),
+        ..Default::default()
+    };
+    let result = provider.verify(&input).unwrap();
+    assert!(!result.address_valid);
+    assert_eq!(result.derived_address, address);
+
+    let input = VerificationInput {
+        expected_address: address,
+        ..input
+    };
+    assert!(provider.verify(&input).unwrap().is_valid());
+}
+
+/// Test EIP-6492 verification for a counterfactual (undeployed) smart account
+#[test]
+fn test_wallet_connect_provider_erc6492_counterfactual_account() {
+    use ethers_core::types::Address;
+    use std::sync::Arc;
+    use wallet_signature_verify::wallets::wallet_connect::erc1271::{
+        ContractWallet, InMemoryEvmState,
+    };
+    use wallet_signature_verify::wallets::wallet_connect::erc6492::Erc6492Signature;
+    use wallet_signature_verify::wallets::{
+        EvmProvider, VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    let challenge = "example.com:1760706960:afba42ef:login:smart-account";
+    let (owner_signature, owner) = personal_sign(challenge.as_bytes(), 0x55);
+
+    let account = "0x4337000000000000000000000000000000004337"
+        .parse::<Address>()
+        .unwrap();
+    let factory = "0xfac7000000000000000000000000000000000001"
+        .parse::<Address>()
+        .unwrap();
+    let factory_calldata = vec![0x5f, 0xbf, 0xb9, 0xcf, 0x01];
+
+    let state = Arc::new(InMemoryEvmState::new());
+    state.register_deployment(
+        factory,
+        factory_calldata.clone(),
+        account,
+        ContractWallet {
+            owners: vec![owner.parse().unwrap()],
+            ..Default::default()
+        },
+    );
+
+    let wrapped = Erc6492Signature {
+        factory,
+        factory_calldata,
+        inner_signature: hex::decode(owner_signature.trim_start_matches("0x")).unwrap(),
+    }
+    .wrap();
+
+    let input = VerificationInput {
+        signature_data: format!("0x{}", hex::encode(wrapped)),
+        expected_address: format!("{:?}", account),
+        challenge: Some(challenge.to_string()),
+        ..Default::default()
+    };
+
+    // Without a simulator the wrapped signature cannot be checked
+    assert!(WalletConnectProvider.verify(&input).is_err());
+
+    let provider = EvmProvider::new().with_erc6492_simulator(state.clone());
+    let result = provider.verify(&input).unwrap();
+    assert!(
+        result.is_valid(),
+        "Counterfactual account should accept owner signature"
+    );
+
+    // Simulation does not persist the deployment
+    assert!(!state.has_code(account));
+}
+
+/// Test that results report the EIP-55 checksummed address
+#[test]
+fn test_wallet_connect_derived_address_is_checksummed() {
+    let signature = "0xe5092134a1e3a91dafe7095916466a00d93fa01c540914fc3a010c05220281eb1f8fbcb34ce784875cd4a01cabef782c3c0f7e33d508410e957fb01c1c5b10071b";
+    let challenge = "nuff.tech:1760706960:afba42ef-fbb7-4504-8915-583046d6eb26:login:0x33f9D9f0348c1a4Bace2ad839903bBD47F430651";
+
+    let result = verify_evm_signature(
+        signature,
+        challenge,
+        "0x33f9d9f0348c1a4bace2ad839903bbd47f430651",
+    )
+    .unwrap();
+
+    assert!(result.is_valid());
+    assert_eq!(
+        result.derived_address,
+        "0x33f9D9f0348c1a4Bace2ad839903bBD47F430651"
+    );
+}
+
+/// Test that a mixed-case address with a broken EIP-55 checksum is rejected
+#[test]
+fn test_wallet_connect_bad_checksum_rejected() {
+    use wallet_signature_verify::wallets::{
+        ChecksumPolicy, EvmProvider, VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    let signature = "0xe5092134a1e3a91dafe7095916466a00d93fa01c540914fc3a010c05220281eb1f8fbcb34ce784875cd4a01cabef782c3c0f7e33d508410e957fb01c1c5b10071b";
+    let challenge = "nuff.tech:1760706960:afba42ef-fbb7-4504-8915-583046d6eb26:login:0x33f9D9f0348c1a4Bace2ad839903bBD47F430651";
+    // One letter's case flipped ("bBD4" -> "bbD4")
+    let mistyped = "0x33f9D9f0348c1a4Bace2ad839903bbD47F430651".to_string();
+
+    // The core function reports the mismatch instead of failing
+    let result = verify_evm_signature(signature, challenge, &mistyped).unwrap();
+    assert!(!result.address_valid);
+    assert!(result.signature_valid);
+    assert!(!result.is_valid());
+
+    let input = VerificationInput {
+        signature_data: signature.to_string(),
+        expected_address: mistyped,
+        challenge: Some(challenge.to_string()),
+        ..Default::default()
+    };
+    assert!(WalletConnectProvider.validate_input(&input).is_err());
+
+    // Lenient policy ignores the casing and still verifies
+    let lenient = EvmProvider::new().with_checksum_policy(ChecksumPolicy::Ignore);
+    assert!(lenient.verify(&input).unwrap().is_valid());
+
+    // Strict policy also rejects non-checksummed lowercase input
+    let strict = EvmProvider::new().with_checksum_policy(ChecksumPolicy::Require);
+    let lowercase = VerificationInput {
+        expected_address: "0x33f9d9f0348c1a4bace2ad839903bbd47f430651".to_string(),
+        ..input
+    };
+    assert!(strict.validate_input(&lowercase).is_err());
+}
+
+/// Test that 0/1, EIP-155 and EIP-2098 encodings of the same signature all verify
+#[test]
+fn test_wallet_connect_signature_encodings() {
+    use wallet_signature_verify::types::SignatureEncoding;
+
+    let challenge = "example.com:1760706960:afba42ef:login:ledger";
+    let (signature, address) = personal_sign(challenge.as_bytes(), 0x66);
+    let bytes = hex::decode(signature.trim_start_matches("0x")).unwrap();
+    let parity = bytes[64] - 27;
+
+    let raw = [&bytes[..64], &[parity]].concat();
+    let eip155 = [&bytes[..64], &[(56 * 2 + 35 + parity as u64) as u8]].concat();
+    let mut compact = bytes[..64].to_vec();
+    compact[32] |= parity << 7;
+
+    let cases = [
+        (bytes.clone(), SignatureEncoding::RecoverableLegacy),
+        (raw, SignatureEncoding::RecoverableRaw),
+        (eip155, SignatureEncoding::Eip155 { chain_id: 56 }),
+        (compact, SignatureEncoding::Eip2098),
+    ];
+
+    for (signature, expected_encoding) in cases {
+        let result = verify_evm_signature(&hex::encode(&signature), challenge, &address).unwrap();
+        assert!(result.is_valid(), "{:?} should verify", expected_encoding);
+        assert_eq!(result.signature_encoding, Some(expected_encoding));
+    }
+}
+
+/// Test that the provider accepts 64-byte EIP-2098 signatures
+#[test]
+fn test_wallet_connect_provider_accepts_compact_signature() {
+    use wallet_signature_verify::wallets::{
+        VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    let challenge = "example.com:1760706960:afba42ef:login:compact";
+    let (signature, address) = personal_sign(challenge.as_bytes(), 0x77);
+    let bytes = hex::decode(signature.trim_start_matches("0x")).unwrap();
+    let mut compact = bytes[..64].to_vec();
+    compact[32] |= (bytes[64] - 27) << 7;
+
+    let input = VerificationInput {
+        signature_data: format!("0x{}", hex::encode(compact)),
+        expected_address: address,
+        challenge: Some(challenge.to_string()),
+        ..Default::default()
+    };
+
+    assert!(WalletConnectProvider.verify(&input).unwrap().is_valid());
+}
+
+/// Test personal_sign over a `0x`-prefixed hex message (the wallet signs the decoded bytes)
+#[test]
+fn test_wallet_connect_hex_bytes_message() {
+    use wallet_signature_verify::types::MessageEncoding;
+    use wallet_signature_verify::wallets::{
+        VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    let message = b"example.com:1760706960:afba42ef:login:hex";
+    let challenge = format!("0x{}", hex::encode(message));
+    let (signature, address) = personal_sign(message, 0x88);
+
+    let input = VerificationInput {
+        signature_data: signature,
+        expected_address: address,
+        challenge: Some(challenge.clone()),
+        message_encoding: MessageEncoding::HexBytes,
+        ..Default::default()
+    };
+    let provider = WalletConnectProvider;
+
+    let result = provider.verify(&input).unwrap();
+    assert!(result.is_valid(), "Decoded hex bytes should verify");
+    assert_eq!(result.message_encoding, Some(MessageEncoding::HexBytes));
+    assert_eq!(result.found_challenge.as_deref(), Some(challenge.as_str()));
+
+    // Auto-detect resolves the same challenge to hex bytes
+    let auto = VerificationInput {
+        message_encoding: MessageEncoding::Auto,
+        ..input.clone()
+    };
+    let result = provider.verify(&auto).unwrap();
+    assert!(result.is_valid());
+    assert_eq!(result.message_encoding, Some(MessageEncoding::HexBytes));
+
+    // Hashing the hex text as UTF-8 recovers a different signer
+    let utf8 = VerificationInput {
+        message_encoding: MessageEncoding::Utf8,
+        ..input
+    };
+    let result = provider.verify(&utf8).unwrap();
+    assert!(!result.is_valid());
+    assert_eq!(result.message_encoding, Some(MessageEncoding::Utf8));
+}
+
+/// Test that auto-detect falls back to UTF-8 for plain text challenges
+#[test]
+fn test_wallet_connect_auto_encoding_plain_text() {
+    use wallet_signature_verify::types::MessageEncoding;
+    use wallet_signature_verify::wallets::{
+        VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    let challenge = "example.com:1760706960:afba42ef:login:auto";
+    let (signature, address) = personal_sign(challenge.as_bytes(), 0x99);
+
+    let input = VerificationInput {
+        signature_data: signature,
+        expected_address: address,
+        challenge: Some(challenge.to_string()),
+        message_encoding: MessageEncoding::Auto,
+        ..Default::default()
+    };
+
+    let result = WalletConnectProvider.verify(&input).unwrap();
+    assert!(result.is_valid());
+    assert_eq!(result.message_encoding, Some(MessageEncoding::Utf8));
+}
+
+/// Test that a hex-bytes challenge without valid hex is rejected
+#[test]
+fn test_wallet_connect_invalid_hex_message() {
+    use wallet_signature_verify::types::MessageEncoding;
+    use wallet_signature_verify::wallets::wallet_connect::core::verify_evm_signature_with_encoding;
+
+    let (signature, address) = personal_sign(b"anything", 0x99);
+
+    for challenge in ["not hex", "0xzz"] {
+        assert!(verify_evm_signature_with_encoding(
+            &signature,
+            challenge,
+            &address,
+            MessageEncoding::HexBytes
+        )
+        .is_err());
+    }
+}
+
+/// Test WalletConnect verification against a supplied public key (no recovery)
+#[test]
+fn test_wallet_connect_supplied_public_key() {
+    use secp256k1::{PublicKey, Secp256k1, SecretKey};
+    use wallet_signature_verify::wallets::{
+        VerificationInput, WalletConnectProvider, WalletProvider,
+    };
+
+    let challenge = "example.com:1760706960:afba42ef:login:pubkey";
+    let (signature, address) = personal_sign(challenge.as_bytes(), 0xaa);
+    let public_key = PublicKey::from_secret_key(
+        &Secp256k1::new(),
+        &SecretKey::from_slice(&[0xaa; 32]).unwrap(),
+    );
+    let uncompressed = public_key.serialize_uncompressed();
+
+    let provider = WalletConnectProvider;
+    for key in [
+        hex::encode(public_key.serialize()),
+        hex::encode(uncompressed),
+        format!("0x{}", hex::encode(&uncompressed[1..])),
+    ] {
+        let input = VerificationInput {
+            signature_data: signature.clone(),
+            expected_address: address.clone(),
+            challenge: Some(challenge.to_string()),
+            public_key: Some(key),
+            ..Default::default()
+        };
+        let result = provider.verify(&input).unwrap();
+        assert!(result.is_valid(), "Supplied key should verify");
+        assert_eq!(result.derived_address, address);
+    }
+
+    // A key for another account fails the address check
+    let (_, other_address) = personal_sign(challenge.as_bytes(), 0xab);
+    let input = VerificationInput {
+        signature_data: signature,
+        expected_address: other_address,
+        challenge: Some(challenge.to_string()),
+        public_key: Some(hex::encode(public_key.serialize())),
+        ..Default::default()
+    };
+    let result = provider.verify(&input).unwrap();
+    assert!(!result.address_valid);
+    assert!(result.signature_valid);
+}
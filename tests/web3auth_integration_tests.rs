@@ -140,3 +140,445 @@ fn test_web3auth_address_format() {
         "XRPL address length should be valid"
     );
 }
+
+/// Test that a CAIP-122 XRPL sign-in challenge is parsed into the result
+#[test]
+fn test_web3auth_sign_in_challenge_exposes_caip10_account() {
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+    use wallet_signature_verify::caip::{AccountId, ChainId, SignInMessage};
+    use wallet_signature_verify::crypto::{account_id_from_pubkey, sha512half};
+
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(&[0x22; 32]).unwrap();
+    let pubkey = PublicKey::from_secret_key(&secp, &secret_key).serialize();
+    let address = ripple_address_codec::encode_account_id(&account_id_from_pubkey(&pubkey));
+
+    let message = SignInMessage {
+        domain: "example.com".to_string(),
+        account: AccountId::new(ChainId::xrpl(0), &address).unwrap(),
+        statement: None,
+        uri: "https://example.com".to_string(),
+        version: "1".to_string(),
+        nonce: "57e06102".to_string(),
+        issued_at: "2025-10-17T12:00:00Z".to_string(),
+        expiration_time: None,
+        not_before: None,
+        request_id: None,
+        resources: Vec::new(),
+    };
+    let challenge = message.render();
+
+    let digest = sha512half(challenge.as_bytes());
+    let signature = secp.sign_ecdsa(&Message::from_digest_slice(&digest).unwrap(), &secret_key);
+    let signature_hex = hex::encode(signature.serialize_der());
+
+    let result = verify_web3auth_signature(&signature_hex, &challenge, &address).unwrap();
+
+    assert!(result.is_valid(), "Signed sign-in message should verify");
+    assert_eq!(result.chain_id(), Some(&ChainId::xrpl(0)));
+    assert_eq!(
+        result.caip10_account().map(|a| a.to_string()),
+        Some(format!("xrpl:0:{}", address))
+    );
+}
+
+/// Signs `challenge` hashed with `scheme` using a test key, returning (DER hex, XRPL address)
+fn sign_with_scheme(
+    challenge: &str,
+    scheme: wallet_signature_verify::types::MessageHashScheme,
+    secret_byte: u8,
+) -> (String, String) {
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+    use wallet_signature_verify::crypto::account_id_from_pubkey;
+    use wallet_signature_verify::wallets::web3auth::core::hash_challenge_for_web3auth;
+
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(&[secret_byte; 32]).unwrap();
+    let pubkey = PublicKey::from_secret_key(&secp, &secret_key).serialize();
+    let address = ripple_address_codec::encode_account_id(&account_id_from_pubkey(&pubkey));
+
+    let digest = hash_challenge_for_web3auth(challenge, scheme).unwrap();
+    let signature = secp.sign_ecdsa(&Message::from_digest_slice(&digest).unwrap(), &secret_key);
+
+    (hex::encode(signature.serialize_der()), address)
+}
+
+/// Test that every hash scheme verifies when selected explicitly and via auto-detect
+#[test]
+fn test_web3auth_hash_schemes() {
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::web3auth::core::{
+        hash_challenge_for_web3auth, verify_web3auth_signature_with_scheme,
+    };
+
+    let challenge = "example.com:1760706960:afba42ef:login:web3auth";
+
+    for scheme in MessageHashScheme::KNOWN {
+        let (signature, address) = sign_with_scheme(challenge, scheme, 0x33);
+
+        let explicit =
+            verify_web3auth_signature_with_scheme(&signature, challenge, &address, scheme).unwrap();
+        assert!(explicit.is_valid(), "{:?} should verify", scheme);
+        assert_eq!(explicit.hash_scheme, Some(scheme));
+
+        let auto = verify_web3auth_signature_with_scheme(
+            &signature,
+            challenge,
+            &address,
+            MessageHashScheme::Auto,
+        )
+        .unwrap();
+        assert!(auto.is_valid(), "{:?} should be auto-detected", scheme);
+        assert_eq!(auto.hash_scheme, Some(scheme));
+    }
+
+    // Auto is a selection of schemes, not a hash of its own
+    assert!(hash_challenge_for_web3auth(challenge, MessageHashScheme::Auto).is_err());
+}
+
+/// Test that a signature is rejected when verified with a different hash scheme
+#[test]
+fn test_web3auth_wrong_hash_scheme() {
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::web3auth::core::verify_web3auth_signature_with_scheme;
+
+    let challenge = "example.com:1760706960:afba42ef:login:web3auth";
+    let (signature, address) = sign_with_scheme(challenge, MessageHashScheme::Keccak256, 0x44);
+
+    let result = verify_web3auth_signature_with_scheme(
+        &signature,
+        challenge,
+        &address,
+        MessageHashScheme::Sha256,
+    )
+    .unwrap();
+
+    assert!(!result.is_valid());
+    assert_eq!(result.hash_scheme, None);
+}
+
+/// Test that the provider uses the hash scheme from the verification input
+#[test]
+fn test_web3auth_provider_hash_scheme_from_input() {
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::{VerificationInput, WalletProvider, Web3AuthProvider};
+
+    let challenge = "example.com:1760706960:afba42ef:login:eip191";
+    let (signature, address) = sign_with_scheme(challenge, MessageHashScheme::Eip191, 0x55);
+
+    let input = VerificationInput {
+        signature_data: signature,
+        expected_address: address,
+        challenge: Some(challenge.to_string()),
+        hash_scheme: MessageHashScheme::Eip191,
+        ..Default::default()
+    };
+    assert!(Web3AuthProvider.verify(&input).unwrap().is_valid());
+
+    // The default (SHA-512Half) does not match an EIP-191 signature
+    let default_scheme = VerificationInput {
+        hash_scheme: MessageHashScheme::default(),
+        ..input
+    };
+    assert!(!Web3AuthProvider.verify(&default_scheme).unwrap().is_valid());
+}
+
+/// Test that DER, compact and recoverable encodings of the same signature all verify
+#[test]
+fn test_web3auth_signature_formats() {
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+    use wallet_signature_verify::crypto::{account_id_from_pubkey, sha512half};
+    use wallet_signature_verify::types::SignatureEncoding;
+
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(&[0x66; 32]).unwrap();
+    let pubkey = PublicKey::from_secret_key(&secp, &secret_key).serialize();
+    let address = ripple_address_codec::encode_account_id(&account_id_from_pubkey(&pubkey));
+
+    let challenge = "example.com:1760706960:afba42ef:login:formats";
+    let message = Message::from_digest_slice(&sha512half(challenge.as_bytes())).unwrap();
+    let (recovery_id, compact) = secp
+        .sign_ecdsa_recoverable(&message, &secret_key)
+        .serialize_compact();
+    let recovery_id = recovery_id.to_i32() as u8;
+    let der = secp.sign_ecdsa(&message, &secret_key).serialize_der();
+
+    let cases = [
+        (der.to_vec(), SignatureEncoding::Der),
+        (compact.to_vec(), SignatureEncoding::Compact),
+        (
+            [&compact[..], &[recovery_id]].concat(),
+            SignatureEncoding::RecoverableRaw,
+        ),
+        (
+            [&compact[..], &[recovery_id + 27]].concat(),
+            SignatureEncoding::RecoverableLegacy,
+        ),
+    ];
+
+    for (signature, expected_encoding) in cases {
+        let result =
+            verify_web3auth_signature(&hex::encode(signature), challenge, &address).unwrap();
+        assert!(result.is_valid(), "{:?} should verify", expected_encoding);
+        assert_eq!(result.signature_encoding, Some(expected_encoding));
+    }
+}
+
+/// Test that a recoverable signature with the wrong recovery ID does not match
+#[test]
+fn test_web3auth_recoverable_wrong_recovery_id() {
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+    use wallet_signature_verify::crypto::{account_id_from_pubkey, sha512half};
+
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(&[0x77; 32]).unwrap();
+    let pubkey = PublicKey::from_secret_key(&secp, &secret_key).serialize();
+    let address = ripple_address_codec::encode_account_id(&account_id_from_pubkey(&pubkey));
+
+    let challenge = "example.com:1760706960:afba42ef:login:recid";
+    let message = Message::from_digest_slice(&sha512half(challenge.as_bytes())).unwrap();
+    let (recovery_id, compact) = secp
+        .sign_ecdsa_recoverable(&message, &secret_key)
+        .serialize_compact();
+    let flipped = (recovery_id.to_i32() as u8) ^ 1;
+
+    let signature = hex::encode([&compact[..], &[flipped]].concat());
+    let result = verify_web3auth_signature(&signature, challenge, &address).unwrap();
+
+    assert!(
+        !result.is_valid(),
+        "Recovery ID is used as given, not brute-forced"
+    );
+}
+
+/// Test that signatures in an unknown format are rejected
+#[test]
+fn test_web3auth_unknown_signature_format() {
+    let challenge = "test:challenge";
+    let address = "rExampleAddress123456789XXXXXXXXXXX";
+
+    // 65 bytes with an out-of-range recovery byte, and a 40-byte blob
+    let bad_v = format!("{}{}", "11".repeat(64), "05");
+    let short = "11".repeat(40);
+
+    assert!(verify_web3auth_signature(&bad_v, challenge, address).is_err());
+    assert!(verify_web3auth_signature(&short, challenge, address).is_err());
+}
+
+/// Signs the SHA-512Half of `challenge` with a test ed25519 key, returning
+/// (signature hex, raw public key hex, XRPL address)
+fn sign_ed25519(challenge: &str, secret_byte: u8) -> (String, String, String) {
+    use ed25519_dalek::{Signer, SigningKey};
+    use wallet_signature_verify::crypto::{account_id_from_pubkey, sha512half};
+
+    let signing_key = SigningKey::from_bytes(&[secret_byte; 32]);
+    let public_key = signing_key.verifying_key().to_bytes();
+    let xrpl_key = [&[0xED], &public_key[..]].concat();
+    let address = ripple_address_codec::encode_account_id(&account_id_from_pubkey(&xrpl_key));
+
+    let signature = signing_key.sign(&sha512half(challenge.as_bytes()));
+
+    (
+        hex::encode(signature.to_bytes()),
+        hex::encode(public_key),
+        address,
+    )
+}
+
+/// Test Web3Auth ed25519 verification with a supplied public key
+#[test]
+fn test_web3auth_ed25519_signature() {
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::{VerificationInput, WalletProvider, Web3AuthProvider};
+
+    let challenge = "example.com:1760706960:afba42ef:login:ed25519";
+    let (signature, public_key, address) = sign_ed25519(challenge, 0x11);
+    assert!(address.starts_with('r'));
+
+    let input = VerificationInput {
+        signature_data: signature,
+        expected_address: address.clone(),
+        challenge: Some(challenge.to_string()),
+        public_key: Some(public_key.clone()),
+        ..Default::default()
+    };
+
+    let result = Web3AuthProvider.verify(&input).unwrap();
+    assert!(result.is_valid(), "Ed25519 signature should verify");
+    assert_eq!(result.derived_address, address);
+    assert_eq!(result.hash_scheme, Some(MessageHashScheme::Sha512Half));
+
+    // The 33-byte XRPL form of the key is accepted too
+    let xrpl_form = VerificationInput {
+        public_key: Some(format!("ED{}", public_key.to_uppercase())),
+        ..input
+    };
+    assert!(Web3AuthProvider.verify(&xrpl_form).unwrap().is_valid());
+}
+
+/// Test Web3Auth ed25519 verification of a signature over the raw challenge bytes
+#[test]
+fn test_web3auth_ed25519_raw_message_signature() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use wallet_signature_verify::types::{MessageEncoding, MessageHashScheme};
+    use wallet_signature_verify::wallets::web3auth::ed25519::verify_web3auth_ed25519_signature;
+
+    let challenge = "example.com:1760706960:afba42ef:login:ed25519-raw";
+    let (_, public_key, address) = sign_ed25519(challenge, 0x44);
+    let signature = SigningKey::from_bytes(&[0x44; 32]).sign(challenge.as_bytes());
+
+    let result = verify_web3auth_ed25519_signature(
+        &hex::encode(signature.to_bytes()),
+        &public_key,
+        challenge,
+        &address,
+        MessageHashScheme::Sha512Half,
+    )
+    .unwrap();
+
+    assert!(result.is_valid(), "Raw-message signature should verify");
+    assert_eq!(result.hash_scheme, None);
+    assert_eq!(result.message_encoding, Some(MessageEncoding::Utf8));
+}
+
+/// Test that an ed25519 key for a different address is rejected
+#[test]
+fn test_web3auth_ed25519_address_mismatch() {
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::web3auth::ed25519::verify_web3auth_ed25519_signature;
+
+    let challenge = "example.com:1760706960:afba42ef:login:ed25519";
+    let (signature, public_key, _) = sign_ed25519(challenge, 0x11);
+    let (_, _, other_address) = sign_ed25519(challenge, 0x22);
+
+    let result = verify_web3auth_ed25519_signature(
+        &signature,
+        &public_key,
+        challenge,
+        &other_address,
+        MessageHashScheme::Sha512Half,
+    )
+    .unwrap();
+
+    assert!(
+        !result.address_valid,
+        "Key must derive to the expected address"
+    );
+    assert!(result.signature_valid);
+    assert!(!result.is_valid());
+}
+
+/// Test that an ed25519 signature over a different challenge is rejected
+#[test]
+fn test_web3auth_ed25519_wrong_challenge() {
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::web3auth::ed25519::verify_web3auth_ed25519_signature;
+
+    let (signature, public_key, address) = sign_ed25519("original:challenge", 0x33);
+
+    let result = verify_web3auth_ed25519_signature(
+        &signature,
+        &public_key,
+        "tampered:challenge",
+        &address,
+        MessageHashScheme::Auto,
+    )
+    .unwrap();
+
+    assert!(result.address_valid);
+    assert!(!result.signature_valid);
+    assert_eq!(result.hash_scheme, None);
+}
+
+/// Test Web3Auth verification against a supplied secp256k1 public key (no recovery)
+#[test]
+fn test_web3auth_supplied_secp256k1_public_key() {
+    use secp256k1::{PublicKey, Secp256k1, SecretKey};
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::{VerificationInput, WalletProvider, Web3AuthProvider};
+
+    let challenge = "example.com:1760706960:afba42ef:login:pubkey";
+    let (signature, address) = sign_with_scheme(challenge, MessageHashScheme::Sha256, 0x88);
+    let public_key = PublicKey::from_secret_key(
+        &Secp256k1::new(),
+        &SecretKey::from_slice(&[0x88; 32]).unwrap(),
+    );
+
+    // Compressed and uncompressed forms both derive the XRPL address
+    for key in [
+        hex::encode(public_key.serialize()),
+        hex::encode(public_key.serialize_uncompressed()),
+    ] {
+        let input = VerificationInput {
+            signature_data: signature.clone(),
+            expected_address: address.clone(),
+            challenge: Some(challenge.to_string()),
+            hash_scheme: MessageHashScheme::Auto,
+            public_key: Some(key),
+            ..Default::default()
+        };
+        let result = Web3AuthProvider.verify(&input).unwrap();
+        assert!(result.is_valid(), "Supplied key should verify");
+        assert_eq!(result.hash_scheme, Some(MessageHashScheme::Sha256));
+        assert_eq!(result.derived_address, address);
+    }
+}
+
+/// Test that a supplied key for another account or an invalid key is rejected
+#[test]
+fn test_web3auth_supplied_public_key_mismatch() {
+    use secp256k1::{PublicKey, Secp256k1, SecretKey};
+    use wallet_signature_verify::types::MessageHashScheme;
+    use wallet_signature_verify::wallets::web3auth::core::verify_web3auth_signature_with_public_key;
+
+    let challenge = "example.com:1760706960:afba42ef:login:pubkey";
+    let (signature, address) = sign_with_scheme(challenge, MessageHashScheme::Sha512Half, 0x99);
+    let other_key = PublicKey::from_secret_key(
+        &Secp256k1::new(),
+        &SecretKey::from_slice(&[0x98; 32]).unwrap(),
+    );
+
+    let result = verify_web3auth_signature_with_public_key(
+        &signature,
+        &hex::encode(other_key.serialize()),
+        challenge,
+        &address,
+        MessageHashScheme::Sha512Half,
+    )
+    .unwrap();
+    assert!(!result.address_valid);
+    assert!(!result.signature_valid);
+
+    let error = verify_web3auth_signature_with_public_key(
+        &signature,
+        &format!("02{}", "00".repeat(32)),
+        challenge,
+        &address,
+        MessageHashScheme::Sha512Half,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("Invalid secp256k1 public key"));
+}
+
+/// Test that results report the signing key's XRPL, EVM and Bitcoin addresses
+#[test]
+fn test_web3auth_derived_addresses() {
+    use ethers_core::{k256::ecdsa::SigningKey, utils::secret_key_to_address, utils::to_checksum};
+    use wallet_signature_verify::types::MessageHashScheme;
+
+    let challenge = "example.com:1760706960:afba42ef:login:multichain";
+    let (signature, address) = sign_with_scheme(challenge, MessageHashScheme::Sha512Half, 0x12);
+
+    let result = verify_web3auth_signature(&signature, challenge, &address).unwrap();
+    assert!(result.is_valid());
+
+    let addresses = result.derived_addresses.expect("Derived addresses");
+    let evm_address =
+        secret_key_to_address(&SigningKey::from_bytes((&[0x12u8; 32]).into()).unwrap());
+
+    assert_eq!(addresses.xrpl, address);
+    assert_eq!(addresses.evm, Some(to_checksum(&evm_address, None)));
+    assert!(addresses
+        .bitcoin_p2pkh
+        .is_some_and(|btc| btc.starts_with('1')));
+}
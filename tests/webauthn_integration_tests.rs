@@ -0,0 +1,272 @@
+//! Integration tests for WebAuthn (passkey) assertion verification
+//!
+//! Authenticators sign `authenticatorData || SHA-256(clientDataJSON)` with the P-256 key
+//! registered for the credential, which the relying party stored as a COSE key. The
+//! tests assemble assertions the way a browser does and check the challenge, origin,
+//! RP ID hash, user flags and credential key; `tests/vectors/webauthn.py` encodes its
+//! COSE key and signs its assertion with pyca/cryptography.
+
+mod common;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL, Engine as _};
+use common::{p256_key, CHALLENGE};
+use p256::ecdsa::{signature::Signer as _, Signature};
+use sha2::{Digest, Sha256};
+use wallet_signature_verify::cbor::CborValue;
+use wallet_signature_verify::wallets::{
+    VerificationInput, WalletProvider, WalletType, WebAuthnProvider,
+};
+
+const RP_ID: &str = "example.com";
+const ORIGIN: &str = "https://example.com";
+const CREDENTIAL_ID: &str = "Y3JlZGVudGlhbC0x";
+
+/// Registered COSE key (base64url) of the key for `secret_byte`
+fn cose_key(secret_byte: u8) -> String {
+    let point = p256_key(secret_byte)
+        .verifying_key()
+        .to_encoded_point(false);
+    let cose_key = CborValue::Map(vec![
+        (CborValue::Int(1), CborValue::Int(2)),  // kty: EC2
+        (CborValue::Int(3), CborValue::Int(-7)), // alg: ES256
+        (CborValue::Int(-1), CborValue::Int(1)), // crv: P-256
+        (
+            CborValue::Int(-2),
+            CborValue::Bytes(point.x().unwrap().to_vec()),
+        ),
+        (
+            CborValue::Int(-3),
+            CborValue::Bytes(point.y().unwrap().to_vec()),
+        ),
+    ]);
+    BASE64_URL.encode(cose_key.encode())
+}
+
+/// Produces the `PublicKeyCredential` JSON of an assertion like a browser does
+fn assert_credential(
+    secret_byte: u8,
+    challenge: &str,
+    origin: &str,
+    rp_id: &str,
+    flags: u8,
+) -> String {
+    let mut authenticator_data = Sha256::digest(rp_id.as_bytes()).to_vec();
+    authenticator_data.push(flags);
+    authenticator_data.extend_from_slice(&1u32.to_be_bytes());
+    let client_data_json = serde_json::json!({
+        "type": "webauthn.get",
+        "challenge": BASE64_URL.encode(challenge),
+        "origin": origin,
+        "crossOrigin": false,
+    })
+    .to_string();
+
+    let mut signed = authenticator_data.clone();
+    signed.extend_from_slice(&Sha256::digest(client_data_json.as_bytes()));
+    let signature: Signature = p256_key(secret_byte).sign(&signed);
+
+    serde_json::json!({
+        "id": CREDENTIAL_ID,
+        "rawId": CREDENTIAL_ID,
+        "type": "public-key",
+        "response": {
+            "authenticatorData": BASE64_URL.encode(&authenticator_data),
+            "clientDataJSON": BASE64_URL.encode(client_data_json),
+            "signature": BASE64_URL.encode(signature.to_der()),
+            "userHandle": null,
+        },
+    })
+    .to_string()
+}
+
+/// Provider configured for the test relying party
+fn provider() -> WebAuthnProvider {
+    WebAuthnProvider::new()
+        .with_rp_id(RP_ID)
+        .with_allowed_origin(ORIGIN)
+}
+
+/// Test a valid passkey assertion
+#[test]
+fn test_webauthn_valid_assertion() {
+    let input = VerificationInput {
+        signature_data: assert_credential(0x01, CHALLENGE, ORIGIN, RP_ID, 0x05),
+        expected_address: CREDENTIAL_ID.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(0x01)),
+        ..Default::default()
+    };
+
+    let result = provider()
+        .with_user_verification(true)
+        .verify(&input)
+        .unwrap();
+    assert!(result.is_valid(), "Valid assertion should verify");
+    assert_eq!(result.found_challenge.as_deref(), Some(CHALLENGE));
+
+    // Without a relying party there is nothing to check the assertion against, so
+    // WebAuthn is library-only
+    assert!(WebAuthnProvider::new().verify(&input).is_err());
+    assert!(WalletType::from_str("passkey").is_err());
+}
+
+/// Test that an assertion whose clientDataJSON carries another challenge is rejected
+#[test]
+fn test_webauthn_wrong_challenge() {
+    let input = VerificationInput {
+        signature_data: assert_credential(0x02, "original challenge", ORIGIN, RP_ID, 0x01),
+        expected_address: CREDENTIAL_ID.to_string(),
+        challenge: Some("tampered challenge".to_string()),
+        public_key: Some(cose_key(0x02)),
+        ..Default::default()
+    };
+    let result = provider().verify(&input).unwrap();
+
+    assert!(!result.is_valid());
+    assert!(!result.challenge_valid);
+    assert!(result.signature_valid);
+}
+
+/// Test that the origin, RP ID hash and user flags are enforced
+#[test]
+fn test_webauthn_relying_party_checks() {
+    let verify = |credential: String| {
+        let input = VerificationInput {
+            signature_data: credential,
+            expected_address: CREDENTIAL_ID.to_string(),
+            challenge: Some(CHALLENGE.to_string()),
+            public_key: Some(cose_key(0x03)),
+            ..Default::default()
+        };
+        provider().verify(&input).unwrap()
+    };
+
+    let phishing_origin = verify(assert_credential(
+        0x03,
+        CHALLENGE,
+        "https://examp1e.com",
+        RP_ID,
+        0x01,
+    ));
+    assert!(!phishing_origin.challenge_valid);
+
+    let other_rp = verify(assert_credential(0x03, CHALLENGE, ORIGIN, "evil.com", 0x01));
+    assert!(!other_rp.challenge_valid);
+
+    let user_absent = verify(assert_credential(0x03, CHALLENGE, ORIGIN, RP_ID, 0x00));
+    assert!(!user_absent.challenge_valid);
+    assert!(user_absent.signature_valid);
+
+    let input = VerificationInput {
+        signature_data: assert_credential(0x03, CHALLENGE, ORIGIN, RP_ID, 0x01),
+        expected_address: CREDENTIAL_ID.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(0x03)),
+        ..Default::default()
+    };
+    assert!(provider().verify(&input).unwrap().is_valid());
+    assert!(!provider()
+        .with_user_verification(true)
+        .verify(&input)
+        .unwrap()
+        .is_valid());
+}
+
+/// Test that another credential's key or ID is rejected
+#[test]
+fn test_webauthn_wrong_key_and_credential() {
+    let credential = assert_credential(0x04, CHALLENGE, ORIGIN, RP_ID, 0x01);
+
+    let wrong_key = VerificationInput {
+        signature_data: credential.clone(),
+        expected_address: CREDENTIAL_ID.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(0x05)),
+        ..Default::default()
+    };
+    let result = provider().verify(&wrong_key).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.signature_valid);
+
+    let wrong_credential = VerificationInput {
+        signature_data: credential,
+        expected_address: "b3RoZXItY3JlZGVudGlhbA".to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(0x04)),
+        ..Default::default()
+    };
+    let result = provider().verify(&wrong_credential).unwrap();
+    assert!(!result.is_valid());
+    assert!(!result.address_valid);
+}
+
+/// Test that a public key, challenge, RP ID and allowed origin are required
+#[test]
+fn test_webauthn_provider_validation() {
+    let credential = assert_credential(0x06, CHALLENGE, ORIGIN, RP_ID, 0x01);
+
+    let missing_key = VerificationInput {
+        signature_data: credential.clone(),
+        expected_address: CREDENTIAL_ID.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        ..Default::default()
+    };
+    assert!(provider().verify(&missing_key).is_err());
+
+    let missing_challenge = VerificationInput {
+        signature_data: credential,
+        expected_address: CREDENTIAL_ID.to_string(),
+        public_key: Some(cose_key(0x06)),
+        ..Default::default()
+    };
+    assert!(provider().verify(&missing_challenge).is_err());
+
+    let input = VerificationInput {
+        signature_data: assert_credential(0x06, CHALLENGE, ORIGIN, RP_ID, 0x01),
+        expected_address: CREDENTIAL_ID.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(cose_key(0x06)),
+        ..Default::default()
+    };
+    assert!(provider().verify(&input).unwrap().is_valid());
+    assert!(WebAuthnProvider::new()
+        .with_allowed_origin(ORIGIN)
+        .verify(&input)
+        .is_err());
+    assert!(WebAuthnProvider::new()
+        .with_rp_id(RP_ID)
+        .verify(&input)
+        .is_err());
+}
+
+/// Test a passkey assertion and COSE key produced outside this crate by
+/// `tests/vectors/webauthn.py`
+#[test]
+fn test_webauthn_independent_vector() {
+    const CREDENTIAL_ID: &str = "4mW29WRgGh_o3EJ4XNGKhg";
+    const COSE_KEY: &str = "pQECAyYgASFYIOFXJ5eXoUOgS4MpJCdbCFozwrf1yrNl-g9rq8RPrZZuIlgg7nWlS93C6r598v2q_yP2gs7-WLyZp5mjifBydXaL6aU";
+    const CREDENTIAL: &str = r#"{"id":"4mW29WRgGh_o3EJ4XNGKhg","rawId":"4mW29WRgGh_o3EJ4XNGKhg","type":"public-key","response":{"authenticatorData":"o3mm9u6vuaVeN4wRgDTidR5oL6ufLTCrE9ISVYbOGUcFAAAABw","clientDataJSON":"eyJ0eXBlIjoid2ViYXV0aG4uZ2V0IiwiY2hhbGxlbmdlIjoiWlhoaGJYQnNaUzVqYjIwNk1UYzJNRGN3TmprMk1EcGhabUpoTkRKbFpqcHNiMmRwYmciLCJvcmlnaW4iOiJodHRwczovL2V4YW1wbGUuY29tIiwiY3Jvc3NPcmlnaW4iOmZhbHNlfQ","signature":"MEQCIEbYxuIeu_2opRyOjgYfw6uXVKIklWp21vd_qzzMIHGFAiBcJWOrndTCfMJqfBw1IWUFk5tUHWUxyb9YymBe-sALxA","userHandle":null}}"#;
+
+    let input = VerificationInput {
+        signature_data: CREDENTIAL.to_string(),
+        expected_address: CREDENTIAL_ID.to_string(),
+        challenge: Some(CHALLENGE.to_string()),
+        public_key: Some(COSE_KEY.to_string()),
+        ..Default::default()
+    };
+    let result = provider()
+        .with_user_verification(true)
+        .verify(&input)
+        .unwrap();
+    assert!(
+        result.is_valid(),
+        "Independent WebAuthn vector should verify"
+    );
+
+    let other_rp = WebAuthnProvider::new()
+        .with_rp_id("other.example")
+        .with_allowed_origin(ORIGIN)
+        .verify(&input)
+        .unwrap();
+    assert!(!other_rp.is_valid());
+}
@@ -207,3 +207,31 @@ fn test_xaman_verification_is_deterministic() {
         assert_eq!(result.derived_address, address);
     }
 }
+
+/// Test that a supplied public key must match the blob's SigningPubKey
+#[test]
+fn test_xaman_supplied_public_key() {
+    use wallet_signature_verify::wallets::{VerificationInput, WalletProvider, XamanProvider};
+
+    let signed_hex = "732102ACE0AE76CC7DA925442A417FA3618811B5043A66566C9909503D22A96514B2B87446304402207E22A82A87D5FCBFBD63BA78E078DFF6708923F90F96696C978B7C00FF4C71870220547C1C18010E8EA93E0D9D187DF4D93480C43CF19D54A49E69E12ED49CCE17D28114717251C1BFE144D8E3577777E6D04E1101E87336F9EA7C04417574687D636578616D706C652E636F6D3A313736303037393239303A35376530363130322D633063382D346366382D626539372D3533306332353135613535643A73796E6B3A72424C694A6A6E4768517238743144555358576676634E577858356D506956535755E1F1";
+
+    let input = VerificationInput {
+        signature_data: signed_hex.to_string(),
+        expected_address: "rBLiJjnGhQr8t1DUSXWfvcNWxX5mPiVSWU".to_string(),
+        challenge: None,
+        public_key: Some(
+            "02ace0ae76cc7da925442a417fa3618811b5043a66566c9909503d22a96514b2b8".to_string(),
+        ),
+        ..Default::default()
+    };
+    assert!(XamanProvider.verify(&input).unwrap().is_valid());
+
+    let other_key = VerificationInput {
+        public_key: Some(
+            "03ACE0AE76CC7DA925442A417FA3618811B5043A66566C9909503D22A96514B2B8".to_string(),
+        ),
+        ..input
+    };
+    let error = XamanProvider.verify(&other_key).unwrap_err();
+    assert!(error.to_string().contains("does not match"));
+}